@@ -14,22 +14,48 @@
 
 use std::fmt::Debug;
 use std::io::Result;
+use std::ops::BitOr;
+use std::ops::BitOrAssign;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::stream;
+use futures::StreamExt;
 
+use crate::error::unsupported;
+use crate::io_util::block_on;
+use crate::io_util::into_blocking_iterator;
+use crate::io_util::into_blocking_reader;
+use crate::io_util::into_blocking_writer;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
+use crate::ops::OpPresign;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::PresignedRequest;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWriter;
 use crate::BytesReader;
 use crate::BytesWriter;
 use crate::Metadata;
+use crate::ObjectIterator;
+use crate::ObjectPart;
 use crate::ObjectStreamer;
 use crate::Scheme;
 
+/// The concurrency of default `batch_delete` implementation.
+const DEFAULT_BATCH_DELETE_CONCURRENCY: usize = 16;
+
 /// Underlying trait of all backends for implementors.
 ///
 /// # Note to users
@@ -41,11 +67,14 @@ use crate::Scheme;
 ///
 /// - Path in args will all be normalized into the same style, services
 ///   should handle them based on services requirement.
+/// - Operations that not implemented will return [`std::io::ErrorKind::Unsupported`],
+///   services should declare the operations they support via
+///   [`AccessorCapability`] in [`AccessorMetadata`].
 #[async_trait]
 pub trait Accessor: Send + Sync + Debug {
     /// Invoke the `metadata` operation to get metadata of accessor.
     fn metadata(&self) -> AccessorMetadata {
-        unimplemented!()
+        AccessorMetadata::default()
     }
 
     /// Invoke the `read` operation on the specified path, returns corresponding
@@ -57,8 +86,7 @@ pub trait Accessor: Send + Sync + Debug {
     /// - Create on existing dir SHOULD succeed.
     /// - Create on existing file SHOULD overwrite and truncate.
     async fn create(&self, args: &OpCreate) -> Result<()> {
-        let _ = args;
-        unimplemented!()
+        Err(unsupported("create", args.path()))
     }
 
     /// Invoke the `read` operation on the specified path, returns a
@@ -68,8 +96,7 @@ pub trait Accessor: Send + Sync + Debug {
     ///
     /// - Input path MUST be file path, DON'T NEED to check object mode.
     async fn read(&self, args: &OpRead) -> Result<BytesReader> {
-        let _ = args;
-        unimplemented!()
+        Err(unsupported("read", args.path()))
     }
 
     /// Invoke the `write` operation on the specified path, returns a
@@ -78,9 +105,22 @@ pub trait Accessor: Send + Sync + Debug {
     /// # Behavior
     ///
     /// - Input path MUST be file path, DON'T NEED to check object mode.
+    /// - If `args.is_unsized()`, the size is unknown and services should
+    ///   write until the returned writer is closed.
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
-        let _ = args;
-        unimplemented!()
+        Err(unsupported("write", args.path()))
+    }
+
+    /// Invoke the `append` operation to append data to the end of the specified path.
+    ///
+    /// # Behavior
+    ///
+    /// - Input path MUST be file path, DON'T NEED to check object mode.
+    /// - Input reader MUST contain exactly `args.size()` bytes.
+    /// - `append` SHOULD create the object if it's not exist.
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let _ = r;
+        Err(unsupported("append", args.path()))
     }
 
     /// Invoke the `stat` operation on the specified path.
@@ -90,8 +130,7 @@ pub trait Accessor: Send + Sync + Debug {
     /// - `stat` empty path means stat backend's root path.
     /// - `stat` a path endswith "/" means stating a dir.
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
-        let _ = args;
-        unimplemented!()
+        Err(unsupported("stat", args.path()))
     }
 
     /// Invoke the `delete` operation on the specified path.
@@ -101,8 +140,31 @@ pub trait Accessor: Send + Sync + Debug {
     /// - `delete` is an idempotent operation, it's safe to call `Delete` on the same path multiple times.
     /// - `delete` SHOULD return `Ok(())` if the path is deleted successfully or not exist.
     async fn delete(&self, args: &OpDelete) -> Result<()> {
-        let _ = args;
-        unimplemented!()
+        Err(unsupported("delete", args.path()))
+    }
+
+    /// Invoke the `batch_delete` operation on the specified paths, returns
+    /// the delete result of every path in the same order of input.
+    ///
+    /// # Behavior
+    ///
+    /// - Failure of one path MUST NOT affect other paths.
+    /// - Services that don't have native batch delete support will fall back
+    ///   to concurrent `delete`, which is the default implementation.
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        let results = stream::iter(args.paths().to_vec())
+            .map(|path| async move {
+                let res = match OpDelete::new(&path) {
+                    Ok(op) => self.delete(&op).await,
+                    Err(e) => Err(e),
+                };
+                (path, res)
+            })
+            .buffered(DEFAULT_BATCH_DELETE_CONCURRENCY)
+            .collect()
+            .await;
+
+        Ok(results)
     }
 
     /// Invoke the `list` operation on the specified path.
@@ -110,9 +172,139 @@ pub trait Accessor: Send + Sync + Debug {
     /// # Behavior
     ///
     /// - Input path MUST be dir path, DON'T NEED to check object mode.
+    /// - If `args.recursive()` is set, `list` SHOULD return all descendants
+    ///   of the path as a flat stream.
     async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
-        let _ = args;
-        unimplemented!()
+        Err(unsupported("list", args.path()))
+    }
+
+    /// Invoke the `copy` operation to copy object from `from` to `to`.
+    ///
+    /// # Behavior
+    ///
+    /// - Input paths MUST be file paths, DON'T NEED to check object mode.
+    /// - `copy` SHOULD overwrite the existing object at `to`.
+    /// - `copy` SHOULD return `NotFound` if `from` is not exist.
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        Err(unsupported("copy", args.from()))
+    }
+
+    /// Invoke the `rename` operation to move object from `from` to `to`.
+    ///
+    /// # Behavior
+    ///
+    /// - Input paths MUST be file paths, DON'T NEED to check object mode.
+    /// - `rename` SHOULD overwrite the existing object at `to`.
+    /// - `rename` SHOULD return `NotFound` if `from` is not exist.
+    /// - Services that don't have native rename support MAY implement it
+    ///   as `copy` and `delete`, which is not atomic.
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        Err(unsupported("rename", args.from()))
+    }
+
+    /// Invoke the `create_multipart` operation on the specified path,
+    /// returns the upload id if operate successful.
+    ///
+    /// # Behavior
+    ///
+    /// - Input path MUST be file path, DON'T NEED to check object mode.
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        Err(unsupported("create_multipart", args.path()))
+    }
+
+    /// Invoke the `write_multipart` operation to upload a part of the multipart
+    /// upload, returns the uploaded [`ObjectPart`] if operate successful.
+    ///
+    /// # Behavior
+    ///
+    /// - Input reader MUST contain exactly `args.size()` bytes.
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        let _ = r;
+        Err(unsupported("write_multipart", args.path()))
+    }
+
+    /// Invoke the `complete_multipart` operation to assemble uploaded parts into an object.
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        Err(unsupported("complete_multipart", args.path()))
+    }
+
+    /// Invoke the `abort_multipart` operation to discard all uploaded parts.
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        Err(unsupported("abort_multipart", args.path()))
+    }
+
+    /// Invoke the `presign` operation on the specified path, returns a
+    /// request that can be sent without credential before expired.
+    ///
+    /// # Behavior
+    ///
+    /// - Input path MUST be file path, DON'T NEED to check object mode.
+    /// - `presign` SHOULD NOT send any request to services.
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        Err(unsupported("presign", args.path()))
+    }
+
+    /// Invoke the blocking version of [`Accessor::create`].
+    ///
+    /// # Behavior
+    ///
+    /// - Default implementation runs `create` on the internal runtime,
+    ///   services that can operate synchronously SHOULD implement it natively.
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        block_on(self.create(args))
+    }
+
+    /// Invoke the blocking version of [`Accessor::read`].
+    ///
+    /// # Behavior
+    ///
+    /// - Default implementation runs `read` on the internal runtime,
+    ///   services that can operate synchronously SHOULD implement it natively.
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let r = block_on(self.read(args))?;
+        Ok(Box::new(into_blocking_reader(r)))
+    }
+
+    /// Invoke the blocking version of [`Accessor::write`].
+    ///
+    /// # Behavior
+    ///
+    /// - Default implementation runs `write` on the internal runtime,
+    ///   services that can operate synchronously SHOULD implement it natively.
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        let w = block_on(self.write(args))?;
+        Ok(Box::new(into_blocking_writer(w)))
+    }
+
+    /// Invoke the blocking version of [`Accessor::stat`].
+    ///
+    /// # Behavior
+    ///
+    /// - Default implementation runs `stat` on the internal runtime,
+    ///   services that can operate synchronously SHOULD implement it natively.
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        block_on(self.stat(args))
+    }
+
+    /// Invoke the blocking version of [`Accessor::delete`].
+    ///
+    /// # Behavior
+    ///
+    /// - Default implementation runs `delete` on the internal runtime,
+    ///   services that can operate synchronously SHOULD implement it natively.
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        block_on(self.delete(args))
+    }
+
+    /// Invoke the blocking version of [`Accessor::list`].
+    ///
+    /// # Behavior
+    ///
+    /// - Default implementation runs `list` on the internal runtime,
+    ///   services that can operate synchronously SHOULD implement it natively.
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        let s = block_on(self.list(args))?;
+        Ok(Box::new(into_blocking_iterator(s)))
     }
 }
 
@@ -132,15 +324,62 @@ impl<T: Accessor> Accessor for Arc<T> {
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
         self.as_ref().write(args).await
     }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        self.as_ref().append(args, r).await
+    }
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
         self.as_ref().stat(args).await
     }
     async fn delete(&self, args: &OpDelete) -> Result<()> {
         self.as_ref().delete(args).await
     }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        self.as_ref().batch_delete(args).await
+    }
     async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
         self.as_ref().list(args).await
     }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        self.as_ref().copy(args).await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        self.as_ref().rename(args).await
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        self.as_ref().create_multipart(args).await
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        self.as_ref().write_multipart(args, r).await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        self.as_ref().complete_multipart(args).await
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        self.as_ref().abort_multipart(args).await
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        self.as_ref().presign(args)
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        self.as_ref().blocking_create(args)
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        self.as_ref().blocking_read(args)
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        self.as_ref().blocking_write(args)
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        self.as_ref().blocking_stat(args)
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        self.as_ref().blocking_delete(args)
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        self.as_ref().blocking_list(args)
+    }
 }
 
 /// Metadata for accessor, users can use this metadata to get information of underlying backend.
@@ -149,6 +388,7 @@ pub struct AccessorMetadata {
     scheme: Scheme,
     root: String,
     name: String,
+    capabilities: AccessorCapability,
 }
 
 impl AccessorMetadata {
@@ -186,4 +426,137 @@ impl AccessorMetadata {
         self.name = name.to_string();
         self
     }
+
+    /// [`AccessorCapability`] of backend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::services::memory;
+    /// use opendal::AccessorCapability;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// if op.metadata().capabilities().contains(AccessorCapability::PRESIGN) {
+    ///     println!("presign is supported")
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capabilities(&self) -> AccessorCapability {
+        self.capabilities
+    }
+
+    pub(crate) fn set_capabilities(&mut self, capabilities: AccessorCapability) -> &mut Self {
+        self.capabilities = capabilities;
+        self
+    }
+}
+
+/// AccessorCapability is a set of operations that supported by backend.
+///
+/// Capabilities can be combined via `|`, for example:
+/// `AccessorCapability::READ | AccessorCapability::WRITE`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct AccessorCapability(u32);
+
+impl AccessorCapability {
+    /// Support `read`.
+    pub const READ: Self = Self(1 << 0);
+    /// Support `write` and `create`.
+    pub const WRITE: Self = Self(1 << 1);
+    /// Support `list`.
+    pub const LIST: Self = Self(1 << 2);
+    /// Support `read` with range.
+    pub const RANGE_READ: Self = Self(1 << 3);
+    /// Support `presign`.
+    pub const PRESIGN: Self = Self(1 << 4);
+    /// Support multipart operations like `create_multipart`.
+    pub const MULTIPART: Self = Self(1 << 5);
+    /// Support `copy`.
+    pub const COPY: Self = Self(1 << 6);
+    /// Support `rename`.
+    pub const RENAME: Self = Self(1 << 7);
+    /// Support append to existing objects.
+    pub const APPEND: Self = Self(1 << 8);
+    /// Support blocking operations natively instead of running them on an
+    /// internal runtime.
+    pub const BLOCKING: Self = Self(1 << 9);
+    /// Support `batch_delete` natively instead of falling back to `delete`.
+    pub const BATCH_DELETE: Self = Self(1 << 10);
+
+    /// Create an empty capability set.
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Check whether this set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Check whether all capabilities in `other` are supported.
+    pub fn contains(&self, other: AccessorCapability) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for AccessorCapability {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AccessorCapability {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct EmptyAccessor;
+
+    impl Accessor for EmptyAccessor {}
+
+    #[tokio::test]
+    async fn test_accessor_unsupported() -> Result<()> {
+        let acc = EmptyAccessor;
+
+        assert!(acc.metadata().capabilities().is_empty());
+
+        let err = acc
+            .read(&OpRead::new("abc", ..)?)
+            .await
+            .err()
+            .expect("read must fail");
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let err = acc.stat(&OpStat::new("abc")?).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_accessor_capability() {
+        let cap = AccessorCapability::READ | AccessorCapability::WRITE;
+
+        assert!(cap.contains(AccessorCapability::READ));
+        assert!(cap.contains(AccessorCapability::READ | AccessorCapability::WRITE));
+        assert!(!cap.contains(AccessorCapability::LIST));
+        assert!(!cap.contains(AccessorCapability::READ | AccessorCapability::LIST));
+        assert!(cap.contains(AccessorCapability::empty()));
+        assert!(AccessorCapability::default().is_empty());
+    }
 }
@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::Deserialize;
+
+use crate::services;
+use crate::Scheme;
+
+/// Config of all services that OpenDAL supports, which can be deserialized
+/// via serde.
+///
+/// The service is selected by the `scheme` field, other fields are the same
+/// as the `Config` of that service. For example, in TOML:
+///
+/// ```toml
+/// scheme = "s3"
+/// bucket = "test"
+/// region = "us-east-1"
+/// ```
+///
+/// Use [`Operator::from_config`][crate::Operator::from_config] to build an operator.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "scheme", rename_all = "lowercase")]
+pub enum Config {
+    /// Config for [azblob][crate::services::azblob].
+    Azblob(services::azblob::Config),
+    /// Config for [fs][crate::services::fs].
+    Fs(services::fs::Config),
+    /// Config for [hdfs][crate::services::hdfs].
+    #[cfg(feature = "services-hdfs")]
+    Hdfs(services::hdfs::Config),
+    /// Config for [memory][crate::services::memory].
+    Memory(services::memory::Config),
+    /// Config for [s3][crate::services::s3].
+    S3(services::s3::Config),
+}
+
+impl Config {
+    /// Get the scheme of this config.
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            Config::Azblob(_) => Scheme::Azblob,
+            Config::Fs(_) => Scheme::Fs,
+            #[cfg(feature = "services-hdfs")]
+            Config::Hdfs(_) => Scheme::Hdfs,
+            Config::Memory(_) => Scheme::Memory,
+            Config::S3(_) => Scheme::S3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize() -> Result<()> {
+        let cfg: Config = serde_json::from_str(
+            r#"{"scheme": "s3", "bucket": "test", "region": "us-east-1", "access_key_id": "ak"}"#,
+        )?;
+        assert_eq!(cfg.scheme(), Scheme::S3);
+        match cfg {
+            Config::S3(v) => {
+                assert_eq!(v.bucket.as_deref(), Some("test"));
+                assert_eq!(v.region.as_deref(), Some("us-east-1"));
+                assert_eq!(v.access_key_id.as_deref(), Some("ak"));
+                assert_eq!(v.endpoint, None);
+            }
+            v => panic!("unexpected config: {v:?}"),
+        }
+
+        let cfg: Config = serde_json::from_str(r#"{"scheme": "memory"}"#)?;
+        assert_eq!(cfg.scheme(), Scheme::Memory);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        for input in [
+            r#"{"bucket": "test"}"#,
+            r#"{"scheme": "unknown"}"#,
+            r#"{"scheme": "fs", "bucket": "test"}"#,
+        ] {
+            assert!(serde_json::from_str::<Config>(input).is_err(), "{input}");
+        }
+    }
+}
@@ -14,6 +14,10 @@
 
 //! Errors that returned by OpenDAL
 //!
+//! All OpenDAL APIs return [`std::io::Error`] so that they can work with the
+//! whole io ecosystem. Users can convert them into [`Error`] to get the
+//! structured [`ErrorKind`] and context like op, path and the raw response.
+//!
 //! # Examples
 //!
 //! ```
@@ -33,56 +37,320 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! Use [`ErrorKind::of`] to check the structured kind:
+//!
+//! ```
+//! # use anyhow::Result;
+//! # use opendal::Operator;
+//! # use opendal::services::fs;
+//! use opendal::ErrorKind;
+//! # #[tokio::main]
+//! # async fn main() -> Result<()> {
+//! # let op = Operator::new(fs::Backend::build().root("/tmp").finish().await?);
+//! if let Err(e) = op.object("test_file").read().await {
+//!     match ErrorKind::of(&e) {
+//!         ErrorKind::NotFound => println!("object not exist"),
+//!         ErrorKind::RateLimited => println!("slow down"),
+//!         _ => return Err(e.into()),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
 
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::io;
 
+use anyhow::anyhow;
+use http::StatusCode;
 use thiserror::Error;
 
+use crate::Scheme;
+
+/// ErrorKind is the structured kind of errors returned by OpenDAL.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The object is not found.
+    NotFound,
+    /// The operation is not permitted, for example, credential is invalid.
+    PermissionDenied,
+    /// The object already exists.
+    AlreadyExists,
+    /// The request is throttled by services, users should slow down and retry.
+    RateLimited,
+    /// The operation or stream can't make progress in time, it could be retried.
+    TimedOut,
+    /// The condition of the request (like `If-Match`) is not matched.
+    ConditionNotMatch,
+    /// The operation is not supported by the underlying services.
+    Unsupported,
+    /// The config of backend is invalid.
+    ConfigInvalid,
+    /// Errors that we don't expect, check the context for more details.
+    Unexpected,
+}
+
+impl ErrorKind {
+    /// Get the [`ErrorKind`] of an [`io::Error`] returned by OpenDAL.
+    ///
+    /// Errors that don't carry structured context will be mapped from their
+    /// [`io::ErrorKind`].
+    pub fn of(err: &io::Error) -> ErrorKind {
+        let kind = match err.get_ref() {
+            Some(e) => {
+                if let Some(e) = e.downcast_ref::<ObjectError>() {
+                    e.kind()
+                } else if let Some(e) = e.downcast_ref::<BackendError>() {
+                    e.kind()
+                } else {
+                    ErrorKind::Unexpected
+                }
+            }
+            None => ErrorKind::Unexpected,
+        };
+
+        match kind {
+            ErrorKind::Unexpected => ErrorKind::from(err.kind()),
+            v => v,
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ErrorKind::NotFound => "NotFound",
+            ErrorKind::PermissionDenied => "PermissionDenied",
+            ErrorKind::AlreadyExists => "AlreadyExists",
+            ErrorKind::RateLimited => "RateLimited",
+            ErrorKind::TimedOut => "TimedOut",
+            ErrorKind::ConditionNotMatch => "ConditionNotMatch",
+            ErrorKind::Unsupported => "Unsupported",
+            ErrorKind::ConfigInvalid => "ConfigInvalid",
+            ErrorKind::Unexpected => "Unexpected",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+impl From<io::ErrorKind> for ErrorKind {
+    fn from(kind: io::ErrorKind) -> Self {
+        match kind {
+            io::ErrorKind::NotFound => ErrorKind::NotFound,
+            io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            io::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+            io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+            io::ErrorKind::TimedOut => ErrorKind::TimedOut,
+            _ => ErrorKind::Unexpected,
+        }
+    }
+}
+
+impl From<ErrorKind> for io::ErrorKind {
+    /// Convert into [`io::ErrorKind`].
+    ///
+    /// `RateLimited` will be converted into `Interrupted` so that it can be
+    /// retried like other temporary errors.
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::NotFound => io::ErrorKind::NotFound,
+            ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
+            ErrorKind::AlreadyExists => io::ErrorKind::AlreadyExists,
+            ErrorKind::RateLimited => io::ErrorKind::Interrupted,
+            ErrorKind::TimedOut => io::ErrorKind::TimedOut,
+            ErrorKind::Unsupported => io::ErrorKind::Unsupported,
+            ErrorKind::ConfigInvalid => io::ErrorKind::InvalidInput,
+            ErrorKind::ConditionNotMatch | ErrorKind::Unexpected => io::ErrorKind::Other,
+        }
+    }
+}
+
+/// Error is the structured error carried by [`io::Error`] returned by OpenDAL.
+#[derive(Error, Debug)]
+pub enum Error {
+    /// Errors happened while operating on objects.
+    #[error(transparent)]
+    Object(ObjectError),
+    /// Errors happened while building backends.
+    #[error(transparent)]
+    Backend(BackendError),
+}
+
+impl Error {
+    /// Convert an [`io::Error`] returned by OpenDAL into [`Error`].
+    ///
+    /// The input error will be returned as is if it doesn't carry any
+    /// structured context.
+    pub fn from_io(err: io::Error) -> Result<Self, io::Error> {
+        let io_kind = err.kind();
+        let is_object = err.get_ref().map(|e| e.is::<ObjectError>()) == Some(true);
+        let is_backend = err.get_ref().map(|e| e.is::<BackendError>()) == Some(true);
+        if !is_object && !is_backend {
+            return Err(err);
+        }
+
+        let inner = err.into_inner().expect("inner error must exist");
+        if is_object {
+            let mut e = *inner
+                .downcast::<ObjectError>()
+                .expect("must be ObjectError");
+            if e.kind == ErrorKind::Unexpected {
+                e.kind = ErrorKind::from(io_kind);
+            }
+            Ok(Error::Object(e))
+        } else {
+            let mut e = *inner
+                .downcast::<BackendError>()
+                .expect("must be BackendError");
+            if e.kind == ErrorKind::Unexpected {
+                e.kind = ErrorKind::from(io_kind);
+            }
+            Ok(Error::Backend(e))
+        }
+    }
+
+    /// [`ErrorKind`] of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Object(e) => e.kind(),
+            Error::Backend(e) => e.kind(),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Object(e) => e.into(),
+            Error::Backend(e) => e.into(),
+        }
+    }
+}
+
 /// BackendError carries backend related context.
 ///
 /// # Notes
 ///
-/// This error is used to carry context only, and should never be returned to users.
-/// Please wrap in [`std::io::Error`] instead.
+/// This error will be wrapped in [`std::io::Error`], use [`Error::from_io`]
+/// to get it back.
 #[derive(Error, Debug)]
 #[error("backend error: (context: {context:?}, source: {source})")]
-pub(crate) struct BackendError {
+pub struct BackendError {
+    kind: ErrorKind,
     context: HashMap<String, String>,
     source: anyhow::Error,
 }
 
 impl BackendError {
-    pub fn new(context: HashMap<String, String>, source: impl Into<anyhow::Error>) -> Self {
+    pub(crate) fn new(context: HashMap<String, String>, source: impl Into<anyhow::Error>) -> Self {
         BackendError {
+            kind: ErrorKind::ConfigInvalid,
             context,
             source: source.into(),
         }
     }
+
+    pub(crate) fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// [`ErrorKind`] of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Context of the backend while building.
+    pub fn context(&self) -> &HashMap<String, String> {
+        &self.context
+    }
+}
+
+impl From<BackendError> for io::Error {
+    fn from(err: BackendError) -> Self {
+        io::Error::new(err.kind.into(), err)
+    }
 }
 
 /// ObjectError carries object related context.
 ///
 /// # Notes
 ///
-/// This error is used to carry context only, and should never be returned to users.
-/// Please wrap in [`std::io::Error`] with correct [`std::io::ErrorKind`] instead.
+/// This error will be wrapped in [`std::io::Error`], use [`Error::from_io`]
+/// to get it back.
 #[derive(Error, Debug)]
 #[error("object error: (op: {op}, path: {path}, source: {source})")]
-pub(crate) struct ObjectError {
+pub struct ObjectError {
+    kind: ErrorKind,
     op: &'static str,
     path: String,
+    scheme: Option<Scheme>,
+    response: Option<String>,
     source: anyhow::Error,
 }
 
 impl ObjectError {
-    pub fn new(op: &'static str, path: &str, source: impl Into<anyhow::Error>) -> Self {
+    pub(crate) fn new(op: &'static str, path: &str, source: impl Into<anyhow::Error>) -> Self {
         ObjectError {
+            kind: ErrorKind::Unexpected,
             op,
             path: path.to_string(),
+            scheme: None,
+            response: None,
             source: source.into(),
         }
     }
+
+    pub(crate) fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub(crate) fn with_scheme(mut self, scheme: Scheme) -> Self {
+        self.scheme = Some(scheme);
+        self
+    }
+
+    pub(crate) fn with_response(mut self, response: String) -> Self {
+        self.response = Some(response);
+        self
+    }
+
+    /// [`ErrorKind`] of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The operation that returns this error, like `read`.
+    pub fn op(&self) -> &'static str {
+        self.op
+    }
+
+    /// The path that operation is taken on.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The [`Scheme`] of services that returns this error, if known.
+    pub fn scheme(&self) -> Option<Scheme> {
+        self.scheme
+    }
+
+    /// The raw response returned by services, if any.
+    pub fn response(&self) -> Option<&str> {
+        self.response.as_deref()
+    }
+}
+
+impl From<ObjectError> for io::Error {
+    fn from(err: ObjectError) -> Self {
+        io::Error::new(err.kind.into(), err)
+    }
 }
 
 /// Copied for [`io::Error::other`], should be removed after `io_error_other` stable.
@@ -92,3 +360,113 @@ where
 {
     io::Error::new(io::ErrorKind::Other, error.into())
 }
+
+/// Build an [`io::ErrorKind::Unsupported`] error for operations that
+/// underlying accessor doesn't support.
+pub(crate) fn unsupported(op: &'static str, path: &str) -> io::Error {
+    ObjectError::new(op, path, anyhow!("operation is not supported"))
+        .with_kind(ErrorKind::Unsupported)
+        .into()
+}
+
+/// Build an error for config keys that the service doesn't understand.
+///
+/// Only the key is kept in context, values may carry credentials.
+pub(crate) fn unknown_config_key(key: &str) -> io::Error {
+    other(BackendError::new(
+        HashMap::from([("key".to_string(), key.to_string())]),
+        anyhow!("config key {key} is not supported"),
+    ))
+}
+
+/// Parse the status code of http response into [`io::ErrorKind`] and [`ErrorKind`].
+///
+/// - Temporary errors like `500` will be `Interrupted` so that they can be retried.
+/// - `429` will be `RateLimited`, which is also `Interrupted`.
+/// - `409` will be `Unexpected`, services should decide whether it's
+///   `AlreadyExists` by the error code in response.
+pub(crate) fn parse_http_status(status: StatusCode) -> (io::ErrorKind, ErrorKind) {
+    match status {
+        StatusCode::NOT_FOUND => (io::ErrorKind::NotFound, ErrorKind::NotFound),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            (io::ErrorKind::PermissionDenied, ErrorKind::PermissionDenied)
+        }
+        StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
+            (io::ErrorKind::Other, ErrorKind::ConditionNotMatch)
+        }
+        StatusCode::TOO_MANY_REQUESTS => (io::ErrorKind::Interrupted, ErrorKind::RateLimited),
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => (io::ErrorKind::Interrupted, ErrorKind::Unexpected),
+        _ => (io::ErrorKind::Other, ErrorKind::Unexpected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kind_of() {
+        let err = other(ObjectError::new("read", "abc", anyhow!("test")));
+        assert_eq!(ErrorKind::of(&err), ErrorKind::Unexpected);
+
+        // Fallback to io error kind.
+        let err = io::Error::new(
+            io::ErrorKind::NotFound,
+            ObjectError::new("read", "abc", anyhow!("test")),
+        );
+        assert_eq!(ErrorKind::of(&err), ErrorKind::NotFound);
+
+        let err: io::Error = ObjectError::new("read", "abc", anyhow!("test"))
+            .with_kind(ErrorKind::RateLimited)
+            .into();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert_eq!(ErrorKind::of(&err), ErrorKind::RateLimited);
+
+        let err = io::Error::new(io::ErrorKind::AlreadyExists, "plain io error");
+        assert_eq!(ErrorKind::of(&err), ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn test_parse_http_status() {
+        assert_eq!(
+            parse_http_status(StatusCode::NOT_FOUND),
+            (io::ErrorKind::NotFound, ErrorKind::NotFound)
+        );
+        assert_eq!(
+            parse_http_status(StatusCode::TOO_MANY_REQUESTS),
+            (io::ErrorKind::Interrupted, ErrorKind::RateLimited)
+        );
+        // Conflicts are not always caused by existing objects.
+        assert_eq!(
+            parse_http_status(StatusCode::CONFLICT),
+            (io::ErrorKind::Other, ErrorKind::Unexpected)
+        );
+    }
+
+    #[test]
+    fn test_error_from_io() {
+        let err: io::Error = ObjectError::new("stat", "abc", anyhow!("test"))
+            .with_kind(ErrorKind::PermissionDenied)
+            .with_scheme(Scheme::S3)
+            .with_response("<Error></Error>".to_string())
+            .into();
+
+        let err = Error::from_io(err).expect("must be structured error");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        match err {
+            Error::Object(e) => {
+                assert_eq!(e.op(), "stat");
+                assert_eq!(e.path(), "abc");
+                assert_eq!(e.scheme(), Some(Scheme::S3));
+                assert_eq!(e.response(), Some("<Error></Error>"));
+            }
+            Error::Backend(_) => panic!("must be object error"),
+        }
+
+        let err = io::Error::new(io::ErrorKind::NotFound, "plain io error");
+        assert!(Error::from_io(err).is_err());
+    }
+}
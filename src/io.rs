@@ -35,6 +35,37 @@ impl<T> BytesWrite for T where T: AsyncWrite + Unpin + Send {}
 /// BytesWriter is a boxed dyn [`BytesWrite`].
 pub type BytesWriter = Box<dyn BytesWrite>;
 
+/// BlockingBytesRead represents a blocking reader of bytes.
+pub trait BlockingBytesRead: std::io::Read + Send {}
+impl<T> BlockingBytesRead for T where T: std::io::Read + Send {}
+
+/// BlockingBytesReader is a boxed dyn [`BlockingBytesRead`].
+pub type BlockingBytesReader = Box<dyn BlockingBytesRead>;
+
+/// BlockingBytesWrite represents a blocking writer of bytes.
+///
+/// Different from [`std::io::Write`], written data is only guaranteed to be
+/// persisted after [`BlockingBytesWrite::close`] returns successfully.
+pub trait BlockingBytesWrite: std::io::Write + Send {
+    /// Close this writer and make sure all data has been persisted.
+    fn close(&mut self) -> Result<()>;
+}
+
+impl<T: BlockingBytesWrite + ?Sized> BlockingBytesWrite for Box<T> {
+    fn close(&mut self) -> Result<()> {
+        (**self).close()
+    }
+}
+
+impl BlockingBytesWrite for std::fs::File {
+    fn close(&mut self) -> Result<()> {
+        std::io::Write::flush(self)
+    }
+}
+
+/// BlockingBytesWriter is a boxed dyn [`BlockingBytesWrite`].
+pub type BlockingBytesWriter = Box<dyn BlockingBytesWrite>;
+
 /// BytesStream represents a stream of bytes.
 ///
 /// This trait is used as alias to `Stream<Item = Result<Bytes>> + Unpin + Send`.
@@ -0,0 +1,225 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::io::Read;
+use std::io::Result;
+use std::io::Write;
+
+use futures::AsyncReadExt;
+use futures::AsyncWriteExt;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use tokio::runtime::Runtime;
+
+use crate::BlockingBytesWrite;
+use crate::BytesRead;
+use crate::BytesWrite;
+use crate::Object;
+use crate::ObjectStream;
+
+/// The internal runtime used to run async operations in blocking code.
+static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .thread_name("opendal-blocking")
+        .enable_all()
+        .build()
+        .expect("build blocking runtime must succeed")
+});
+
+/// Run the future to complete on the internal runtime of OpenDAL.
+///
+/// Blocking operations of services that don't support them natively are
+/// running on this runtime. Users can also use it to build backends in
+/// blocking code.
+///
+/// # Panics
+///
+/// This function will panic if called within an async execution context.
+///
+/// # Example
+///
+/// ```rust
+/// use opendal::io_util::block_on;
+/// use opendal::services::memory;
+/// use opendal::Operator;
+/// # use std::io::Result;
+///
+/// # fn main() -> Result<()> {
+/// let op = Operator::new(block_on(memory::Backend::build().finish())?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn block_on<F: Future>(f: F) -> F::Output {
+    RUNTIME.block_on(f)
+}
+
+/// Convert [`BytesRead`][crate::BytesRead] into [`BlockingBytesRead`][crate::BlockingBytesRead].
+///
+/// Every read will be running on the internal runtime via [`block_on`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Read;
+///
+/// use opendal::io_util::into_blocking_reader;
+/// # use std::io::Result;
+/// # use futures::io::Cursor;
+///
+/// # fn main() -> Result<()> {
+/// let mut r = into_blocking_reader(Cursor::new(vec![0; 1024]));
+/// let mut bs = Vec::new();
+/// r.read_to_end(&mut bs)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn into_blocking_reader<R: BytesRead>(r: R) -> IntoBlockingReader<R> {
+    IntoBlockingReader { r }
+}
+
+/// Blocking reader returned by [`into_blocking_reader`].
+pub struct IntoBlockingReader<R: BytesRead> {
+    r: R,
+}
+
+impl<R> IntoBlockingReader<R>
+where
+    R: BytesRead,
+{
+    /// Consume this reader and return the underlying async reader.
+    pub fn into_inner(self) -> R {
+        self.r
+    }
+}
+
+impl<R> Read for IntoBlockingReader<R>
+where
+    R: BytesRead,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        block_on(self.r.read(buf))
+    }
+}
+
+/// Convert [`BytesWrite`][crate::BytesWrite] into [`BlockingBytesWrite`][crate::BlockingBytesWrite].
+///
+/// Every write will be running on the internal runtime via [`block_on`].
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+///
+/// use opendal::io_util::into_blocking_writer;
+/// use opendal::BlockingBytesWrite;
+/// # use std::io::Result;
+/// # use futures::io::Cursor;
+///
+/// # fn main() -> Result<()> {
+/// let mut w = into_blocking_writer(Cursor::new(Vec::new()));
+/// w.write_all(&[0; 1024])?;
+/// w.close()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn into_blocking_writer<W: BytesWrite>(w: W) -> IntoBlockingWriter<W> {
+    IntoBlockingWriter { w }
+}
+
+/// Blocking writer returned by [`into_blocking_writer`].
+pub struct IntoBlockingWriter<W: BytesWrite> {
+    w: W,
+}
+
+impl<W> IntoBlockingWriter<W>
+where
+    W: BytesWrite,
+{
+    /// Consume this writer and return the underlying async writer.
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+}
+
+impl<W> Write for IntoBlockingWriter<W>
+where
+    W: BytesWrite,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        block_on(self.w.write(buf))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        block_on(self.w.flush())
+    }
+}
+
+impl<W> BlockingBytesWrite for IntoBlockingWriter<W>
+where
+    W: BytesWrite,
+{
+    fn close(&mut self) -> Result<()> {
+        block_on(self.w.close())
+    }
+}
+
+/// Convert [`ObjectStream`][crate::ObjectStream] into [`ObjectIterate`][crate::ObjectIterate].
+///
+/// Every poll will be running on the internal runtime via [`block_on`].
+pub fn into_blocking_iterator<S: ObjectStream>(s: S) -> IntoBlockingIterator<S> {
+    IntoBlockingIterator { s }
+}
+
+/// Blocking iterator returned by [`into_blocking_iterator`].
+pub struct IntoBlockingIterator<S: ObjectStream> {
+    s: S,
+}
+
+impl<S> Iterator for IntoBlockingIterator<S>
+where
+    S: ObjectStream,
+{
+    type Item = Result<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        block_on(self.s.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::io::Cursor;
+    use rand::rngs::ThreadRng;
+    use rand::Rng;
+    use rand::RngCore;
+
+    use super::*;
+
+    #[test]
+    fn test_into_blocking_reader_writer() {
+        let mut rng = ThreadRng::default();
+        // Generate size between 1B..16MB.
+        let size = rng.gen_range(1..16 * 1024 * 1024);
+        let mut content = vec![0; size];
+        rng.fill_bytes(&mut content);
+
+        let mut r = into_blocking_reader(Cursor::new(content.clone()));
+        let mut w = into_blocking_writer(Cursor::new(Vec::new()));
+        std::io::copy(&mut r, &mut w).expect("copy must success");
+        w.close().expect("close must success");
+
+        assert_eq!(w.into_inner().into_inner(), content)
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::Result;
+
+use anyhow::anyhow;
+use http::HeaderMap;
+
+use crate::error::other;
+use crate::error::ObjectError;
+
+/// Parse header value to str, returns `None` if the header doesn't exist.
+pub(crate) fn parse_header_to_str<'a>(
+    headers: &'a HeaderMap,
+    name: &str,
+    op: &'static str,
+    path: &str,
+) -> Result<Option<&'a str>> {
+    match headers.get(name) {
+        None => Ok(None),
+        Some(v) => v.to_str().map(Some).map_err(|e| {
+            other(ObjectError::new(
+                op,
+                path,
+                anyhow!("parse {} header: {:?}", name, e),
+            ))
+        }),
+    }
+}
+
+/// Parse user defined metadata from headers which start with `prefix`
+/// like `x-amz-meta-`, the prefix will be trimmed.
+pub(crate) fn parse_user_metadata(
+    headers: &HeaderMap,
+    prefix: &str,
+    op: &'static str,
+    path: &str,
+) -> Result<HashMap<String, String>> {
+    let mut m = HashMap::new();
+    for (k, v) in headers {
+        if let Some(key) = k.as_str().strip_prefix(prefix) {
+            let v = v.to_str().map_err(|e| {
+                other(ObjectError::new(
+                    op,
+                    path,
+                    anyhow!("parse {} header: {:?}", k, e),
+                ))
+            })?;
+            m.insert(key.to_string(), v.to_string());
+        }
+    }
+
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_user_metadata() -> anyhow::Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-amz-meta-location", HeaderValue::from_static("earth"));
+        headers.insert("x-amz-meta-author", HeaderValue::from_static("opendal"));
+        headers.insert("x-amz-storage-class", HeaderValue::from_static("STANDARD"));
+
+        let m = parse_user_metadata(&headers, "x-amz-meta-", "stat", "test")?;
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.get("location").map(|v| v.as_str()), Some("earth"));
+        assert_eq!(m.get("author").map(|v| v.as_str()), Some("opendal"));
+
+        Ok(())
+    }
+}
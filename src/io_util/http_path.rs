@@ -0,0 +1,50 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::AsciiSet;
+use percent_encoding::NON_ALPHANUMERIC;
+
+/// AsciiSet for object path, keeps unreserved characters and `/` as is.
+static PATH_ENCODE_SET: AsciiSet = NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent encode path so that it can be used in url or headers like
+/// `x-amz-copy-source`.
+pub(crate) fn percent_encode_path(path: &str) -> String {
+    utf8_percent_encode(path, &PATH_ENCODE_SET).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_encode_path() {
+        let cases = vec![
+            ("simple", "path/to/file.txt", "path/to/file.txt"),
+            ("space", "path/to/a b", "path/to/a%20b"),
+            ("plus and query", "a+b?c", "a%2Bb%3Fc"),
+            ("unicode", "中文.txt", "%E4%B8%AD%E6%96%87.txt"),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(percent_encode_path(input), expected, "{name}");
+        }
+    }
+}
@@ -26,13 +26,23 @@ pub use into_reader::into_reader;
 mod into_writer;
 pub use into_writer::into_writer;
 
+mod blocking;
+pub use blocking::block_on;
+pub use blocking::into_blocking_iterator;
+pub use blocking::into_blocking_reader;
+pub use blocking::into_blocking_writer;
+
 mod read_observer;
+pub use read_observer::observe_blocking_read;
 pub use read_observer::observe_read;
+pub use read_observer::BlockingReadObserver;
 pub use read_observer::ReadEvent;
 pub use read_observer::ReadObserver;
 
 mod write_observer;
+pub use write_observer::observe_blocking_write;
 pub use write_observer::observe_write;
+pub use write_observer::BlockingWriteObserver;
 pub use write_observer::WriteEvent;
 pub use write_observer::WriteObserver;
 
@@ -40,6 +50,19 @@ mod http_body;
 pub(crate) use http_body::new_http_channel;
 pub(crate) use http_body::HttpBodyWriter;
 
+mod http_header;
+pub(crate) use http_header::parse_header_to_str;
+pub(crate) use http_header::parse_user_metadata;
+
+mod http_path;
+pub(crate) use http_path::percent_encode_path;
+
+mod multipart_writer;
+pub use multipart_writer::MultipartWriter;
+
+mod walk;
+pub(crate) use walk::BfsWalker;
+
 mod seekable_reader;
 pub use seekable_reader::seekable_read;
 pub use seekable_reader::SeekableReader;
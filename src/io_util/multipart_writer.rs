@@ -0,0 +1,426 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::min;
+use std::io::Error;
+use std::io::Result;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use anyhow::anyhow;
+use bytes::BytesMut;
+use futures::future::BoxFuture;
+use futures::io::Cursor;
+use futures::ready;
+use futures::stream::FuturesUnordered;
+use futures::AsyncWrite;
+use futures::FutureExt;
+use futures::StreamExt;
+use log::debug;
+use log::warn;
+
+use crate::error::other;
+use crate::error::ObjectError;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpWriteMultipart;
+use crate::ObjectMultipart;
+use crate::ObjectPart;
+
+/// Default concurrency of [`MultipartWriter`].
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// MultipartWriter will split data into parts and upload them via multipart upload.
+///
+/// - Written data will be buffered until `part_size` is reached, then the part
+///   will be uploaded in background.
+/// - At most `concurrency` parts will be uploaded at the same time, `write`
+///   will return `Pending` if we have too many in-flight parts.
+/// - `close` will upload the last part and complete the multipart upload.
+/// - The multipart upload will be aborted if any error happened.
+///
+/// # Notes
+///
+/// Users MUST call `close` to finish the upload, otherwise the uploaded parts
+/// will be left on the services.
+///
+/// # Example
+///
+/// ```no_run
+/// # use opendal::services::s3;
+/// # use anyhow::Result;
+/// # use opendal::Operator;
+/// use futures::AsyncWriteExt;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// # let op = Operator::new(s3::Backend::build().bucket("test").finish().await?);
+/// let mut w = op
+///     .object("path/to/file")
+///     .multipart_writer(8 * 1024 * 1024)
+///     .await?
+///     .with_concurrency(8);
+/// w.write_all(&vec![0; 32 * 1024 * 1024]).await?;
+/// w.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MultipartWriter {
+    mp: ObjectMultipart,
+    part_size: usize,
+    concurrency: usize,
+
+    buf: BytesMut,
+    next_part_number: usize,
+    parts: Vec<ObjectPart>,
+    futs: FuturesUnordered<BoxFuture<'static, Result<ObjectPart>>>,
+    state: State,
+}
+
+enum State {
+    Writing,
+    Completing(BoxFuture<'static, Result<()>>),
+    Aborting(BoxFuture<'static, Result<()>>, Option<Error>),
+    Closed,
+    Failed,
+}
+
+impl MultipartWriter {
+    /// Create a new MultipartWriter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part_size` is zero.
+    pub fn new(mp: ObjectMultipart, part_size: usize) -> Self {
+        assert!(part_size > 0, "part size must be larger than zero");
+
+        Self {
+            mp,
+            part_size,
+            concurrency: DEFAULT_CONCURRENCY,
+
+            buf: BytesMut::with_capacity(part_size),
+            next_part_number: 1,
+            parts: Vec::new(),
+            futs: FuturesUnordered::new(),
+            state: State::Writing,
+        }
+    }
+
+    /// Set the max number of parts that can be uploaded at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is zero.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "concurrency must be larger than zero");
+
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Start uploading current buffer as a new part.
+    fn upload_part(&mut self) {
+        let bs = self.buf.split().freeze();
+        let part_number = self.next_part_number;
+        self.next_part_number += 1;
+
+        let acc = self.mp.accessor();
+        let path = self.mp.path().to_string();
+        let upload_id = self.mp.upload_id().to_string();
+        let fut = async move {
+            let op = OpWriteMultipart::new(&path, &upload_id, part_number, bs.len() as u64)?;
+            debug!(
+                "object {} upload part {}: size {}",
+                &path,
+                part_number,
+                bs.len()
+            );
+            acc.write_multipart(&op, Box::new(Cursor::new(bs))).await
+        };
+
+        self.futs.push(fut.boxed());
+    }
+
+    /// Collect all finished parts without blocking.
+    ///
+    /// Returns `Poll::Pending` if there are still in-flight parts.
+    fn poll_parts(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match ready!(self.futs.poll_next_unpin(cx)) {
+                Some(Ok(part)) => self.parts.push(part),
+                Some(Err(e)) => return Poll::Ready(Err(e)),
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+
+    /// Abort the multipart upload and return the error after aborted.
+    fn abort(&mut self, err: Error) {
+        // Drop all in-flight parts, they are useless now.
+        self.futs = FuturesUnordered::new();
+
+        let acc = self.mp.accessor();
+        let path = self.mp.path().to_string();
+        let upload_id = self.mp.upload_id().to_string();
+        let fut = async move {
+            let op = OpAbortMultipart::new(&path, &upload_id)?;
+            acc.abort_multipart(&op).await
+        };
+
+        self.state = State::Aborting(fut.boxed(), Some(err));
+    }
+
+    /// Drive the abort future until finished.
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Error> {
+        match &mut self.state {
+            State::Aborting(fut, err) => {
+                if let Err(e) = ready!(fut.poll_unpin(cx)) {
+                    warn!(
+                        "object {} abort multipart {} failed: {:?}",
+                        self.mp.path(),
+                        self.mp.upload_id(),
+                        e
+                    );
+                }
+                let err = err.take().expect("abort error must be valid");
+                self.state = State::Failed;
+                Poll::Ready(err)
+            }
+            _ => unreachable!("poll_abort must be called in aborting state"),
+        }
+    }
+
+    fn closed_error(&self) -> Error {
+        other(ObjectError::new(
+            "write_multipart",
+            self.mp.path(),
+            anyhow!("multipart writer has been closed"),
+        ))
+    }
+}
+
+impl AsyncWrite for MultipartWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        match self.state {
+            State::Writing => {}
+            State::Aborting(_, _) => return Poll::Ready(Err(ready!(self.poll_abort(cx)))),
+            _ => return Poll::Ready(Err(self.closed_error())),
+        }
+
+        // Collect finished parts first, the result will be `Pending` if
+        // there are in-flight parts which is expected.
+        if let Poll::Ready(Err(e)) = self.poll_parts(cx) {
+            self.abort(e);
+            return Poll::Ready(Err(ready!(self.poll_abort(cx))));
+        }
+
+        if self.buf.len() >= self.part_size {
+            // Wait for in-flight parts if we reach the concurrency limit.
+            //
+            // `poll_parts` has registered the waker for us.
+            if self.futs.len() >= self.concurrency {
+                return Poll::Pending;
+            }
+
+            self.upload_part();
+        }
+
+        let size = min(self.part_size - self.buf.len(), buf.len());
+        self.buf.extend_from_slice(&buf[..size]);
+
+        Poll::Ready(Ok(size))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Writing => {
+                    // Upload the last part, at least one part is required to
+                    // complete a multipart upload.
+                    if !self.buf.is_empty() || self.next_part_number == 1 {
+                        self.upload_part();
+                    }
+
+                    if let Err(e) = ready!(self.poll_parts(cx)) {
+                        self.abort(e);
+                        continue;
+                    }
+
+                    let mut parts = std::mem::take(&mut self.parts);
+                    parts.sort_by_key(|p| p.part_number());
+
+                    let acc = self.mp.accessor();
+                    let path = self.mp.path().to_string();
+                    let upload_id = self.mp.upload_id().to_string();
+                    let fut = async move {
+                        let op = OpCompleteMultipart::new(&path, &upload_id, parts)?;
+                        acc.complete_multipart(&op).await
+                    };
+                    self.state = State::Completing(fut.boxed());
+                }
+                State::Completing(fut) => match ready!(fut.poll_unpin(cx)) {
+                    Ok(_) => {
+                        debug!(
+                            "object {} multipart {} completed",
+                            self.mp.path(),
+                            self.mp.upload_id()
+                        );
+                        self.state = State::Closed;
+                        return Poll::Ready(Ok(()));
+                    }
+                    Err(e) => self.abort(e),
+                },
+                State::Aborting(_, _) => return Poll::Ready(Err(ready!(self.poll_abort(cx)))),
+                State::Closed => return Poll::Ready(Ok(())),
+                State::Failed => return Poll::Ready(Err(self.closed_error())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use futures::AsyncReadExt;
+    use futures::AsyncWriteExt;
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::ops::OpCreateMultipart;
+    use crate::Accessor;
+    use crate::BytesReader;
+    use crate::Operator;
+
+    #[derive(Debug, Clone, Default)]
+    struct MockService {
+        parts: Arc<Mutex<HashMap<usize, Vec<u8>>>>,
+        content: Arc<Mutex<Option<Vec<u8>>>>,
+        aborted: Arc<Mutex<bool>>,
+    }
+
+    #[async_trait]
+    impl Accessor for MockService {
+        async fn create_multipart(&self, _: &OpCreateMultipart) -> Result<String> {
+            Ok("upload_id".to_string())
+        }
+
+        async fn write_multipart(
+            &self,
+            args: &OpWriteMultipart,
+            mut r: BytesReader,
+        ) -> Result<ObjectPart> {
+            if args.path() == "failed_part" && args.part_number() == 2 {
+                return Err(other(anyhow!("failed to write part")));
+            }
+
+            let mut bs = Vec::new();
+            r.read_to_end(&mut bs).await?;
+            assert_eq!(bs.len() as u64, args.size());
+
+            self.parts.lock().insert(args.part_number(), bs);
+            Ok(ObjectPart::new(
+                args.part_number(),
+                &format!("etag-{}", args.part_number()),
+            ))
+        }
+
+        async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+            let parts = self.parts.lock();
+
+            let mut content = Vec::new();
+            for (idx, part) in args.parts().iter().enumerate() {
+                assert_eq!(part.part_number(), idx + 1);
+                assert_eq!(part.etag(), format!("etag-{}", idx + 1));
+                content.extend_from_slice(&parts[&part.part_number()]);
+            }
+            *self.content.lock() = Some(content);
+
+            Ok(())
+        }
+
+        async fn abort_multipart(&self, _: &OpAbortMultipart) -> Result<()> {
+            *self.aborted.lock() = true;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multipart_writer() -> anyhow::Result<()> {
+        let srv = Arc::new(MockService::default());
+        let op = Operator::new(srv.clone());
+
+        let content: Vec<u8> = (0..10 * 1024).map(|v| v as u8).collect();
+
+        let mut w = op
+            .object("test_file")
+            .multipart_writer(1024)
+            .await?
+            .with_concurrency(2);
+        w.write_all(&content).await?;
+        w.close().await?;
+
+        assert_eq!(srv.parts.lock().len(), 10);
+        assert_eq!(srv.content.lock().as_ref(), Some(&content));
+        assert!(!*srv.aborted.lock());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multipart_writer_empty() -> anyhow::Result<()> {
+        let srv = Arc::new(MockService::default());
+        let op = Operator::new(srv.clone());
+
+        let mut w = op.object("test_file").multipart_writer(1024).await?;
+        w.close().await?;
+
+        assert_eq!(srv.parts.lock().len(), 1);
+        assert_eq!(srv.content.lock().as_ref(), Some(&Vec::new()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multipart_writer_abort_on_error() -> anyhow::Result<()> {
+        let srv = Arc::new(MockService::default());
+        let op = Operator::new(srv.clone());
+
+        let mut w = op.object("failed_part").multipart_writer(1024).await?;
+        let result = async {
+            w.write_all(&[0; 4 * 1024]).await?;
+            w.close().await
+        }
+        .await;
+
+        assert!(result.is_err());
+        assert!(srv.content.lock().is_none());
+        assert!(*srv.aborted.lock());
+
+        Ok(())
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use std::io::ErrorKind;
+use std::io::Read;
 use std::io::Result;
 use std::pin::Pin;
 use std::task::Context;
@@ -20,6 +21,7 @@ use std::task::Poll;
 use futures::AsyncRead;
 use pin_project::pin_project;
 
+use crate::BlockingBytesReader;
 use crate::BytesReader;
 
 /// Create an observer over BytesReader.
@@ -110,6 +112,45 @@ where
     }
 }
 
+/// Create an observer over [`BlockingBytesReader`].
+///
+/// Same as [`observe_read`] but for blocking readers, [`ReadEvent::Pending`]
+/// will never be emitted.
+pub fn observe_blocking_read<F: FnMut(ReadEvent)>(
+    r: BlockingBytesReader,
+    f: F,
+) -> BlockingReadObserver<F> {
+    BlockingReadObserver { r, f }
+}
+
+/// Observer that created via [`observe_blocking_read`].
+pub struct BlockingReadObserver<F: FnMut(ReadEvent)> {
+    r: BlockingBytesReader,
+    f: F,
+}
+
+impl<F> Read for BlockingReadObserver<F>
+where
+    F: FnMut(ReadEvent),
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self.r.read(buf) {
+            Ok(0) => {
+                (self.f)(ReadEvent::Terminated);
+                Ok(0)
+            }
+            Ok(n) => {
+                (self.f)(ReadEvent::Read(n));
+                Ok(n)
+            }
+            Err(e) => {
+                (self.f)(ReadEvent::Error(e.kind()));
+                Err(e)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::io;
@@ -144,4 +185,23 @@ mod tests {
         assert_eq!(read_size, size);
         assert!(is_terminated);
     }
+
+    #[test]
+    fn test_blocking_read_observer() {
+        let content = vec![0; 4096];
+
+        let mut read_size = 0;
+        let mut is_terminated = false;
+        let mut r = observe_blocking_read(Box::new(std::io::Cursor::new(content)), |e| match e {
+            ReadEvent::Read(n) => read_size += n,
+            ReadEvent::Terminated => is_terminated = true,
+            _ => {}
+        });
+
+        std::io::copy(&mut r, &mut std::io::sink()).expect("copy must succeed");
+        drop(r);
+
+        assert_eq!(read_size, 4096);
+        assert!(is_terminated);
+    }
 }
@@ -0,0 +1,117 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::io::Result;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::future::BoxFuture;
+use futures::ready;
+use futures::Future;
+use futures::StreamExt;
+use log::debug;
+
+use crate::ops::OpList;
+use crate::Accessor;
+use crate::Object;
+use crate::ObjectMode;
+use crate::ObjectStreamer;
+
+/// BfsWalker walks the given dir in BFS order via non-recursive `list`,
+/// and returns all descendants as a flat stream.
+///
+/// Services that can't list recursively natively could use it to
+/// implement recursive `list`.
+pub(crate) struct BfsWalker {
+    acc: Arc<dyn Accessor>,
+    dirs: VecDeque<String>,
+    state: State,
+}
+
+enum State {
+    Idle,
+    Listing(String, BoxFuture<'static, Result<ObjectStreamer>>),
+    Streaming(String, ObjectStreamer),
+}
+
+impl BfsWalker {
+    /// Create a new walker on given dir path.
+    pub(crate) fn new(acc: Arc<dyn Accessor>, path: &str) -> Self {
+        Self {
+            acc,
+            dirs: VecDeque::from([path.to_string()]),
+            state: State::Idle,
+        }
+    }
+}
+
+impl futures::Stream for BfsWalker {
+    type Item = Result<Object>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            match &mut this.state {
+                State::Idle => {
+                    let dir = match this.dirs.pop_front() {
+                        Some(dir) => dir,
+                        None => {
+                            debug!("walk done");
+                            return Poll::Ready(None);
+                        }
+                    };
+
+                    let acc = this.acc.clone();
+                    let path = dir.clone();
+                    let fut = async move {
+                        let op = OpList::new(&path)?;
+                        acc.list(&op).await
+                    };
+                    this.state = State::Listing(dir, Box::pin(fut));
+                }
+                State::Listing(dir, fut) => {
+                    let dir = dir.clone();
+                    match ready!(Pin::new(fut).poll(cx)) {
+                        Ok(s) => this.state = State::Streaming(dir, s),
+                        Err(e) => {
+                            this.state = State::Idle;
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                    }
+                }
+                State::Streaming(dir, s) => match ready!(s.poll_next_unpin(cx)) {
+                    Some(Ok(o)) => {
+                        let meta = o.metadata_ref();
+                        // Some services will return the dir itself, skip it
+                        // to avoid walking the same dir again.
+                        if meta.mode() == ObjectMode::DIR && meta.path() != dir {
+                            let path = meta.path().to_string();
+                            this.dirs.push_back(path);
+                        }
+                        return Poll::Ready(Some(Ok(o)));
+                    }
+                    Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    None => {
+                        debug!("object {} walk dir done", dir);
+                        this.state = State::Idle
+                    }
+                },
+            }
+        }
+    }
+}
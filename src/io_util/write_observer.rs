@@ -14,6 +14,7 @@
 
 use std::io::ErrorKind;
 use std::io::Result;
+use std::io::Write;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
@@ -21,6 +22,8 @@ use std::task::Poll;
 use futures::AsyncWrite;
 use pin_project::pin_project;
 
+use crate::BlockingBytesWrite;
+use crate::BlockingBytesWriter;
 use crate::BytesWriter;
 
 /// Create an observer over [`crate::BytesWrite`].
@@ -144,6 +147,72 @@ where
     }
 }
 
+/// Create an observer over [`BlockingBytesWriter`].
+///
+/// Same as [`observe_write`] but for blocking writers, [`WriteEvent::Pending`]
+/// will never be emitted.
+pub fn observe_blocking_write<F: FnMut(WriteEvent)>(
+    w: BlockingBytesWriter,
+    f: F,
+) -> BlockingWriteObserver<F> {
+    BlockingWriteObserver { w, f }
+}
+
+/// Observer that created via [`observe_blocking_write`].
+pub struct BlockingWriteObserver<F: FnMut(WriteEvent)> {
+    w: BlockingBytesWriter,
+    f: F,
+}
+
+impl<F> Write for BlockingWriteObserver<F>
+where
+    F: FnMut(WriteEvent),
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        match self.w.write(buf) {
+            Ok(n) => {
+                (self.f)(WriteEvent::Written(n));
+                Ok(n)
+            }
+            Err(e) => {
+                (self.f)(WriteEvent::Error(e.kind()));
+                Err(e)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self.w.flush() {
+            Ok(_) => {
+                (self.f)(WriteEvent::Flushed);
+                Ok(())
+            }
+            Err(e) => {
+                (self.f)(WriteEvent::Error(e.kind()));
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<F> BlockingBytesWrite for BlockingWriteObserver<F>
+where
+    F: FnMut(WriteEvent) + Send,
+{
+    fn close(&mut self) -> Result<()> {
+        match self.w.close() {
+            Ok(_) => {
+                (self.f)(WriteEvent::Closed);
+                Ok(())
+            }
+            Err(e) => {
+                (self.f)(WriteEvent::Error(e.kind()));
+                Err(e)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::io;
@@ -0,0 +1,642 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide read-through cache support via [`CacheLayer`].
+
+use std::io::ErrorKind;
+use std::io::Result;
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::io::Cursor;
+use futures::ready;
+use futures::AsyncReadExt;
+use futures::AsyncWrite;
+use futures::Future;
+use futures::StreamExt;
+use log::warn;
+
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpDelete;
+use crate::ops::OpRead;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::Accessor;
+use crate::BlockingBytesWrite;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::Layer;
+use crate::Metadata;
+use crate::Operator;
+
+/// Default max size of a single cached read.
+const DEFAULT_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+/// CacheLayer will cache contents of objects and range reads in another
+/// [`Operator`], for example `services::memory` or a local `services::fs`.
+///
+/// - Every `read` of a path that matches the patterns will be served by
+///   cache if the same range has been read before, otherwise the content
+///   will be read from the underlying service and stored in cache.
+/// - Cached contents of a path will be invalidated by `write`, `append`,
+///   `delete`, `batch_delete`, `copy`, `rename` and `complete_multipart`
+///   through this layer.
+/// - With validation enabled, every `read` will `stat` the underlying
+///   service first and only use cached contents stored for the same
+///   ETag (or last modified time if ETag is missing).
+///   Cached contents of other versions will be removed once a new version
+///   is read.
+///
+/// # Notes
+///
+/// Contents are cached as a whole, reads larger than `max_size` (64 MiB by
+/// default) will bypass the cache. If the size of a read is unknown, at
+/// most `max_size + 1` bytes will be buffered to find it out. Reads with preconditions and blocking
+/// reads always bypass the cache.
+///
+/// Changes made without this layer (for example by other processes) will
+/// not be noticed unless validation is enabled. `write` invalidates cache
+/// both before the content is written and after the writer is closed, so
+/// old content cached by concurrent reads will not survive the write.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::CacheLayer;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let cache = Operator::new(memory::Backend::build().finish().await?);
+/// let layer = CacheLayer::new(cache)
+///     .with_pattern("*.parquet")
+///     .with_validation(true);
+/// let op = Operator::new(memory::Backend::build().finish().await?).layer(layer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheLayer {
+    cache: Operator,
+    patterns: Vec<String>,
+    validation: bool,
+    max_size: u64,
+}
+
+impl CacheLayer {
+    /// Create a new layer which caches all reads in `cache`.
+    pub fn new(cache: Operator) -> Self {
+        Self {
+            cache,
+            patterns: Vec::new(),
+            validation: false,
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+
+    /// Only cache paths that match `pattern`, can be called multiple times.
+    ///
+    /// In pattern, `*` matches any sequence of characters (including `/`)
+    /// and `?` matches any single character. All paths will be cached if
+    /// no pattern is set.
+    #[must_use]
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.to_string());
+        self
+    }
+
+    /// Validate cached contents by ETag or last modified time of objects.
+    ///
+    /// Objects that have neither of them will not be cached.
+    #[must_use]
+    pub fn with_validation(mut self, validation: bool) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Set the max size of a single cached read.
+    #[must_use]
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    fn is_cacheable(&self, path: &str) -> bool {
+        self.patterns.is_empty()
+            || self
+                .patterns
+                .iter()
+                .any(|p| glob_match(p.as_bytes(), path.as_bytes()))
+    }
+}
+
+impl Layer for CacheLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(CacheAccessor {
+            inner,
+            layer: self.clone(),
+        })
+    }
+}
+
+/// Match `path` against glob `pattern` which supports `*` and `?`.
+fn glob_match(pattern: &[u8], path: &[u8]) -> bool {
+    let (mut p, mut s) = (0, 0);
+    // Position of last `*` in pattern and the position in path it matched.
+    let mut star: Option<(usize, usize)> = None;
+
+    while s < path.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, s));
+                p += 1;
+            }
+            Some(c) if *c == b'?' || *c == path[s] => {
+                p += 1;
+                s += 1;
+            }
+            _ => match star {
+                // Let the last `*` match one more character.
+                Some((sp, ss)) => {
+                    star = Some((sp, ss + 1));
+                    p = sp + 1;
+                    s = ss + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p.min(pattern.len())..].iter().all(|c| *c == b'*')
+}
+
+/// Dir in cache that holds all cached contents of `path`.
+fn cache_dir(path: &str) -> String {
+    format!("{:x}/", md5::compute(path))
+}
+
+/// Prefix of keys in cache of the given version of `path`.
+fn cache_version_prefix(path: &str, version: &str) -> String {
+    format!("{}{:x}.", cache_dir(path), md5::compute(version))
+}
+
+/// Key in cache of the given range read of `path`.
+fn cache_key(path: &str, version: Option<&str>, offset: Option<u64>, size: Option<u64>) -> String {
+    let range = format!(
+        "{}-{}",
+        offset.map(|v| v.to_string()).unwrap_or_default(),
+        size.map(|v| v.to_string()).unwrap_or_default()
+    );
+
+    match version {
+        None => format!("{}{}", cache_dir(path), range),
+        Some(v) => format!("{}{}", cache_version_prefix(path, v), range),
+    }
+}
+
+/// Version of object used in validation.
+fn version(meta: &Metadata) -> Option<String> {
+    meta.etag().map(|v| v.to_string()).or_else(|| {
+        meta.last_modified()
+            .map(|v| v.unix_timestamp_nanos().to_string())
+    })
+}
+
+/// Remove all cached contents of `path`.
+async fn invalidate(cache: &Operator, path: &str) -> Result<()> {
+    let dir = cache.object(&cache_dir(path));
+    let mut entries = match dir.list().await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    while let Some(entry) = entries.next().await {
+        entry?.delete().await?;
+    }
+
+    match dir.delete().await {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn blocking_invalidate(cache: &Operator, path: &str) -> Result<()> {
+    let dir = cache.object(&cache_dir(path));
+    let entries = match dir.blocking_list() {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        entry?.blocking_delete()?;
+    }
+
+    match dir.blocking_delete() {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Remove cached contents of `path` that don't belong to `version`.
+///
+/// Objects changed without this layer will leave contents of old versions
+/// in cache, remove them once a new version is found.
+async fn remove_stale(cache: &Operator, path: &str, version: &str) -> Result<()> {
+    let prefix = cache_version_prefix(path, version);
+    let mut entries = match cache.object(&cache_dir(path)).list().await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    while let Some(entry) = entries.next().await {
+        let entry = entry?;
+        if !entry.path().starts_with(&prefix) {
+            entry.delete().await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writer that invalidates cached contents after closed.
+struct InvalidateOnClose<T> {
+    inner: T,
+    cache: Operator,
+    path: String,
+    invalidating: Option<BoxFuture<'static, Result<()>>>,
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for InvalidateOnClose<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        if self.invalidating.is_none() {
+            let res = ready!(Pin::new(&mut self.inner).poll_close(cx));
+            let (cache, path) = (self.cache.clone(), self.path.clone());
+            self.invalidating = Some(Box::pin(async move {
+                let invalidated = invalidate(&cache, &path).await;
+                res.and(invalidated)
+            }));
+        }
+
+        let fut = self
+            .invalidating
+            .as_mut()
+            .expect("invalidating must be set");
+        let res = ready!(fut.as_mut().poll(cx));
+        self.invalidating = None;
+        Poll::Ready(res)
+    }
+}
+
+impl<T: Write> Write for InvalidateOnClose<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: BlockingBytesWrite> BlockingBytesWrite for InvalidateOnClose<T> {
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        res.and(blocking_invalidate(&self.cache, &self.path))
+    }
+}
+
+#[derive(Debug)]
+struct CacheAccessor {
+    inner: Arc<dyn Accessor>,
+    layer: CacheLayer,
+}
+
+impl CacheAccessor {
+    async fn invalidate(&self, path: &str) -> Result<()> {
+        invalidate(&self.layer.cache, path).await
+    }
+
+    fn blocking_invalidate(&self, path: &str) -> Result<()> {
+        blocking_invalidate(&self.layer.cache, path)
+    }
+
+    fn invalidate_on_close<T>(&self, inner: T, path: &str) -> InvalidateOnClose<T> {
+        InvalidateOnClose {
+            inner,
+            cache: self.layer.cache.clone(),
+            path: path.to_string(),
+            invalidating: None,
+        }
+    }
+}
+
+#[async_trait]
+impl LayeredAccessor for CacheAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        if !args.preconditions().is_empty() || !self.layer.is_cacheable(args.path()) {
+            return self.inner.read(args).await;
+        }
+
+        let meta = if self.layer.validation {
+            Some(self.inner.stat(&OpStat::new(args.path())?).await?)
+        } else {
+            None
+        };
+        let version = match &meta {
+            None => None,
+            Some(meta) => match version(meta) {
+                Some(v) => Some(v),
+                // Can't validate cached contents, bypass the cache.
+                None => return self.inner.read(args).await,
+            },
+        };
+
+        let key = cache_key(args.path(), version.as_deref(), args.offset(), args.size());
+        match self.layer.cache.object(&key).read().await {
+            Ok(bs) => return Ok(Box::new(Cursor::new(bs))),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => warn!(
+                target: "opendal::layers::cache",
+                "read cache of {} failed: {e:?}",
+                args.path()
+            ),
+        }
+
+        // Size of the range to read, unknown if validation is disabled and
+        // the read has no size.
+        let size = match (args.size(), &meta) {
+            (Some(size), _) => Some(size),
+            (None, Some(meta)) => Some(
+                meta.content_length()
+                    .saturating_sub(args.offset().unwrap_or_default()),
+            ),
+            (None, None) => None,
+        };
+        if matches!(size, Some(size) if size > self.layer.max_size) {
+            return self.inner.read(args).await;
+        }
+
+        // Read at most `max_size + 1` bytes so that we can tell whether the
+        // content is too large without another `stat`.
+        let mut bs = Vec::with_capacity(size.unwrap_or_default() as usize);
+        let mut r = self.inner.read(args).await?;
+        (&mut r)
+            .take(self.layer.max_size + 1)
+            .read_to_end(&mut bs)
+            .await?;
+        if bs.len() as u64 > self.layer.max_size {
+            return Ok(Box::new(Cursor::new(bs).chain(r)));
+        }
+        if let Some(v) = &version {
+            if let Err(e) = remove_stale(&self.layer.cache, args.path(), v).await {
+                warn!(
+                    target: "opendal::layers::cache",
+                    "remove stale cache of {} failed: {e:?}",
+                    args.path()
+                );
+            }
+        }
+        if let Err(e) = self.layer.cache.object(&key).write(&bs).await {
+            warn!(
+                target: "opendal::layers::cache",
+                "write cache of {} failed: {e:?}",
+                args.path()
+            );
+        }
+
+        Ok(Box::new(Cursor::new(bs)))
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        self.invalidate(args.path()).await?;
+        let w = self.inner.write(args).await?;
+
+        Ok(Box::new(self.invalidate_on_close(w, args.path())))
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        self.inner.append(args, r).await?;
+        self.invalidate(args.path()).await
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        self.inner.delete(args).await?;
+        self.invalidate(args.path()).await
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        let results = self.inner.batch_delete(args).await?;
+        for path in args.paths() {
+            self.invalidate(path).await?;
+        }
+
+        Ok(results)
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        self.inner.copy(args).await?;
+        self.invalidate(args.to()).await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        self.inner.rename(args).await?;
+        self.invalidate(args.from()).await?;
+        self.invalidate(args.to()).await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        self.inner.complete_multipart(args).await?;
+        self.invalidate(args.path()).await
+    }
+
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        self.blocking_invalidate(args.path())?;
+        let w = self.inner.blocking_write(args)?;
+
+        Ok(Box::new(self.invalidate_on_close(w, args.path())))
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        self.inner.blocking_delete(args)?;
+        self.blocking_invalidate(args.path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::AsyncWriteExt;
+
+    use super::*;
+    use crate::services::memory;
+
+    #[test]
+    fn test_glob_match() {
+        let cases = vec![
+            ("*", "a/b/c", true),
+            ("*.parquet", "a/b.parquet", true),
+            ("*.parquet", "a/b.parquet.tmp", false),
+            ("a/?.csv", "a/b.csv", true),
+            ("a/?.csv", "a/bc.csv", false),
+            ("a/*/c", "a/b/c", true),
+            ("a/*/c", "a/b/d", false),
+            ("abc", "abc", true),
+            ("abc", "ab", false),
+            ("", "", true),
+        ];
+
+        for (pattern, path, expected) in cases {
+            assert_eq!(
+                glob_match(pattern.as_bytes(), path.as_bytes()),
+                expected,
+                "{pattern} vs {path}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_layer() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let cache = Operator::new(memory::Backend::build().finish().await?);
+        let op = source
+            .clone()
+            .layer(CacheLayer::new(cache).with_pattern("*.parquet"));
+
+        op.object("test.parquet").write("Hello, World!").await?;
+        op.object("test.csv").write("Hello, World!").await?;
+        assert_eq!(op.object("test.parquet").read().await?, b"Hello, World!");
+        assert_eq!(op.object("test.parquet").range_read(7..12).await?, b"World");
+        assert_eq!(op.object("test.csv").read().await?, b"Hello, World!");
+
+        // Changes made without this layer are not noticed.
+        source.object("test.parquet").write("Hello, Cache!").await?;
+        source.object("test.csv").write("Hello, Cache!").await?;
+        assert_eq!(op.object("test.parquet").read().await?, b"Hello, World!");
+        assert_eq!(op.object("test.parquet").range_read(7..12).await?, b"World");
+        // Paths that don't match patterns are not cached.
+        assert_eq!(op.object("test.csv").read().await?, b"Hello, Cache!");
+
+        // Write through layer invalidates cache.
+        op.object("test.parquet").write("Hello, Again!").await?;
+        assert_eq!(op.object("test.parquet").read().await?, b"Hello, Again!");
+        assert_eq!(op.object("test.parquet").range_read(7..12).await?, b"Again");
+
+        // Delete through layer invalidates cache.
+        op.object("test.parquet").delete().await?;
+        assert!(op.object("test.parquet").read().await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_layer_read_while_writing() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let cache = Operator::new(memory::Backend::build().finish().await?);
+        let op = source.layer(CacheLayer::new(cache));
+
+        op.object("test").write("Hello, World!").await?;
+
+        let mut w = op.object("test").writer(13).await?;
+        // Read during write caches the old content again.
+        assert_eq!(op.object("test").read().await?, b"Hello, World!");
+        w.write_all(b"Hello, Again!").await?;
+        w.close().await?;
+
+        assert_eq!(op.object("test").read().await?, b"Hello, Again!");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_layer_max_size() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let cache = Operator::new(memory::Backend::build().finish().await?);
+        let op = source
+            .clone()
+            .layer(CacheLayer::new(cache.clone()).with_max_size(6));
+
+        op.object("test").write("Hello, World!").await?;
+        // Reads larger than max size are returned as is but not cached.
+        assert_eq!(op.object("test").read().await?, b"Hello, World!");
+        assert_eq!(op.object("test").range_read(7..).await?, b"World!");
+
+        source.object("test").write("Hello, Cache!").await?;
+        assert_eq!(op.object("test").read().await?, b"Hello, Cache!");
+        // Range with offset only counts the remaining bytes.
+        assert_eq!(op.object("test").range_read(7..).await?, b"World!");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_layer_validation_with_offset() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let cache = Operator::new(memory::Backend::build().finish().await?);
+        let op = source.clone().layer(
+            CacheLayer::new(cache.clone())
+                .with_validation(true)
+                .with_max_size(6),
+        );
+
+        op.object("test").write("Hello, World!").await?;
+        assert_eq!(op.object("test").range_read(7..).await?, b"World!");
+
+        // Only the range is cached, the whole content is too large.
+        let dir = cache.object(&cache_dir("test"));
+        assert_eq!(dir.list().await?.count().await, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_layer_validation() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let cache = Operator::new(memory::Backend::build().finish().await?);
+        let op = source
+            .clone()
+            .layer(CacheLayer::new(cache.clone()).with_validation(true));
+
+        op.object("test").write("Hello, World!").await?;
+        assert_eq!(op.object("test").read().await?, b"Hello, World!");
+
+        // Changes made without this layer are noticed via ETag.
+        source.object("test").write("Hello, Cache!").await?;
+        assert_eq!(op.object("test").read().await?, b"Hello, Cache!");
+
+        // Contents of old versions are removed once a new version is found.
+        let dir = cache.object(&cache_dir("test"));
+        assert_eq!(dir.list().await?.count().await, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,470 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide concurrency limit support for all operations via [`ConcurrentLimitLayer`].
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::io::Result;
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use futures::AsyncRead;
+use futures::AsyncWrite;
+use futures::Stream;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+
+use crate::io_util::block_on;
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpPresign;
+use crate::ops::OpRead;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::PresignedRequest;
+use crate::Accessor;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWrite;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::Layer;
+use crate::Metadata;
+use crate::ObjectIterator;
+use crate::ObjectPart;
+use crate::ObjectStreamer;
+
+/// Operations that can be limited via [`ConcurrentLimitLayer::with_operation_limit`].
+const OPERATIONS: &[&str] = &[
+    "create",
+    "read",
+    "write",
+    "append",
+    "stat",
+    "delete",
+    "batch_delete",
+    "list",
+    "copy",
+    "rename",
+    "create_multipart",
+    "write_multipart",
+    "complete_multipart",
+    "abort_multipart",
+];
+
+/// ConcurrentLimitLayer will limit the number of in-flight operations.
+///
+/// - The total limit is set by [`ConcurrentLimitLayer::new`].
+/// - Limits of specific operations can be set via
+///   [`ConcurrentLimitLayer::with_operation_limit`], operations must meet
+///   both limits before they are started.
+///
+/// Permits are held until operations finished. For `read`, `write` and
+/// `list`, permits are held until the returned reader, writer or streamer
+/// is dropped.
+///
+/// Limits are shared by all operators that use the same layer (or its
+/// clones), and blocking operations share limits with the async ones, for
+/// example, `blocking_read` is limited by `read`.
+///
+/// # Notes
+///
+/// Blocking operations wait for permits in the same FIFO queue as async
+/// operations by parking the calling thread. Don't call them on threads of
+/// an async runtime (for example tokio workers), otherwise the tasks that
+/// hold permits could be starved and never release them. Use
+/// `tokio::task::spawn_blocking` instead.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::ConcurrentLimitLayer;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let op = Operator::new(memory::Backend::build().finish().await?)
+///     .layer(ConcurrentLimitLayer::new(1024).with_operation_limit("read", 64));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConcurrentLimitLayer {
+    permits: Option<Arc<Semaphore>>,
+    operations: HashMap<&'static str, Arc<Semaphore>>,
+}
+
+impl ConcurrentLimitLayer {
+    /// Create a new layer which allows at most `permits` in-flight operations.
+    pub fn new(permits: usize) -> Self {
+        Self {
+            permits: Some(Arc::new(Semaphore::new(permits))),
+            operations: HashMap::new(),
+        }
+    }
+
+    /// Set the limit of given operation, like `read` or `stat`.
+    ///
+    /// Operation names are the same as [`ObjectError::op`][crate::ObjectError::op].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `op` is not a known operation, for
+    /// example `Read` or `stat_object`.
+    #[must_use]
+    pub fn with_operation_limit(mut self, op: &'static str, permits: usize) -> Self {
+        assert!(
+            OPERATIONS.contains(&op),
+            "operation {op} is unknown, must be one of {OPERATIONS:?}"
+        );
+
+        self.operations
+            .insert(op, Arc::new(Semaphore::new(permits)));
+        self
+    }
+}
+
+impl Layer for ConcurrentLimitLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(ConcurrentLimitAccessor {
+            inner,
+            layer: self.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ConcurrentLimitAccessor {
+    inner: Arc<dyn Accessor>,
+    layer: ConcurrentLimitLayer,
+}
+
+impl ConcurrentLimitAccessor {
+    fn semaphores(&self, op: &'static str) -> Vec<Arc<Semaphore>> {
+        // Always acquire operation limit first so that operations waiting
+        // on their own limit don't hold the total permits.
+        self.layer
+            .operations
+            .get(op)
+            .into_iter()
+            .chain(self.layer.permits.iter())
+            .cloned()
+            .collect()
+    }
+
+    async fn acquire(&self, op: &'static str) -> Permits {
+        let mut permits = Vec::new();
+        for s in self.semaphores(op) {
+            permits.push(
+                s.acquire_owned()
+                    .await
+                    .expect("semaphore of concurrent limit must not be closed"),
+            );
+        }
+
+        permits
+    }
+
+    /// Acquire permits via the semaphore queue, so that blocking operations
+    /// are served in the same order as async ones.
+    fn blocking_acquire(&self, op: &'static str) -> Permits {
+        // `block_on` of the internal runtime panics within an async context,
+        // fallback to `futures::executor::block_on` there since semaphore of
+        // tokio doesn't depend on runtime.
+        if tokio::runtime::Handle::try_current().is_ok() {
+            futures::executor::block_on(self.acquire(op))
+        } else {
+            block_on(self.acquire(op))
+        }
+    }
+}
+
+type Permits = Vec<OwnedSemaphorePermit>;
+
+/// Wrapper that holds permits until dropped.
+struct Permitted<T> {
+    inner: T,
+    _permits: Permits,
+}
+
+impl<T> Permitted<T> {
+    fn new(inner: T, permits: Permits) -> Self {
+        Self {
+            inner,
+            _permits: permits,
+        }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for Permitted<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for Permitted<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+impl<T: Stream + Unpin> Stream for Permitted<T> {
+    type Item = T::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+impl<T: Read> Read for Permitted<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<T: Write> Write for Permitted<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: BlockingBytesWrite> BlockingBytesWrite for Permitted<T> {
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+impl<T: Iterator> Iterator for Permitted<T> {
+    type Item = T::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[async_trait]
+impl LayeredAccessor for ConcurrentLimitAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        let _permits = self.acquire("create").await;
+        self.inner.create(args).await
+    }
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let permits = self.acquire("read").await;
+        let r = self.inner.read(args).await?;
+
+        Ok(Box::new(Permitted::new(r, permits)))
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        let permits = self.acquire("write").await;
+        let w = self.inner.write(args).await?;
+
+        Ok(Box::new(Permitted::new(w, permits)))
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let _permits = self.acquire("append").await;
+        self.inner.append(args, r).await
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        let _permits = self.acquire("stat").await;
+        self.inner.stat(args).await
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        let _permits = self.acquire("delete").await;
+        self.inner.delete(args).await
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        let _permits = self.acquire("batch_delete").await;
+        self.inner.batch_delete(args).await
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        let permits = self.acquire("list").await;
+        let s = self.inner.list(args).await?;
+
+        Ok(Box::new(Permitted::new(s, permits)))
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let _permits = self.acquire("copy").await;
+        self.inner.copy(args).await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let _permits = self.acquire("rename").await;
+        self.inner.rename(args).await
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        let _permits = self.acquire("create_multipart").await;
+        self.inner.create_multipart(args).await
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        let _permits = self.acquire("write_multipart").await;
+        self.inner.write_multipart(args, r).await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        let _permits = self.acquire("complete_multipart").await;
+        self.inner.complete_multipart(args).await
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        let _permits = self.acquire("abort_multipart").await;
+        self.inner.abort_multipart(args).await
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        // Presign doesn't send any request, so it's not limited.
+        self.inner.presign(args)
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        let _permits = self.blocking_acquire("create");
+        self.inner.blocking_create(args)
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let permits = self.blocking_acquire("read");
+        let r = self.inner.blocking_read(args)?;
+
+        Ok(Box::new(Permitted::new(r, permits)))
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        let permits = self.blocking_acquire("write");
+        let w = self.inner.blocking_write(args)?;
+
+        Ok(Box::new(Permitted::new(w, permits)))
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        let _permits = self.blocking_acquire("stat");
+        self.inner.blocking_stat(args)
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        let _permits = self.blocking_acquire("delete");
+        self.inner.blocking_delete(args)
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        let permits = self.blocking_acquire("list");
+        let it = self.inner.blocking_list(args)?;
+
+        Ok(Box::new(Permitted::new(it, permits)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::services::memory;
+    use crate::Operator;
+
+    #[tokio::test]
+    async fn test_permits_held_by_reader() -> Result<()> {
+        let op = Operator::new(memory::Backend::build().finish().await?)
+            .layer(ConcurrentLimitLayer::new(1));
+        op.object("test").write("Hello, World!").await?;
+
+        let r = op.object("test").reader().await?;
+        // The only permit is held by reader, so stat can't start.
+        let res =
+            tokio::time::timeout(Duration::from_millis(10), op.object("test").metadata()).await;
+        assert!(res.is_err());
+
+        drop(r);
+        let meta = op.object("test").metadata().await?;
+        assert_eq!(meta.content_length(), 13);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_operation_limit() -> Result<()> {
+        let op = Operator::new(memory::Backend::build().finish().await?)
+            .layer(ConcurrentLimitLayer::default().with_operation_limit("read", 1));
+        op.object("test").write("Hello, World!").await?;
+
+        let _r = op.object("test").reader().await?;
+        // Read is limited, but other operations are not.
+        let res = tokio::time::timeout(Duration::from_millis(10), op.object("test").reader()).await;
+        assert!(res.is_err());
+        op.object("test").metadata().await?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "operation Read is unknown")]
+    fn test_operation_limit_unknown() {
+        let _ = ConcurrentLimitLayer::default().with_operation_limit("Read", 1);
+    }
+
+    #[tokio::test]
+    async fn test_blocking_permits() -> Result<()> {
+        let op = Operator::new(memory::Backend::build().finish().await?)
+            .layer(ConcurrentLimitLayer::new(1));
+        op.object("test").write("Hello, World!").await?;
+
+        let r = op.object("test").reader().await?;
+        let blocking_op = op.clone();
+        let mut handle =
+            tokio::task::spawn_blocking(move || blocking_op.object("test").blocking_metadata());
+
+        // The only permit is held by reader, so blocking stat must wait.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!((&mut handle).now_or_never().is_none());
+
+        drop(r);
+        let meta = handle.await??;
+        assert_eq!(meta.content_length(), 13);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Result;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpPresign;
+use crate::ops::OpRead;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::PresignedRequest;
+use crate::Accessor;
+use crate::AccessorMetadata;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::Metadata;
+use crate::ObjectIterator;
+use crate::ObjectPart;
+use crate::ObjectStreamer;
+
+/// LayeredAccessor is the accessor of layers that wraps an inner accessor.
+///
+/// All operations are forwarded to [`LayeredAccessor::inner`] by default,
+/// so layers only need to override the operations they intercept.
+/// [`Accessor`] is implemented for all `LayeredAccessor`, new operations
+/// only need to be added here.
+#[async_trait]
+pub trait LayeredAccessor: Send + Sync + Debug + 'static {
+    /// The accessor wrapped by this layer.
+    fn inner(&self) -> &Arc<dyn Accessor>;
+
+    fn metadata(&self) -> AccessorMetadata {
+        self.inner().metadata()
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        self.inner().create(args).await
+    }
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        self.inner().read(args).await
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        self.inner().write(args).await
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        self.inner().append(args, r).await
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        self.inner().stat(args).await
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        self.inner().delete(args).await
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        self.inner().batch_delete(args).await
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        self.inner().list(args).await
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        self.inner().copy(args).await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        self.inner().rename(args).await
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        self.inner().create_multipart(args).await
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        self.inner().write_multipart(args, r).await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        self.inner().complete_multipart(args).await
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        self.inner().abort_multipart(args).await
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        self.inner().presign(args)
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        self.inner().blocking_create(args)
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        self.inner().blocking_read(args)
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        self.inner().blocking_write(args)
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        self.inner().blocking_stat(args)
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        self.inner().blocking_delete(args)
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        self.inner().blocking_list(args)
+    }
+}
+
+#[async_trait]
+impl<L: LayeredAccessor> Accessor for L {
+    fn metadata(&self) -> AccessorMetadata {
+        LayeredAccessor::metadata(self)
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        LayeredAccessor::create(self, args).await
+    }
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        LayeredAccessor::read(self, args).await
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        LayeredAccessor::write(self, args).await
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        LayeredAccessor::append(self, args, r).await
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        LayeredAccessor::stat(self, args).await
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        LayeredAccessor::delete(self, args).await
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        LayeredAccessor::batch_delete(self, args).await
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        LayeredAccessor::list(self, args).await
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        LayeredAccessor::copy(self, args).await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        LayeredAccessor::rename(self, args).await
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        LayeredAccessor::create_multipart(self, args).await
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        LayeredAccessor::write_multipart(self, args, r).await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        LayeredAccessor::complete_multipart(self, args).await
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        LayeredAccessor::abort_multipart(self, args).await
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        LayeredAccessor::presign(self, args)
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        LayeredAccessor::blocking_create(self, args)
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        LayeredAccessor::blocking_read(self, args)
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        LayeredAccessor::blocking_write(self, args)
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        LayeredAccessor::blocking_stat(self, args)
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        LayeredAccessor::blocking_delete(self, args)
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        LayeredAccessor::blocking_list(self, args)
+    }
+}
@@ -0,0 +1,458 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide logging support for all operations via [`LoggingLayer`].
+
+use std::fmt::Debug;
+use std::io;
+use std::io::Result;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use log::debug;
+use log::log;
+use log::Level;
+
+use crate::io_util::observe_blocking_read;
+use crate::io_util::observe_blocking_write;
+use crate::io_util::observe_read;
+use crate::io_util::observe_write;
+use crate::io_util::ReadEvent;
+use crate::io_util::WriteEvent;
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpPresign;
+use crate::ops::OpRead;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::PresignedRequest;
+use crate::Accessor;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::ErrorKind;
+use crate::Layer;
+use crate::Metadata;
+use crate::ObjectIterator;
+use crate::ObjectPart;
+use crate::ObjectStreamer;
+use crate::Scheme;
+
+/// Target of all logs emitted by [`LoggingLayer`].
+static LOGGING_TARGET: &str = "opendal::layers::logging";
+
+/// LoggingLayer will log the start, finish and failure of every operation
+/// via [`log`](https://docs.rs/log).
+///
+/// - Operations will be logged with service, operation, path and
+///   range/size, and the latency after finished.
+/// - Readers and writers returned by `read`, `write`, `blocking_read` and
+///   `blocking_write` will log the bytes read/written after they are
+///   terminated or closed.
+/// - Failures will be logged at `Error` level with [`ErrorKind`], expected
+///   failures like `NotFound` will be logged at `Debug` level instead.
+///
+/// All logs are emitted under target `opendal::layers::logging`.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::LoggingLayer;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let op = Operator::new(memory::Backend::build().finish().await?).layer(LoggingLayer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct LoggingLayer;
+
+impl Layer for LoggingLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        let scheme = inner.metadata().scheme();
+
+        Arc::new(LoggingAccessor { scheme, inner })
+    }
+}
+
+#[derive(Debug)]
+struct LoggingAccessor {
+    scheme: Scheme,
+    inner: Arc<dyn Accessor>,
+}
+
+impl LoggingAccessor {
+    fn start(&self, op: &'static str, ctx: &str) -> Instant {
+        debug!(
+            target: LOGGING_TARGET,
+            "service={} operation={} {}: started", self.scheme, op, ctx
+        );
+
+        Instant::now()
+    }
+
+    fn finish<T>(&self, op: &'static str, ctx: &str, start: Instant, res: Result<T>) -> Result<T> {
+        match &res {
+            Ok(_) => debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} {}: finished in {:?}",
+                self.scheme,
+                op,
+                ctx,
+                start.elapsed()
+            ),
+            Err(e) => log_failure(self.scheme, op, ctx, start, e),
+        }
+
+        res
+    }
+}
+
+/// Log the failure of an operation, expected failures will be logged
+/// at `Debug` level so that they don't flood the logs.
+fn log_failure(scheme: Scheme, op: &'static str, ctx: &str, start: Instant, err: &io::Error) {
+    let kind = ErrorKind::of(err);
+    let level = match kind {
+        ErrorKind::NotFound | ErrorKind::ConditionNotMatch => Level::Debug,
+        _ => Level::Error,
+    };
+
+    log!(
+        target: LOGGING_TARGET,
+        level,
+        "service={} operation={} {}: failed in {:?}, kind={} error={}",
+        scheme,
+        op,
+        ctx,
+        start.elapsed(),
+        kind,
+        err
+    );
+}
+
+/// Build the handler of [`ReadEvent`] which logs the bytes read after the
+/// reader is terminated.
+fn read_logger(
+    scheme: Scheme,
+    op: &'static str,
+    ctx: String,
+    start: Instant,
+) -> impl FnMut(ReadEvent) + Send {
+    let mut read = 0;
+    let mut terminated = false;
+    move |e| match e {
+        ReadEvent::Read(n) => read += n,
+        ReadEvent::Terminated if !terminated => {
+            terminated = true;
+            debug!(
+                target: LOGGING_TARGET,
+                "service={} operation={} {}: reader terminated after read {} bytes in {:?}",
+                scheme,
+                op,
+                ctx,
+                read,
+                start.elapsed()
+            );
+        }
+        ReadEvent::Error(kind) => log_failure(
+            scheme,
+            op,
+            &ctx,
+            start,
+            &io::Error::new(kind, format!("reader failed after read {read} bytes")),
+        ),
+        _ => {}
+    }
+}
+
+/// Build the handler of [`WriteEvent`] which logs the bytes written after
+/// the writer is closed.
+fn write_logger(
+    scheme: Scheme,
+    op: &'static str,
+    ctx: String,
+    start: Instant,
+) -> impl FnMut(WriteEvent) + Send {
+    let mut written = 0;
+    move |e| match e {
+        WriteEvent::Written(n) => written += n,
+        WriteEvent::Closed => debug!(
+            target: LOGGING_TARGET,
+            "service={} operation={} {}: writer closed after written {} bytes in {:?}",
+            scheme,
+            op,
+            ctx,
+            written,
+            start.elapsed()
+        ),
+        WriteEvent::Error(kind) => log_failure(
+            scheme,
+            op,
+            &ctx,
+            start,
+            &io::Error::new(kind, format!("writer failed after written {written} bytes")),
+        ),
+        _ => {}
+    }
+}
+
+fn format_range(offset: Option<u64>, size: Option<u64>) -> String {
+    match (offset, size) {
+        (None, None) => "..".to_string(),
+        (offset, size) => {
+            let offset = offset.unwrap_or_default();
+            match size {
+                Some(size) => format!("{}..{}", offset, offset + size),
+                None => format!("{offset}.."),
+            }
+        }
+    }
+}
+
+fn format_write_size(args: &OpWrite) -> String {
+    if args.is_unsized() {
+        "unsized".to_string()
+    } else {
+        args.size().to_string()
+    }
+}
+
+#[async_trait]
+impl LayeredAccessor for LoggingAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        let ctx = format!("path={} mode={}", args.path(), args.mode());
+        let start = self.start("create", &ctx);
+        self.finish("create", &ctx, start, self.inner.create(args).await)
+    }
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let ctx = format!(
+            "path={} range={}",
+            args.path(),
+            format_range(args.offset(), args.size())
+        );
+        let start = self.start("read", &ctx);
+        let r = self.finish("read", &ctx, start, self.inner.read(args).await)?;
+
+        Ok(Box::new(observe_read(
+            r,
+            read_logger(self.scheme, "read", ctx, start),
+        )))
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        let ctx = format!("path={} size={}", args.path(), format_write_size(args));
+        let start = self.start("write", &ctx);
+        let w = self.finish("write", &ctx, start, self.inner.write(args).await)?;
+
+        Ok(Box::new(observe_write(
+            w,
+            write_logger(self.scheme, "write", ctx, start),
+        )))
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let ctx = format!("path={} size={}", args.path(), args.size());
+        let start = self.start("append", &ctx);
+        self.finish("append", &ctx, start, self.inner.append(args, r).await)
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        let ctx = format!("path={}", args.path());
+        let start = self.start("stat", &ctx);
+        self.finish("stat", &ctx, start, self.inner.stat(args).await)
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        let ctx = format!("path={}", args.path());
+        let start = self.start("delete", &ctx);
+        self.finish("delete", &ctx, start, self.inner.delete(args).await)
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        let ctx = format!("paths={}", args.paths().len());
+        let start = self.start("batch_delete", &ctx);
+        self.finish(
+            "batch_delete",
+            &ctx,
+            start,
+            self.inner.batch_delete(args).await,
+        )
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        let ctx = format!("path={} recursive={}", args.path(), args.recursive());
+        let start = self.start("list", &ctx);
+        self.finish("list", &ctx, start, self.inner.list(args).await)
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let ctx = format!("from={} to={}", args.from(), args.to());
+        let start = self.start("copy", &ctx);
+        self.finish("copy", &ctx, start, self.inner.copy(args).await)
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let ctx = format!("from={} to={}", args.from(), args.to());
+        let start = self.start("rename", &ctx);
+        self.finish("rename", &ctx, start, self.inner.rename(args).await)
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        let ctx = format!("path={}", args.path());
+        let start = self.start("create_multipart", &ctx);
+        self.finish(
+            "create_multipart",
+            &ctx,
+            start,
+            self.inner.create_multipart(args).await,
+        )
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        let ctx = format!(
+            "path={} upload_id={} part_number={} size={}",
+            args.path(),
+            args.upload_id(),
+            args.part_number(),
+            args.size()
+        );
+        let start = self.start("write_multipart", &ctx);
+        self.finish(
+            "write_multipart",
+            &ctx,
+            start,
+            self.inner.write_multipart(args, r).await,
+        )
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        let ctx = format!(
+            "path={} upload_id={} parts={}",
+            args.path(),
+            args.upload_id(),
+            args.parts().len()
+        );
+        let start = self.start("complete_multipart", &ctx);
+        self.finish(
+            "complete_multipart",
+            &ctx,
+            start,
+            self.inner.complete_multipart(args).await,
+        )
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        let ctx = format!("path={} upload_id={}", args.path(), args.upload_id());
+        let start = self.start("abort_multipart", &ctx);
+        self.finish(
+            "abort_multipart",
+            &ctx,
+            start,
+            self.inner.abort_multipart(args).await,
+        )
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        let ctx = format!("path={} operation={:?}", args.path(), args.operation());
+        let start = self.start("presign", &ctx);
+        self.finish("presign", &ctx, start, self.inner.presign(args))
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        let ctx = format!("path={} mode={}", args.path(), args.mode());
+        let start = self.start("blocking_create", &ctx);
+        self.finish(
+            "blocking_create",
+            &ctx,
+            start,
+            self.inner.blocking_create(args),
+        )
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let ctx = format!(
+            "path={} range={}",
+            args.path(),
+            format_range(args.offset(), args.size())
+        );
+        let start = self.start("blocking_read", &ctx);
+        let r = self.finish("blocking_read", &ctx, start, self.inner.blocking_read(args))?;
+
+        Ok(Box::new(observe_blocking_read(
+            r,
+            read_logger(self.scheme, "blocking_read", ctx, start),
+        )))
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        let ctx = format!("path={} size={}", args.path(), format_write_size(args));
+        let start = self.start("blocking_write", &ctx);
+        let w = self.finish(
+            "blocking_write",
+            &ctx,
+            start,
+            self.inner.blocking_write(args),
+        )?;
+
+        Ok(Box::new(observe_blocking_write(
+            w,
+            write_logger(self.scheme, "blocking_write", ctx, start),
+        )))
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        let ctx = format!("path={}", args.path());
+        let start = self.start("blocking_stat", &ctx);
+        self.finish("blocking_stat", &ctx, start, self.inner.blocking_stat(args))
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        let ctx = format!("path={}", args.path());
+        let start = self.start("blocking_delete", &ctx);
+        self.finish(
+            "blocking_delete",
+            &ctx,
+            start,
+            self.inner.blocking_delete(args),
+        )
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        let ctx = format!("path={} recursive={}", args.path(), args.recursive());
+        let start = self.start("blocking_list", &ctx);
+        self.finish("blocking_list", &ctx, start, self.inner.blocking_list(args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_range() {
+        let cases = vec![
+            (None, None, ".."),
+            (Some(1024), None, "1024.."),
+            (None, Some(1024), "0..1024"),
+            (Some(1024), Some(1024), "1024..2048"),
+        ];
+
+        for (offset, size, expected) in cases {
+            assert_eq!(format_range(offset, size), expected);
+        }
+    }
+}
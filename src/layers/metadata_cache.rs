@@ -0,0 +1,570 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide object metadata cache support via [`MetadataCacheLayer`].
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use futures::ready;
+use futures::AsyncWrite;
+use futures::StreamExt;
+use parking_lot::Mutex;
+
+use crate::error::ObjectError;
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpCreate;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::Accessor;
+use crate::BlockingBytesWrite;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::Layer;
+use crate::Metadata;
+use crate::Object;
+use crate::ObjectIterator;
+use crate::ObjectStreamer;
+
+/// Default TTL of cached metadata.
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+/// Default TTL of cached not found results.
+const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(10);
+/// Default max number of cached entries.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// MetadataCacheLayer will cache results of `stat` in memory.
+///
+/// - Metadata returned by `stat` will be cached for `ttl`.
+/// - `NotFound` errors returned by `stat` will be cached for `negative_ttl`.
+/// - Complete metadata of objects returned by `list` will be cached too,
+///   so `stat` after `list` doesn't need to send another request.
+/// - Cached metadata of a path (and its parent dirs) will be invalidated by
+///   `create`, `write`, `append`, `delete`, `batch_delete`, `copy`,
+///   `rename` and `complete_multipart` through this layer.
+///
+/// Different from [`Object::metadata_cached`], the cache is shared by all
+/// objects created from the same operator.
+///
+/// # Notes
+///
+/// At most `capacity` entries will be cached, the oldest entries will be
+/// evicted first. Every operator that uses this layer will have its own
+/// cache.
+///
+/// Changes made without this layer (for example by other processes) will
+/// not be noticed until the cached entries expire. `stat` with
+/// preconditions always bypasses the cache.
+///
+/// Only complete metadata returned by `list` will be cached. Entries listed
+/// from s3 don't carry content type, cache control and user metadata, so
+/// they are not cached and `stat` will still send a request for them.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::MetadataCacheLayer;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let layer = MetadataCacheLayer::new()
+///     .with_ttl(Duration::from_secs(300))
+///     .with_negative_ttl(Duration::from_secs(30))
+///     .with_capacity(100_000);
+/// let op = Operator::new(memory::Backend::build().finish().await?).layer(layer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MetadataCacheLayer {
+    ttl: Duration,
+    negative_ttl: Duration,
+    capacity: usize,
+}
+
+impl Default for MetadataCacheLayer {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_TTL,
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+}
+
+impl MetadataCacheLayer {
+    /// Create a new layer with default ttl (60s), negative ttl (10s) and
+    /// capacity (10000).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the ttl of cached metadata, zero means don't cache metadata.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the ttl of cached not found results, zero means don't cache
+    /// not found results.
+    #[must_use]
+    pub fn with_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Set the max number of cached entries.
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+}
+
+impl Layer for MetadataCacheLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(MetadataCacheAccessor {
+            inner,
+            cache: Arc::new(MetadataCache {
+                layer: *self,
+                state: Mutex::new(CacheState::default()),
+            }),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct MetadataCache {
+    layer: MetadataCacheLayer,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    /// Paths in insertion order with the seq of their entries, records
+    /// whose seq doesn't match the entry are outdated.
+    order: VecDeque<(String, u64)>,
+    seq: u64,
+    /// Bumped on every invalidation, so results of operations that started
+    /// before the invalidation will not be cached.
+    epoch: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    /// `None` means the object is not found.
+    meta: Option<Metadata>,
+    expire: Instant,
+    seq: u64,
+}
+
+impl MetadataCache {
+    fn epoch(&self) -> u64 {
+        self.state.lock().epoch
+    }
+
+    fn get(&self, path: &str) -> Option<Option<Metadata>> {
+        let mut state = self.state.lock();
+
+        match state.entries.get(path) {
+            Some(entry) if entry.expire > Instant::now() => Some(entry.meta.clone()),
+            Some(_) => {
+                state.entries.remove(path);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Insert metadata of path if no invalidation happened since `epoch`.
+    fn insert(&self, epoch: u64, path: &str, meta: Option<Metadata>) {
+        let ttl = match meta {
+            Some(_) => self.layer.ttl,
+            None => self.layer.negative_ttl,
+        };
+        if ttl.is_zero() || self.layer.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        if state.epoch != epoch {
+            return;
+        }
+
+        state.seq += 1;
+        let seq = state.seq;
+        state.entries.insert(
+            path.to_string(),
+            CacheEntry {
+                meta,
+                expire: Instant::now() + ttl,
+                seq,
+            },
+        );
+        state.order.push_back((path.to_string(), seq));
+
+        let CacheState { entries, order, .. } = &mut *state;
+        while entries.len() > self.layer.capacity {
+            match order.pop_front() {
+                Some((path, seq)) => {
+                    if entries.get(&path).map(|v| v.seq) == Some(seq) {
+                        entries.remove(&path);
+                    }
+                }
+                None => break,
+            }
+        }
+        // Drop outdated records so that order doesn't grow forever.
+        if order.len() > self.layer.capacity * 2 {
+            order.retain(|(path, seq)| entries.get(path).map(|v| v.seq) == Some(*seq));
+        }
+    }
+
+    /// Remove cached metadata of path and its parent dirs.
+    fn invalidate(&self, path: &str) {
+        let mut state = self.state.lock();
+        state.epoch += 1;
+
+        state.entries.remove(path);
+        let mut parent = path.trim_end_matches('/');
+        while let Some(idx) = parent.rfind('/') {
+            parent = &parent[..idx];
+            state.entries.remove(&format!("{parent}/"));
+        }
+        state.entries.remove("/");
+    }
+
+    /// Cache complete metadata of listed object.
+    fn seed(&self, epoch: u64, o: &Result<Object>) {
+        if let Ok(o) = o {
+            let meta = o.metadata_ref();
+            if meta.complete() {
+                self.insert(epoch, meta.path(), Some(meta.clone()));
+            }
+        }
+    }
+}
+
+/// Writer that invalidates cached metadata after closed.
+struct InvalidateOnClose<T> {
+    inner: T,
+    cache: Arc<MetadataCache>,
+    path: String,
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for InvalidateOnClose<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let res = ready!(Pin::new(&mut self.inner).poll_close(cx));
+        self.cache.invalidate(&self.path);
+        Poll::Ready(res)
+    }
+}
+
+impl<T: Write> Write for InvalidateOnClose<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: BlockingBytesWrite> BlockingBytesWrite for InvalidateOnClose<T> {
+    fn close(&mut self) -> Result<()> {
+        let res = self.inner.close();
+        self.cache.invalidate(&self.path);
+        res
+    }
+}
+
+#[derive(Debug)]
+struct MetadataCacheAccessor {
+    inner: Arc<dyn Accessor>,
+    cache: Arc<MetadataCache>,
+}
+
+impl MetadataCacheAccessor {
+    /// Returns cached result of stat if exists.
+    fn cached_stat(&self, args: &OpStat) -> Option<Result<Metadata>> {
+        if !args.preconditions().is_empty() {
+            return None;
+        }
+
+        self.cache.get(args.path()).map(|meta| {
+            meta.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NotFound,
+                    ObjectError::new("stat", args.path(), anyhow!("object not found (cached)")),
+                )
+            })
+        })
+    }
+
+    /// Cache result of stat.
+    fn cache_stat(&self, epoch: u64, args: &OpStat, res: &Result<Metadata>) {
+        if !args.preconditions().is_empty() {
+            return;
+        }
+
+        match res {
+            Ok(meta) => self.cache.insert(epoch, args.path(), Some(meta.clone())),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                self.cache.insert(epoch, args.path(), None)
+            }
+            Err(_) => {}
+        }
+    }
+
+    fn invalidate_on_close<T>(&self, inner: T, path: &str) -> InvalidateOnClose<T> {
+        InvalidateOnClose {
+            inner,
+            cache: self.cache.clone(),
+            path: path.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl LayeredAccessor for MetadataCacheAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        let res = self.inner.create(args).await;
+        self.cache.invalidate(args.path());
+        res
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        self.cache.invalidate(args.path());
+        let w = self.inner.write(args).await?;
+
+        Ok(Box::new(self.invalidate_on_close(w, args.path())))
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let res = self.inner.append(args, r).await;
+        self.cache.invalidate(args.path());
+        res
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        if let Some(res) = self.cached_stat(args) {
+            return res;
+        }
+
+        let epoch = self.cache.epoch();
+        let res = self.inner.stat(args).await;
+        self.cache_stat(epoch, args, &res);
+        res
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        let res = self.inner.delete(args).await;
+        self.cache.invalidate(args.path());
+        res
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        let res = self.inner.batch_delete(args).await;
+        for path in args.paths() {
+            self.cache.invalidate(path);
+        }
+        res
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        let epoch = self.cache.epoch();
+        let cache = self.cache.clone();
+        let s = self.inner.list(args).await?;
+
+        Ok(Box::new(s.inspect(move |o| cache.seed(epoch, o))))
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let res = self.inner.copy(args).await;
+        self.cache.invalidate(args.to());
+        res
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let res = self.inner.rename(args).await;
+        self.cache.invalidate(args.from());
+        self.cache.invalidate(args.to());
+        res
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        let res = self.inner.complete_multipart(args).await;
+        self.cache.invalidate(args.path());
+        res
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        let res = self.inner.blocking_create(args);
+        self.cache.invalidate(args.path());
+        res
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        self.cache.invalidate(args.path());
+        let w = self.inner.blocking_write(args)?;
+
+        Ok(Box::new(self.invalidate_on_close(w, args.path())))
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        if let Some(res) = self.cached_stat(args) {
+            return res;
+        }
+
+        let epoch = self.cache.epoch();
+        let res = self.inner.blocking_stat(args);
+        self.cache_stat(epoch, args, &res);
+        res
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        let res = self.inner.blocking_delete(args);
+        self.cache.invalidate(args.path());
+        res
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        let epoch = self.cache.epoch();
+        let cache = self.cache.clone();
+        let it = self.inner.blocking_list(args)?;
+
+        Ok(Box::new(it.inspect(move |o| cache.seed(epoch, o))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::memory;
+    use crate::Operator;
+
+    #[tokio::test]
+    async fn test_metadata_cache_layer() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let op = source.clone().layer(MetadataCacheLayer::new());
+
+        op.object("test").write("Hello, World!").await?;
+        assert_eq!(op.object("test").metadata().await?.content_length(), 13);
+
+        // Changes made without this layer are not noticed.
+        source.object("test").write("Hello").await?;
+        assert_eq!(op.object("test").metadata().await?.content_length(), 13);
+
+        // Write through layer invalidates cache.
+        op.object("test").write("Hello").await?;
+        assert_eq!(op.object("test").metadata().await?.content_length(), 5);
+
+        // Delete through layer invalidates cache.
+        op.object("test").delete().await?;
+        let err = op
+            .object("test")
+            .metadata()
+            .await
+            .expect_err("must not found");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cache_layer_negative() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let op = source
+            .clone()
+            .layer(MetadataCacheLayer::new().with_negative_ttl(Duration::from_millis(100)));
+
+        assert!(!op.object("test").is_exist().await?);
+        source.object("test").write("Hello, World!").await?;
+        assert!(!op.object("test").is_exist().await?);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(op.object("test").is_exist().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cache_layer_list() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let op = source.clone().layer(MetadataCacheLayer::new());
+
+        source.object("dir/test").write("Hello, World!").await?;
+        let mut obs = op.object("dir/").list().await?;
+        while let Some(o) = obs.next().await {
+            o?;
+        }
+
+        // Metadata is cached by list.
+        source.object("dir/test").write("Hello").await?;
+        assert_eq!(op.object("dir/test").metadata().await?.content_length(), 13);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cache_layer_capacity() -> anyhow::Result<()> {
+        let source = Operator::new(memory::Backend::build().finish().await?);
+        let op = source
+            .clone()
+            .layer(MetadataCacheLayer::new().with_capacity(1));
+
+        source.object("a").write("Hello, World!").await?;
+        source.object("b").write("Hello, World!").await?;
+        op.object("a").metadata().await?;
+        op.object("b").metadata().await?;
+
+        // `a` has been evicted by `b`.
+        source.object("a").write("Hello").await?;
+        source.object("b").write("Hello").await?;
+        assert_eq!(op.object("b").metadata().await?.content_length(), 13);
+        assert_eq!(op.object("a").metadata().await?.content_length(), 5);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,387 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide metrics support for all operations via [`MetricsLayer`].
+
+use std::io::Result;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use metrics::counter;
+use metrics::histogram;
+use metrics::increment_counter;
+
+use crate::io_util::observe_blocking_read;
+use crate::io_util::observe_blocking_write;
+use crate::io_util::observe_read;
+use crate::io_util::observe_write;
+use crate::io_util::ReadEvent;
+use crate::io_util::WriteEvent;
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpPresign;
+use crate::ops::OpRead;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::PresignedRequest;
+use crate::Accessor;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::ErrorKind;
+use crate::Layer;
+use crate::Metadata;
+use crate::ObjectIterator;
+use crate::ObjectPart;
+use crate::ObjectStreamer;
+use crate::Scheme;
+
+/// Counter of requests.
+static METRIC_REQUESTS_TOTAL: &str = "opendal_requests_total";
+/// Counter of failed requests.
+static METRIC_ERRORS_TOTAL: &str = "opendal_errors_total";
+/// Histogram of request latency in seconds.
+static METRIC_REQUESTS_DURATION_SECONDS: &str = "opendal_requests_duration_seconds";
+/// Counter of bytes read or written.
+static METRIC_BYTES_TOTAL: &str = "opendal_bytes_total";
+
+static LABEL_SERVICE: &str = "service";
+static LABEL_OPERATION: &str = "operation";
+static LABEL_ERROR: &str = "error";
+
+/// MetricsLayer will record metrics of every operation via
+/// [`metrics`](https://docs.rs/metrics).
+///
+/// Following metrics will be recorded with labels `service` and `operation`:
+///
+/// - `opendal_requests_total`: counter of requests.
+/// - `opendal_errors_total`: counter of failed requests, with extra label
+///   `error` of the [`ErrorKind`].
+/// - `opendal_requests_duration_seconds`: histogram of request latency.
+/// - `opendal_bytes_total`: counter of bytes read or written, bytes of
+///   `read` and `write` are counted while the returned reader/writer is used.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::MetricsLayer;
+/// use opendal::Operator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let op = Operator::new(memory::Backend::build().finish().await?).layer(MetricsLayer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct MetricsLayer;
+
+impl Layer for MetricsLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        let scheme = inner.metadata().scheme();
+
+        Arc::new(MetricsAccessor { scheme, inner })
+    }
+}
+
+#[derive(Debug)]
+struct MetricsAccessor {
+    scheme: Scheme,
+    inner: Arc<dyn Accessor>,
+}
+
+impl MetricsAccessor {
+    fn observe<T>(&self, op: &'static str, start: Instant, res: Result<T>) -> Result<T> {
+        let service = self.scheme.into_static();
+
+        increment_counter!(METRIC_REQUESTS_TOTAL, LABEL_SERVICE => service, LABEL_OPERATION => op);
+        histogram!(
+            METRIC_REQUESTS_DURATION_SECONDS,
+            start.elapsed().as_secs_f64(),
+            LABEL_SERVICE => service,
+            LABEL_OPERATION => op
+        );
+        if let Err(e) = &res {
+            increment_counter!(
+                METRIC_ERRORS_TOTAL,
+                LABEL_SERVICE => service,
+                LABEL_OPERATION => op,
+                LABEL_ERROR => ErrorKind::of(e).to_string()
+            );
+        }
+
+        res
+    }
+}
+
+fn record_bytes(scheme: Scheme, op: &'static str, n: u64) {
+    counter!(
+        METRIC_BYTES_TOTAL,
+        n,
+        LABEL_SERVICE => scheme.into_static(),
+        LABEL_OPERATION => op
+    );
+}
+
+fn record_error(scheme: Scheme, op: &'static str, kind: std::io::ErrorKind) {
+    increment_counter!(
+        METRIC_ERRORS_TOTAL,
+        LABEL_SERVICE => scheme.into_static(),
+        LABEL_OPERATION => op,
+        LABEL_ERROR => ErrorKind::from(kind).to_string()
+    );
+}
+
+#[async_trait]
+impl LayeredAccessor for MetricsAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        let start = Instant::now();
+        self.observe("create", start, self.inner.create(args).await)
+    }
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let start = Instant::now();
+        let r = self.observe("read", start, self.inner.read(args).await)?;
+
+        let scheme = self.scheme;
+        Ok(Box::new(observe_read(r, move |e| match e {
+            ReadEvent::Read(n) => record_bytes(scheme, "read", n as u64),
+            ReadEvent::Error(kind) => record_error(scheme, "read", kind),
+            _ => {}
+        })))
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        let start = Instant::now();
+        let w = self.observe("write", start, self.inner.write(args).await)?;
+
+        let scheme = self.scheme;
+        Ok(Box::new(observe_write(w, move |e| match e {
+            WriteEvent::Written(n) => record_bytes(scheme, "write", n as u64),
+            WriteEvent::Error(kind) => record_error(scheme, "write", kind),
+            _ => {}
+        })))
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let start = Instant::now();
+        self.observe("append", start, self.inner.append(args, r).await)?;
+
+        record_bytes(self.scheme, "append", args.size());
+        Ok(())
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        let start = Instant::now();
+        self.observe("stat", start, self.inner.stat(args).await)
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        let start = Instant::now();
+        self.observe("delete", start, self.inner.delete(args).await)
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        let start = Instant::now();
+        self.observe("batch_delete", start, self.inner.batch_delete(args).await)
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        let start = Instant::now();
+        self.observe("list", start, self.inner.list(args).await)
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let start = Instant::now();
+        self.observe("copy", start, self.inner.copy(args).await)
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let start = Instant::now();
+        self.observe("rename", start, self.inner.rename(args).await)
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        let start = Instant::now();
+        self.observe(
+            "create_multipart",
+            start,
+            self.inner.create_multipart(args).await,
+        )
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        let start = Instant::now();
+        let part = self.observe(
+            "write_multipart",
+            start,
+            self.inner.write_multipart(args, r).await,
+        )?;
+
+        record_bytes(self.scheme, "write_multipart", args.size());
+        Ok(part)
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        let start = Instant::now();
+        self.observe(
+            "complete_multipart",
+            start,
+            self.inner.complete_multipart(args).await,
+        )
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        let start = Instant::now();
+        self.observe(
+            "abort_multipart",
+            start,
+            self.inner.abort_multipart(args).await,
+        )
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        let start = Instant::now();
+        self.observe("presign", start, self.inner.presign(args))
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        let start = Instant::now();
+        self.observe("blocking_create", start, self.inner.blocking_create(args))
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let start = Instant::now();
+        let r = self.observe("blocking_read", start, self.inner.blocking_read(args))?;
+
+        let scheme = self.scheme;
+        Ok(Box::new(observe_blocking_read(r, move |e| match e {
+            ReadEvent::Read(n) => record_bytes(scheme, "blocking_read", n as u64),
+            ReadEvent::Error(kind) => record_error(scheme, "blocking_read", kind),
+            _ => {}
+        })))
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        let start = Instant::now();
+        let w = self.observe("blocking_write", start, self.inner.blocking_write(args))?;
+
+        let scheme = self.scheme;
+        Ok(Box::new(observe_blocking_write(w, move |e| match e {
+            WriteEvent::Written(n) => record_bytes(scheme, "blocking_write", n as u64),
+            WriteEvent::Error(kind) => record_error(scheme, "blocking_write", kind),
+            _ => {}
+        })))
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        let start = Instant::now();
+        self.observe("blocking_stat", start, self.inner.blocking_stat(args))
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        let start = Instant::now();
+        self.observe("blocking_delete", start, self.inner.blocking_delete(args))
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        let start = Instant::now();
+        self.observe("blocking_list", start, self.inner.blocking_list(args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics_util::debugging::DebugValue;
+    use metrics_util::debugging::DebuggingRecorder;
+    use metrics_util::debugging::Snapshotter;
+    use once_cell::sync::Lazy;
+
+    use super::*;
+    use crate::services::memory;
+    use crate::Operator;
+
+    /// Recorder can only be installed once, share it among tests.
+    static SNAPSHOTTER: Lazy<Snapshotter> = Lazy::new(|| {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        recorder.install().expect("install recorder must succeed");
+        snapshotter
+    });
+
+    /// Find the value of metric `name` with the given labels.
+    fn find(name: &str, labels: &[(&str, &str)]) -> Option<DebugValue> {
+        SNAPSHOTTER
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .find(|(key, _, _, _)| {
+                let key = key.key();
+                key.name() == name
+                    && labels.iter().all(|(k, v)| {
+                        key.labels()
+                            .any(|label| label.key() == *k && label.value() == *v)
+                    })
+            })
+            .map(|(_, _, _, value)| value)
+    }
+
+    fn counter(name: &str, labels: &[(&str, &str)]) -> u64 {
+        match find(name, labels) {
+            Some(DebugValue::Counter(v)) => v,
+            v => panic!("counter {name} {labels:?} not found, got {v:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_layer() -> anyhow::Result<()> {
+        Lazy::force(&SNAPSHOTTER);
+        let op = Operator::new(memory::Backend::build().finish().await?).layer(MetricsLayer);
+
+        op.object("test").write("Hello, World!").await?;
+        assert_eq!(op.object("test").read().await?, b"Hello, World!");
+        assert!(op.object("not_exist").metadata().await.is_err());
+
+        let op = op.clone();
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            op.object("blocking").blocking_write("Hello, World!")?;
+            assert_eq!(op.object("blocking").blocking_read()?, b"Hello, World!");
+            Ok(())
+        })
+        .await??;
+
+        for op in ["write", "read", "stat", "blocking_write", "blocking_read"] {
+            let labels = [(LABEL_SERVICE, "memory"), (LABEL_OPERATION, op)];
+            assert!(counter(METRIC_REQUESTS_TOTAL, &labels) >= 1, "{op}");
+            match find(METRIC_REQUESTS_DURATION_SECONDS, &labels) {
+                Some(DebugValue::Histogram(v)) => assert!(!v.is_empty(), "{op}"),
+                v => panic!("histogram of {op} not found, got {v:?}"),
+            }
+        }
+
+        for op in ["write", "read", "blocking_write", "blocking_read"] {
+            let labels = [(LABEL_SERVICE, "memory"), (LABEL_OPERATION, op)];
+            assert_eq!(counter(METRIC_BYTES_TOTAL, &labels), 13, "{op}");
+        }
+
+        let labels = [
+            (LABEL_SERVICE, "memory"),
+            (LABEL_OPERATION, "stat"),
+            (LABEL_ERROR, "NotFound"),
+        ];
+        assert_eq!(counter(METRIC_ERRORS_TOTAL, &labels), 1);
+
+        Ok(())
+    }
+}
@@ -15,5 +15,32 @@
 mod layer;
 pub use layer::Layer;
 
+mod layered;
+pub(crate) use layered::LayeredAccessor;
+
+mod cache;
+pub use cache::CacheLayer;
+
+mod concurrent_limit;
+pub use concurrent_limit::ConcurrentLimitLayer;
+
+mod logging;
+pub use logging::LoggingLayer;
+
+mod metadata_cache;
+pub use metadata_cache::MetadataCacheLayer;
+
+mod metrics;
+pub use self::metrics::MetricsLayer;
+
+mod throttle;
+pub use throttle::ThrottleLayer;
+
+mod timeout;
+pub use timeout::TimeoutLayer;
+
+mod tracing;
+pub use self::tracing::TracingLayer;
+
 #[cfg(feature = "retry")]
 mod retry;
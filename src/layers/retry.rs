@@ -15,6 +15,7 @@
 //! Provide backoff retry support via implement [`Layer`] for [`backon::Backoff`](https://docs.rs/backon/latest/backon/trait.Backoff.html)
 
 use std::fmt::Debug;
+use std::io::Error;
 use std::io::ErrorKind;
 use std::io::Result;
 use std::sync::Arc;
@@ -22,17 +23,32 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use backon::Retryable;
 
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
+use crate::ops::OpPresign;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::PresignedRequest;
 use crate::Accessor;
+use crate::AccessorMetadata;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWriter;
 use crate::BytesReader;
 use crate::BytesWriter;
 use crate::Layer;
 use crate::Metadata;
+use crate::ObjectIterator;
+use crate::ObjectPart;
 use crate::ObjectStreamer;
 
 /// Implement [`Layer`] for [`backon::Backoff`](https://docs.rs/backon/latest/backon/trait.Backoff.html) so that all backoff can be used as a layer
@@ -66,6 +82,11 @@ where
     }
 }
 
+/// Temporary errors like `Interrupted` and `TimedOut` are retryable.
+fn is_retryable(e: &Error) -> bool {
+    matches!(e.kind(), ErrorKind::Interrupted | ErrorKind::TimedOut)
+}
+
 #[derive(Debug)]
 struct RetryableAccessor<B: backon::Backoff + Debug + Send + Sync> {
     inner: Arc<dyn Accessor>,
@@ -79,6 +100,22 @@ where
     fn create(inner: Arc<dyn Accessor>, backoff: B) -> Self {
         Self { inner, backoff }
     }
+
+    /// Retry blocking operations with backoff if the error is retryable.
+    fn blocking_retry<T>(&self, mut f: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut backoff = self.backoff.clone();
+
+        loop {
+            match f() {
+                Ok(v) => return Ok(v),
+                Err(e) if is_retryable(&e) => match backoff.next() {
+                    Some(dur) => std::thread::sleep(dur),
+                    None => return Err(e),
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -86,42 +123,113 @@ impl<B> Accessor for RetryableAccessor<B>
 where
     B: backon::Backoff + Debug + Send + Sync,
 {
+    fn metadata(&self) -> AccessorMetadata {
+        self.inner.metadata()
+    }
+
     async fn create(&self, args: &OpCreate) -> Result<()> {
         { || self.inner.create(args) }
             .retry(self.backoff.clone())
-            .with_error_fn(|e| e.kind() == ErrorKind::Interrupted)
+            .with_error_fn(is_retryable)
             .await
     }
     async fn read(&self, args: &OpRead) -> Result<BytesReader> {
         { || self.inner.read(args) }
             .retry(self.backoff.clone())
-            .with_error_fn(|e| e.kind() == ErrorKind::Interrupted)
+            .with_error_fn(is_retryable)
             .await
     }
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
         { || self.inner.write(args) }
             .retry(self.backoff.clone())
-            .with_error_fn(|e| e.kind() == ErrorKind::Interrupted)
+            .with_error_fn(is_retryable)
             .await
     }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        // The input reader will be consumed, so we can't retry `append` here.
+        self.inner.append(args, r).await
+    }
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
         { || self.inner.stat(args) }
             .retry(self.backoff.clone())
-            .with_error_fn(|e| e.kind() == ErrorKind::Interrupted)
+            .with_error_fn(is_retryable)
             .await
     }
     async fn delete(&self, args: &OpDelete) -> Result<()> {
         { || self.inner.delete(args) }
             .retry(self.backoff.clone())
-            .with_error_fn(|e| e.kind() == ErrorKind::Interrupted)
+            .with_error_fn(is_retryable)
+            .await
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        { || self.inner.batch_delete(args) }
+            .retry(self.backoff.clone())
+            .with_error_fn(is_retryable)
             .await
     }
     async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
         { || self.inner.list(args) }
             .retry(self.backoff.clone())
-            .with_error_fn(|e| e.kind() == ErrorKind::Interrupted)
+            .with_error_fn(is_retryable)
             .await
     }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        { || self.inner.copy(args) }
+            .retry(self.backoff.clone())
+            .with_error_fn(is_retryable)
+            .await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        { || self.inner.rename(args) }
+            .retry(self.backoff.clone())
+            .with_error_fn(is_retryable)
+            .await
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        { || self.inner.create_multipart(args) }
+            .retry(self.backoff.clone())
+            .with_error_fn(is_retryable)
+            .await
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        // The input reader will be consumed, so we can't retry `write_multipart` here.
+        self.inner.write_multipart(args, r).await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        { || self.inner.complete_multipart(args) }
+            .retry(self.backoff.clone())
+            .with_error_fn(is_retryable)
+            .await
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        { || self.inner.abort_multipart(args) }
+            .retry(self.backoff.clone())
+            .with_error_fn(is_retryable)
+            .await
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        self.inner.presign(args)
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        self.blocking_retry(|| self.inner.blocking_create(args))
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        self.blocking_retry(|| self.inner.blocking_read(args))
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        self.blocking_retry(|| self.inner.blocking_write(args))
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        self.blocking_retry(|| self.inner.blocking_stat(args))
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        self.blocking_retry(|| self.inner.blocking_delete(args))
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        self.blocking_retry(|| self.inner.blocking_list(args))
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +244,7 @@ mod tests {
     use tokio::sync::Mutex;
 
     use crate::error::other;
+    use crate::io_util::block_on;
     use crate::ops::OpRead;
     use crate::Accessor;
     use crate::BytesReader;
@@ -157,6 +266,10 @@ mod tests {
                     io::ErrorKind::Interrupted,
                     anyhow!("retryable_error"),
                 )),
+                "timed_out_error" => Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    anyhow!("timed_out_error"),
+                )),
                 _ => Err(other(anyhow!("not_retryable_error"))),
             }
         }
@@ -180,6 +293,24 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_retry_timed_out_error() -> anyhow::Result<()> {
+        let srv = Arc::new(MockService::default());
+
+        let backoff = ConstantBackoff::default()
+            .with_delay(Duration::from_micros(1))
+            .with_max_times(10);
+        let op = Operator::new(srv.clone()).layer(backoff);
+
+        let result = op.object("timed_out_error").read().await;
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().to_string(), "timed_out_error");
+        // Timeouts are retryable too.
+        assert_eq!(*srv.attempt.lock().await, 11);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_retry_not_retryable_error() -> anyhow::Result<()> {
         let srv = Arc::new(MockService::default());
@@ -195,4 +326,20 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_blocking_retry_retryable_error() {
+        let srv = Arc::new(MockService::default());
+
+        let backoff = ConstantBackoff::default()
+            .with_delay(Duration::from_micros(1))
+            .with_max_times(10);
+        let op = Operator::new(srv.clone()).layer(backoff);
+
+        let result = op.object("retryable_error").blocking_read();
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().to_string(), "retryable_error");
+        // The error is retryable, we should request it 1 + 10 times.
+        assert_eq!(*block_on(srv.attempt.lock()), 11);
+    }
 }
@@ -0,0 +1,386 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide bandwidth throttling support via [`ThrottleLayer`].
+
+use std::future::Future;
+use std::io::Read;
+use std::io::Result;
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use futures::AsyncRead;
+use futures::AsyncWrite;
+use parking_lot::Mutex;
+use tokio::time::Sleep;
+
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAppend;
+use crate::ops::OpRead;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::Accessor;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWrite;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::Layer;
+use crate::ObjectPart;
+
+/// ThrottleLayer will limit the bandwidth of bytes read and written via
+/// token buckets.
+///
+/// - Bytes read via readers returned by `read` are limited by read bandwidth.
+/// - Bytes written via writers returned by `write` and input readers of
+///   `append` and `write_multipart` are limited by write bandwidth.
+///
+/// Buckets are shared by all operators that use the same layer (or its
+/// clones), so a layer can throttle the whole process. Create a new layer
+/// for every operator to throttle them separately.
+///
+/// # Notes
+///
+/// Timers of async readers/writers are driven by tokio, so they must be
+/// used inside tokio runtime.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::Operator;
+/// use opendal::ThrottleLayer;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// // Read at most 10 MiB/s and write at most 5 MiB/s.
+/// let layer = ThrottleLayer::new()
+///     .with_read_bandwidth(10 * 1024 * 1024)
+///     .with_write_bandwidth(5 * 1024 * 1024);
+/// let op = Operator::new(memory::Backend::build().finish().await?).layer(layer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleLayer {
+    read: Option<Arc<TokenBucket>>,
+    write: Option<Arc<TokenBucket>>,
+}
+
+impl ThrottleLayer {
+    /// Create a new layer without any limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit bytes read to `bytes_per_second`, allowing bursts of one
+    /// second's worth of bytes.
+    #[must_use]
+    pub fn with_read_bandwidth(mut self, bytes_per_second: u64) -> Self {
+        self.read = Some(Arc::new(TokenBucket::new(bytes_per_second)));
+        self
+    }
+
+    /// Limit bytes written to `bytes_per_second`, allowing bursts of one
+    /// second's worth of bytes.
+    #[must_use]
+    pub fn with_write_bandwidth(mut self, bytes_per_second: u64) -> Self {
+        self.write = Some(Arc::new(TokenBucket::new(bytes_per_second)));
+        self
+    }
+}
+
+impl Layer for ThrottleLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(ThrottleAccessor {
+            inner,
+            layer: self.clone(),
+        })
+    }
+}
+
+/// Token bucket which allows consuming tokens in debt, callers need to
+/// wait for the returned duration before consuming again.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_second: u64) -> Self {
+        assert!(bytes_per_second > 0, "bandwidth must be greater than 0");
+
+        let rate = bytes_per_second as f64;
+        Self {
+            rate,
+            state: Mutex::new((rate, Instant::now())),
+        }
+    }
+
+    /// Max bytes that can be consumed at once.
+    fn burst(&self) -> usize {
+        self.rate as usize
+    }
+
+    /// Consume `n` tokens, returns the duration to wait before next consuming.
+    fn consume(&self, n: usize) -> Duration {
+        let mut state = self.state.lock();
+        let (tokens, last) = &mut *state;
+
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * self.rate).min(self.rate);
+        *last = now;
+        *tokens -= n as f64;
+
+        if *tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-*tokens / self.rate)
+        }
+    }
+}
+
+/// Async reader or writer throttled by bucket.
+struct Throttled<T> {
+    inner: T,
+    bucket: Arc<TokenBucket>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<T> Throttled<T> {
+    fn new(inner: T, bucket: Arc<TokenBucket>) -> Self {
+        Self {
+            inner,
+            bucket,
+            sleep: None,
+        }
+    }
+
+    /// Wait until bucket has tokens for us.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if let Some(sleep) = &mut self.sleep {
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.sleep = None;
+        }
+
+        Poll::Ready(())
+    }
+
+    fn consume(&mut self, n: usize) {
+        let wait = self.bucket.consume(n);
+        if !wait.is_zero() {
+            self.sleep = Some(Box::pin(tokio::time::sleep(wait)));
+        }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for Throttled<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        if self.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let size = buf.len().min(self.bucket.burst());
+        let poll = Pin::new(&mut self.inner).poll_read(cx, &mut buf[..size]);
+        if let Poll::Ready(Ok(n)) = poll {
+            self.consume(n);
+        }
+
+        poll
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for Throttled<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        if self.poll_ready(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let size = buf.len().min(self.bucket.burst());
+        let poll = Pin::new(&mut self.inner).poll_write(cx, &buf[..size]);
+        if let Poll::Ready(Ok(n)) = poll {
+            self.consume(n);
+        }
+
+        poll
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+/// Blocking reader or writer throttled by bucket.
+struct BlockingThrottled<T> {
+    inner: T,
+    bucket: Arc<TokenBucket>,
+}
+
+impl<T: Read> Read for BlockingThrottled<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let size = buf.len().min(self.bucket.burst());
+        let n = self.inner.read(&mut buf[..size])?;
+        std::thread::sleep(self.bucket.consume(n));
+
+        Ok(n)
+    }
+}
+
+impl<T: Write> Write for BlockingThrottled<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let size = buf.len().min(self.bucket.burst());
+        let n = self.inner.write(&buf[..size])?;
+        std::thread::sleep(self.bucket.consume(n));
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: BlockingBytesWrite> BlockingBytesWrite for BlockingThrottled<T> {
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+#[derive(Debug)]
+struct ThrottleAccessor {
+    inner: Arc<dyn Accessor>,
+    layer: ThrottleLayer,
+}
+
+impl ThrottleAccessor {
+    /// Throttle input reader of `append` and `write_multipart` with write bandwidth.
+    fn throttle_input(&self, r: BytesReader) -> BytesReader {
+        match &self.layer.write {
+            None => r,
+            Some(bucket) => Box::new(Throttled::new(r, bucket.clone())),
+        }
+    }
+}
+
+#[async_trait]
+impl LayeredAccessor for ThrottleAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let r = self.inner.read(args).await?;
+
+        Ok(match &self.layer.read {
+            None => r,
+            Some(bucket) => Box::new(Throttled::new(r, bucket.clone())),
+        })
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        let w = self.inner.write(args).await?;
+
+        Ok(match &self.layer.write {
+            None => w,
+            Some(bucket) => Box::new(Throttled::new(w, bucket.clone())),
+        })
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        self.inner.append(args, self.throttle_input(r)).await
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        self.inner
+            .write_multipart(args, self.throttle_input(r))
+            .await
+    }
+
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let r = self.inner.blocking_read(args)?;
+
+        Ok(match &self.layer.read {
+            None => r,
+            Some(bucket) => Box::new(BlockingThrottled {
+                inner: r,
+                bucket: bucket.clone(),
+            }),
+        })
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        let w = self.inner.blocking_write(args)?;
+
+        Ok(match &self.layer.write {
+            None => w,
+            Some(bucket) => Box::new(BlockingThrottled {
+                inner: w,
+                bucket: bucket.clone(),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::memory;
+    use crate::Operator;
+
+    #[test]
+    fn test_token_bucket() {
+        let bucket = TokenBucket::new(1024);
+
+        // Burst of one second is allowed.
+        assert_eq!(bucket.consume(1024), Duration::ZERO);
+        // Consuming in debt needs to wait.
+        let wait = bucket.consume(512);
+        assert!(wait > Duration::from_millis(400), "{wait:?}");
+        assert!(wait <= Duration::from_millis(500), "{wait:?}");
+    }
+
+    #[tokio::test]
+    async fn test_throttle_read() -> Result<()> {
+        let op = Operator::new(memory::Backend::build().finish().await?)
+            .layer(ThrottleLayer::new().with_read_bandwidth(1024));
+        op.object("test").write(vec![0; 1536]).await?;
+
+        let start = Instant::now();
+        let bs = op.object("test").read().await?;
+        assert_eq!(bs.len(), 1536);
+        // The first 1024 bytes are allowed as burst, the left 512 bytes
+        // need to wait about half a second.
+        assert!(start.elapsed() >= Duration::from_millis(400));
+
+        Ok(())
+    }
+}
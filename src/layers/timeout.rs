@@ -0,0 +1,475 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide timeout support for operations and stalled streams via [`TimeoutLayer`].
+
+use std::future::Future;
+use std::io::Error;
+use std::io::Result;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use futures::AsyncRead;
+use futures::AsyncWrite;
+use tokio::time::Sleep;
+
+use crate::error::ObjectError;
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpRead;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::Accessor;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::Layer;
+use crate::Metadata;
+use crate::ObjectStreamer;
+
+/// TimeoutLayer will return an error if operations or streams can't make
+/// progress in time.
+///
+/// - Deadlines of `stat`, `delete` and `list` can be set separately, other
+///   operations like `create`, `copy` or opening a reader/writer will use
+///   the default deadline.
+/// - IO timeout applies to readers and writers returned by `read` and
+///   `write`: an error will be returned if they make no progress in the
+///   given duration.
+///
+/// Timeouts are returned as [`std::io::ErrorKind::TimedOut`] with kind
+/// [`ErrorKind::TimedOut`][crate::ErrorKind::TimedOut], which is retryable,
+/// so please add retry layer like [`Operator::with_backoff`][crate::Operator::with_backoff]
+/// after this layer to retry them.
+///
+/// # Notes
+///
+/// - Timers are driven by tokio, so operations must run inside tokio runtime.
+/// - `append`, `write_multipart` and blocking operations will not time out,
+///   because their input could be arbitrarily large or they can't be cancelled.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::Operator;
+/// use opendal::TimeoutLayer;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let op = Operator::new(memory::Backend::build().finish().await?)
+///     .layer(
+///         TimeoutLayer::new()
+///             .with_timeout(Duration::from_secs(10))
+///             .with_stat_timeout(Duration::from_secs(3))
+///             .with_io_timeout(Duration::from_secs(30)),
+///     );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TimeoutLayer {
+    timeout: Option<Duration>,
+    stat_timeout: Option<Duration>,
+    delete_timeout: Option<Duration>,
+    list_timeout: Option<Duration>,
+    io_timeout: Option<Duration>,
+}
+
+impl TimeoutLayer {
+    /// Create a new timeout layer without any timeout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the default deadline of operations.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline of `stat`, overrides the default deadline.
+    #[must_use]
+    pub fn with_stat_timeout(mut self, timeout: Duration) -> Self {
+        self.stat_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline of `delete` and `batch_delete`, overrides the default deadline.
+    #[must_use]
+    pub fn with_delete_timeout(mut self, timeout: Duration) -> Self {
+        self.delete_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the deadline of `list`, overrides the default deadline.
+    ///
+    /// Only the request to start listing is covered, the returned streamer
+    /// will not time out.
+    #[must_use]
+    pub fn with_list_timeout(mut self, timeout: Duration) -> Self {
+        self.list_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the idle timeout of readers and writers.
+    #[must_use]
+    pub fn with_io_timeout(mut self, timeout: Duration) -> Self {
+        self.io_timeout = Some(timeout);
+        self
+    }
+}
+
+impl Layer for TimeoutLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        Arc::new(TimeoutAccessor {
+            inner,
+            layer: *self,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct TimeoutAccessor {
+    inner: Arc<dyn Accessor>,
+    layer: TimeoutLayer,
+}
+
+fn timeout_error(op: &'static str, path: &str, timeout: Duration) -> Error {
+    ObjectError::new(op, path, anyhow!("operation timed out after {timeout:?}"))
+        .with_kind(crate::ErrorKind::TimedOut)
+        .into()
+}
+
+/// Run future with deadline, the future will be dropped if timed out.
+async fn with_deadline<T>(
+    op: &'static str,
+    path: &str,
+    timeout: Option<Duration>,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout {
+        None => fut.await,
+        Some(timeout) => tokio::time::timeout(timeout, fut)
+            .await
+            .map_err(|_| timeout_error(op, path, timeout))?,
+    }
+}
+
+/// Check whether the stream has been idle for too long.
+///
+/// `sleep` will be started once the stream returns `Pending`, and reset
+/// after the stream makes progress.
+fn poll_idle(
+    sleep: &mut Option<Pin<Box<Sleep>>>,
+    timeout: Duration,
+    cx: &mut Context<'_>,
+) -> Poll<()> {
+    sleep
+        .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)))
+        .as_mut()
+        .poll(cx)
+}
+
+/// Reader that returns error if no progress in `timeout`.
+struct TimeoutReader {
+    inner: BytesReader,
+    path: String,
+    timeout: Duration,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl AsyncRead for TimeoutReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let this = &mut *self;
+        match Pin::new(&mut this.inner).poll_read(cx, buf) {
+            Poll::Ready(v) => {
+                this.sleep = None;
+                Poll::Ready(v)
+            }
+            Poll::Pending => match poll_idle(&mut this.sleep, this.timeout, cx) {
+                Poll::Ready(()) => {
+                    this.sleep = None;
+                    Poll::Ready(Err(timeout_error("read", &this.path, this.timeout)))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+/// Writer that returns error if no progress in `timeout`.
+struct TimeoutWriter {
+    inner: BytesWriter,
+    path: String,
+    timeout: Duration,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl TimeoutWriter {
+    fn poll_with_timeout<T>(
+        &mut self,
+        cx: &mut Context<'_>,
+        f: impl FnOnce(Pin<&mut BytesWriter>, &mut Context<'_>) -> Poll<Result<T>>,
+    ) -> Poll<Result<T>> {
+        match f(Pin::new(&mut self.inner), cx) {
+            Poll::Ready(v) => {
+                self.sleep = None;
+                Poll::Ready(v)
+            }
+            Poll::Pending => match poll_idle(&mut self.sleep, self.timeout, cx) {
+                Poll::Ready(()) => {
+                    self.sleep = None;
+                    Poll::Ready(Err(timeout_error("write", &self.path, self.timeout)))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+impl AsyncWrite for TimeoutWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        self.poll_with_timeout(cx, |w, cx| w.poll_write(cx, buf))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_with_timeout(cx, |w, cx| w.poll_flush(cx))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_with_timeout(cx, |w, cx| w.poll_close(cx))
+    }
+}
+
+#[async_trait]
+impl LayeredAccessor for TimeoutAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        with_deadline(
+            "create",
+            args.path(),
+            self.layer.timeout,
+            self.inner.create(args),
+        )
+        .await
+    }
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let r = with_deadline(
+            "read",
+            args.path(),
+            self.layer.timeout,
+            self.inner.read(args),
+        )
+        .await?;
+
+        Ok(match self.layer.io_timeout {
+            None => r,
+            Some(timeout) => Box::new(TimeoutReader {
+                inner: r,
+                path: args.path().to_string(),
+                timeout,
+                sleep: None,
+            }),
+        })
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        let w = with_deadline(
+            "write",
+            args.path(),
+            self.layer.timeout,
+            self.inner.write(args),
+        )
+        .await?;
+
+        Ok(match self.layer.io_timeout {
+            None => w,
+            Some(timeout) => Box::new(TimeoutWriter {
+                inner: w,
+                path: args.path().to_string(),
+                timeout,
+                sleep: None,
+            }),
+        })
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        with_deadline(
+            "stat",
+            args.path(),
+            self.layer.stat_timeout.or(self.layer.timeout),
+            self.inner.stat(args),
+        )
+        .await
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        with_deadline(
+            "delete",
+            args.path(),
+            self.layer.delete_timeout.or(self.layer.timeout),
+            self.inner.delete(args),
+        )
+        .await
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        with_deadline(
+            "batch_delete",
+            "/",
+            self.layer.delete_timeout.or(self.layer.timeout),
+            self.inner.batch_delete(args),
+        )
+        .await
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        with_deadline(
+            "list",
+            args.path(),
+            self.layer.list_timeout.or(self.layer.timeout),
+            self.inner.list(args),
+        )
+        .await
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        with_deadline(
+            "copy",
+            args.from(),
+            self.layer.timeout,
+            self.inner.copy(args),
+        )
+        .await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        with_deadline(
+            "rename",
+            args.from(),
+            self.layer.timeout,
+            self.inner.rename(args),
+        )
+        .await
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        with_deadline(
+            "create_multipart",
+            args.path(),
+            self.layer.timeout,
+            self.inner.create_multipart(args),
+        )
+        .await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        with_deadline(
+            "complete_multipart",
+            args.path(),
+            self.layer.timeout,
+            self.inner.complete_multipart(args),
+        )
+        .await
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        with_deadline(
+            "abort_multipart",
+            args.path(),
+            self.layer.timeout,
+            self.inner.abort_multipart(args),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use futures::AsyncReadExt;
+
+    use super::*;
+    use crate::Operator;
+
+    #[derive(Debug)]
+    struct MockService;
+
+    #[async_trait]
+    impl Accessor for MockService {
+        async fn read(&self, _: &OpRead) -> Result<BytesReader> {
+            Ok(Box::new(PendingReader))
+        }
+
+        async fn stat(&self, _: &OpStat) -> Result<Metadata> {
+            futures::future::pending().await
+        }
+    }
+
+    /// Reader that never makes progress.
+    struct PendingReader;
+
+    impl AsyncRead for PendingReader {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &mut [u8],
+        ) -> Poll<Result<usize>> {
+            Poll::Pending
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operation_timeout() {
+        let op = Operator::new(Arc::new(MockService))
+            .layer(TimeoutLayer::new().with_stat_timeout(Duration::from_millis(10)));
+
+        let err = op.object("test").metadata().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::TimedOut);
+    }
+
+    #[tokio::test]
+    async fn test_io_timeout() -> Result<()> {
+        let op = Operator::new(Arc::new(MockService))
+            .layer(TimeoutLayer::new().with_io_timeout(Duration::from_millis(10)));
+
+        let mut r = op.object("test").reader().await?;
+        let mut buf = vec![0; 1024];
+        let err = r.read(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::TimedOut);
+
+        Ok(())
+    }
+}
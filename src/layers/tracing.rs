@@ -0,0 +1,455 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provide tracing support for all operations via [`TracingLayer`].
+
+use std::future::Future;
+use std::io::Result;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use futures::AsyncRead;
+use futures::AsyncWrite;
+use futures::Stream;
+use pin_project::pin_project;
+
+use crate::layers::LayeredAccessor;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
+use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
+use crate::ops::OpDelete;
+use crate::ops::OpList;
+use crate::ops::OpPresign;
+use crate::ops::OpRead;
+use crate::ops::OpRename;
+use crate::ops::OpStat;
+use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::PresignedRequest;
+use crate::Accessor;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWriter;
+use crate::BytesReader;
+use crate::BytesWriter;
+use crate::Layer;
+use crate::Metadata;
+use crate::Object;
+use crate::ObjectIterator;
+use crate::ObjectPart;
+use crate::ObjectStreamer;
+use crate::Scheme;
+
+/// TracingLayer will open a span for every operation.
+///
+/// Spans of `read`, `write` and `list` will be kept open across the
+/// returned reader, writer and streamer until they are terminated, closed
+/// or dropped, so that the whole streaming of bytes is covered.
+///
+/// Spans are reported via [`minitrace`](https://docs.rs/minitrace). If
+/// feature `layers-tracing` is enabled, spans will also be reported via
+/// [`tracing`](https://docs.rs/tracing) with `service` and `path` fields.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::services::memory;
+/// use opendal::Operator;
+/// use opendal::TracingLayer;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<()> {
+/// let op = Operator::new(memory::Backend::build().finish().await?).layer(TracingLayer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TracingLayer;
+
+impl Layer for TracingLayer {
+    fn layer(&self, inner: Arc<dyn Accessor>) -> Arc<dyn Accessor> {
+        let scheme = inner.metadata().scheme();
+
+        Arc::new(TracingAccessor { scheme, inner })
+    }
+}
+
+#[derive(Debug)]
+struct TracingAccessor {
+    #[cfg_attr(not(feature = "layers-tracing"), allow(dead_code))]
+    scheme: Scheme,
+    inner: Arc<dyn Accessor>,
+}
+
+impl TracingAccessor {
+    #[cfg_attr(not(feature = "layers-tracing"), allow(unused_variables))]
+    fn span(&self, op: &'static str, path: &str) -> OpSpan {
+        OpSpan {
+            minitrace: minitrace::Span::enter_with_local_parent(op),
+            #[cfg(feature = "layers-tracing")]
+            tracing: ::tracing::info_span!(
+                "opendal",
+                operation = op,
+                service = self.scheme.into_static(),
+                path = path
+            ),
+        }
+    }
+}
+
+/// Span of an operation, which is reported via all enabled tracing backends.
+struct OpSpan {
+    minitrace: minitrace::Span,
+    #[cfg(feature = "layers-tracing")]
+    tracing: ::tracing::Span,
+}
+
+impl OpSpan {
+    /// Run `f` with this span as the current parent.
+    fn in_span<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _minitrace = self.minitrace.set_local_parent();
+        #[cfg(feature = "layers-tracing")]
+        let _tracing = self.tracing.enter();
+
+        f()
+    }
+}
+
+/// Future that polls inner future within span.
+#[pin_project]
+struct InSpan<'a, F> {
+    #[pin]
+    inner: F,
+    span: &'a OpSpan,
+}
+
+impl<'a, F: Future> Future for InSpan<'a, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let inner = this.inner;
+        this.span.in_span(|| inner.poll(cx))
+    }
+}
+
+fn in_span<F: Future>(span: &OpSpan, inner: F) -> InSpan<'_, F> {
+    InSpan { inner, span }
+}
+
+/// Reader that keeps span open until terminated or dropped.
+struct TracingReader {
+    inner: BytesReader,
+    span: Option<OpSpan>,
+}
+
+impl AsyncRead for TracingReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let this = &mut *self;
+        let inner = &mut this.inner;
+        let poll = match &this.span {
+            Some(span) => span.in_span(|| Pin::new(inner).poll_read(cx, buf)),
+            None => Pin::new(inner).poll_read(cx, buf),
+        };
+        if let Poll::Ready(Ok(0) | Err(_)) = poll {
+            this.span.take();
+        }
+
+        poll
+    }
+}
+
+/// Writer that keeps span open until closed or dropped.
+struct TracingWriter {
+    inner: BytesWriter,
+    span: Option<OpSpan>,
+}
+
+impl TracingWriter {
+    fn poll_in_span<T>(
+        &mut self,
+        f: impl FnOnce(Pin<&mut BytesWriter>) -> Poll<Result<T>>,
+    ) -> Poll<Result<T>> {
+        let inner = Pin::new(&mut self.inner);
+        let poll = match &self.span {
+            Some(span) => span.in_span(|| f(inner)),
+            None => f(inner),
+        };
+        if let Poll::Ready(Err(_)) = poll {
+            self.span.take();
+        }
+
+        poll
+    }
+}
+
+impl AsyncWrite for TracingWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        self.poll_in_span(|w| w.poll_write(cx, buf))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_in_span(|w| w.poll_flush(cx))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let poll = self.poll_in_span(|w| w.poll_close(cx));
+        if poll.is_ready() {
+            self.span.take();
+        }
+
+        poll
+    }
+}
+
+/// Streamer that keeps span open until exhausted or dropped.
+struct TracingStreamer {
+    inner: ObjectStreamer,
+    span: Option<OpSpan>,
+}
+
+impl Stream for TracingStreamer {
+    type Item = Result<Object>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let inner = &mut this.inner;
+        let poll = match &this.span {
+            Some(span) => span.in_span(|| Pin::new(inner).poll_next(cx)),
+            None => Pin::new(inner).poll_next(cx),
+        };
+        if let Poll::Ready(None) = poll {
+            this.span.take();
+        }
+
+        poll
+    }
+}
+
+#[async_trait]
+impl LayeredAccessor for TracingAccessor {
+    fn inner(&self) -> &Arc<dyn Accessor> {
+        &self.inner
+    }
+
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        let span = self.span("create", args.path());
+        in_span(&span, self.inner.create(args)).await
+    }
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let span = self.span("read", args.path());
+        let r = in_span(&span, self.inner.read(args)).await?;
+
+        Ok(Box::new(TracingReader {
+            inner: r,
+            span: Some(span),
+        }))
+    }
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        let span = self.span("write", args.path());
+        let w = in_span(&span, self.inner.write(args)).await?;
+
+        Ok(Box::new(TracingWriter {
+            inner: w,
+            span: Some(span),
+        }))
+    }
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let span = self.span("append", args.path());
+        in_span(&span, self.inner.append(args, r)).await
+    }
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        let span = self.span("stat", args.path());
+        in_span(&span, self.inner.stat(args)).await
+    }
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        let span = self.span("delete", args.path());
+        in_span(&span, self.inner.delete(args)).await
+    }
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        let span = self.span("batch_delete", "");
+        in_span(&span, self.inner.batch_delete(args)).await
+    }
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        let span = self.span("list", args.path());
+        let s = in_span(&span, self.inner.list(args)).await?;
+
+        Ok(Box::new(TracingStreamer {
+            inner: s,
+            span: Some(span),
+        }))
+    }
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let span = self.span("copy", args.from());
+        in_span(&span, self.inner.copy(args)).await
+    }
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let span = self.span("rename", args.from());
+        in_span(&span, self.inner.rename(args)).await
+    }
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        let span = self.span("create_multipart", args.path());
+        in_span(&span, self.inner.create_multipart(args)).await
+    }
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        let span = self.span("write_multipart", args.path());
+        in_span(&span, self.inner.write_multipart(args, r)).await
+    }
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        let span = self.span("complete_multipart", args.path());
+        in_span(&span, self.inner.complete_multipart(args)).await
+    }
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        let span = self.span("abort_multipart", args.path());
+        in_span(&span, self.inner.abort_multipart(args)).await
+    }
+
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        self.span("presign", args.path())
+            .in_span(|| self.inner.presign(args))
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        self.span("blocking_create", args.path())
+            .in_span(|| self.inner.blocking_create(args))
+    }
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        self.span("blocking_read", args.path())
+            .in_span(|| self.inner.blocking_read(args))
+    }
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        self.span("blocking_write", args.path())
+            .in_span(|| self.inner.blocking_write(args))
+    }
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        self.span("blocking_stat", args.path())
+            .in_span(|| self.inner.blocking_stat(args))
+    }
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        self.span("blocking_delete", args.path())
+            .in_span(|| self.inner.blocking_delete(args))
+    }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        self.span("blocking_list", args.path())
+            .in_span(|| self.inner.blocking_list(args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::AsyncReadExt;
+    use futures::AsyncWriteExt;
+    use futures::StreamExt;
+    use minitrace::collector::SpanRecord;
+
+    use super::*;
+    use crate::services::memory;
+    use crate::Operator;
+
+    /// Find the child span of `parent` by name, panic if not found.
+    ///
+    /// Services report spans with the same names as children of ours, so
+    /// the parent must be checked too.
+    fn find<'a>(spans: &'a [SpanRecord], parent: &SpanRecord, name: &str) -> &'a SpanRecord {
+        spans
+            .iter()
+            .find(|s| s.parent_id == parent.id && s.event == name)
+            .unwrap_or_else(|| panic!("span {name} must be reported"))
+    }
+
+    fn end_of(span: &SpanRecord) -> u64 {
+        span.begin_unix_time_ns + span.duration_ns
+    }
+
+    /// Report an empty span as a point in time.
+    fn mark(name: &'static str) {
+        drop(minitrace::Span::enter_with_local_parent(name));
+    }
+
+    #[tokio::test]
+    async fn test_tracing_layer() -> Result<()> {
+        let op = Operator::new(memory::Backend::build().finish().await?).layer(TracingLayer);
+
+        let (root, collector) = minitrace::Span::root("root");
+        {
+            let _guard = root.set_local_parent();
+
+            let mut w = op.object("dir/test").writer(13).await?;
+            w.write_all(b"Hello, ").await?;
+            mark("writing");
+            w.write_all(b"World!").await?;
+            w.close().await?;
+            mark("written");
+
+            let mut r = op.object("dir/test").reader().await?;
+            let mut buf = vec![0; 7];
+            r.read_exact(&mut buf).await?;
+            mark("reading");
+            r.read_to_end(&mut buf).await?;
+            assert_eq!(buf, b"Hello, World!");
+            mark("read");
+
+            let mut ds = op.object("dir/").list().await?;
+            let mut names = vec![];
+            while let Some(de) = ds.next().await {
+                names.push(de?.path());
+                mark("listing");
+            }
+            assert_eq!(names, vec!["dir/test".to_string()]);
+            drop(ds);
+            mark("listed");
+        }
+        drop(root);
+        let spans = collector.collect().await;
+
+        let root = spans
+            .iter()
+            .find(|s| s.event == "root")
+            .expect("root span must be reported");
+
+        // Spans are kept open until the streaming is finished.
+        for (name, during, after) in [
+            ("write", "writing", "written"),
+            ("read", "reading", "read"),
+            ("list", "listing", "listed"),
+        ] {
+            let span = find(&spans, root, name);
+            let during = find(&spans, root, during);
+            let after = find(&spans, root, after);
+            assert!(
+                span.begin_unix_time_ns <= during.begin_unix_time_ns,
+                "{name}"
+            );
+            assert!(end_of(span) >= end_of(during), "{name}");
+            assert!(end_of(span) <= after.begin_unix_time_ns, "{name}");
+        }
+
+        Ok(())
+    }
+}
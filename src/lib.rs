@@ -86,9 +86,23 @@
 // Private module with public types, they will be accessed via `opendal::Xxxx`
 mod accessor;
 pub use accessor::Accessor;
+pub use accessor::AccessorCapability;
 pub use accessor::AccessorMetadata;
 
+mod config;
+pub use config::Config;
+
+mod error;
+pub use error::BackendError;
+pub use error::Error;
+pub use error::ErrorKind;
+pub use error::ObjectError;
+
 mod io;
+pub use io::BlockingBytesRead;
+pub use io::BlockingBytesReader;
+pub use io::BlockingBytesWrite;
+pub use io::BlockingBytesWriter;
 pub use io::BytesRead;
 pub use io::BytesReader;
 pub use io::BytesSink;
@@ -97,27 +111,39 @@ pub use io::BytesWrite;
 pub use io::BytesWriter;
 
 mod layers;
+pub use layers::CacheLayer;
+pub use layers::ConcurrentLimitLayer;
 pub use layers::Layer;
+pub use layers::LoggingLayer;
+pub use layers::MetadataCacheLayer;
+pub use layers::MetricsLayer;
+pub use layers::ThrottleLayer;
+pub use layers::TimeoutLayer;
+pub use layers::TracingLayer;
 
 mod operator;
+pub use operator::BatchOperator;
 pub use operator::Operator;
 
+mod multipart;
+pub use multipart::ObjectMultipart;
+pub use multipart::ObjectPart;
+
 mod object;
 pub use object::Metadata;
 pub use object::Object;
+pub use object::ObjectIterate;
+pub use object::ObjectIterator;
 pub use object::ObjectMode;
 pub use object::ObjectStream;
 pub use object::ObjectStreamer;
 
 mod scheme;
+pub use scheme::register_scheme;
 pub use scheme::Scheme;
+pub use scheme::SchemeFactory;
 
 // Public modules, they will be accessed via `opendal::io_util::Xxxx`
 pub mod io_util;
 pub mod ops;
 pub mod services;
-
-// Private modules, internal use only.
-//
-// Please don't export any type from this module.
-mod error;
@@ -0,0 +1,150 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Result;
+use std::sync::Arc;
+
+use futures::io::Cursor;
+
+use crate::io_util::MultipartWriter;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpWriteMultipart;
+use crate::Accessor;
+
+/// Handler for multipart upload of an object.
+///
+/// Multipart upload allows users to upload an object in several parts, parts
+/// can be uploaded independently and in any order. After all parts have been
+/// uploaded, users need to call [`ObjectMultipart::complete`] to assemble them
+/// into a single object, or [`ObjectMultipart::abort`] to discard them.
+///
+/// `ObjectMultipart` is created by [`Object::create_multipart`][crate::Object::create_multipart]
+/// or [`Object::multipart`][crate::Object::multipart].
+#[derive(Clone, Debug)]
+pub struct ObjectMultipart {
+    acc: Arc<dyn Accessor>,
+    path: String,
+    upload_id: String,
+}
+
+impl ObjectMultipart {
+    pub(crate) fn new(acc: Arc<dyn Accessor>, path: &str, upload_id: &str) -> Self {
+        Self {
+            acc,
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Path of the object that this multipart upload will create.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// ID of this multipart upload.
+    ///
+    /// Users can persist this id and resume the upload later via
+    /// [`Object::multipart`][crate::Object::multipart].
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Upload a part with the given part number.
+    ///
+    /// Part number starts from `1`, uploading the same part number again will
+    /// overwrite the previous one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::services::s3;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(s3::Backend::build().bucket("test").finish().await?);
+    /// let mp = op.object("path/to/file").create_multipart().await?;
+    /// let part = mp.write(1, vec![0; 5 * 1024 * 1024]).await?;
+    /// mp.complete(vec![part]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write(&self, part_number: usize, bs: impl Into<Vec<u8>>) -> Result<ObjectPart> {
+        let bs = bs.into();
+        let op = OpWriteMultipart::new(&self.path, &self.upload_id, part_number, bs.len() as u64)?;
+
+        self.acc
+            .write_multipart(&op, Box::new(Cursor::new(bs)))
+            .await
+    }
+
+    /// Complete this multipart upload with given parts.
+    ///
+    /// Parts will be assembled in the order of input.
+    pub async fn complete(&self, parts: Vec<ObjectPart>) -> Result<()> {
+        let op = OpCompleteMultipart::new(&self.path, &self.upload_id, parts)?;
+
+        self.acc.complete_multipart(&op).await
+    }
+
+    /// Abort this multipart upload, all uploaded parts will be discarded.
+    pub async fn abort(&self) -> Result<()> {
+        let op = OpAbortMultipart::new(&self.path, &self.upload_id)?;
+
+        self.acc.abort_multipart(&op).await
+    }
+
+    /// Create a [`MultipartWriter`] which will split input data into parts
+    /// of `part_size` and upload them in parallel.
+    ///
+    /// Read [`MultipartWriter`] for more details.
+    pub fn writer(&self, part_size: usize) -> MultipartWriter {
+        MultipartWriter::new(self.clone(), part_size)
+    }
+
+    pub(crate) fn accessor(&self) -> Arc<dyn Accessor> {
+        self.acc.clone()
+    }
+}
+
+/// Part of an multipart upload, returned by [`ObjectMultipart::write`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ObjectPart {
+    part_number: usize,
+    etag: String,
+}
+
+impl ObjectPart {
+    /// Create a new part.
+    ///
+    /// Users only need to construct part by hand while resuming a multipart
+    /// upload from persisted states.
+    pub fn new(part_number: usize, etag: &str) -> Self {
+        Self {
+            part_number,
+            etag: etag.to_string(),
+        }
+    }
+
+    /// Part number of this part.
+    pub fn part_number(&self) -> usize {
+        self.part_number
+    }
+
+    /// ETag of this part returned by services.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+}
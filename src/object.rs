@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io::ErrorKind;
+use std::io::Read;
 use std::io::Result;
 use std::ops::RangeBounds;
 use std::sync::Arc;
@@ -23,6 +25,7 @@ use std::sync::Arc;
 use futures::io;
 use futures::io::Cursor;
 use futures::AsyncWriteExt;
+use time::Duration;
 use time::OffsetDateTime;
 
 use crate::io::BytesRead;
@@ -31,17 +34,44 @@ use crate::io_util::seekable_read;
 use crate::io_util::CompressAlgorithm;
 #[cfg(feature = "compress")]
 use crate::io_util::DecompressReader;
+use crate::io_util::MultipartWriter;
 use crate::io_util::SeekableReader;
+use crate::ops::OpAppend;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
+use crate::ops::OpPresign;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::PresignOperation;
+use crate::ops::PresignedRequest;
 use crate::Accessor;
+use crate::BlockingBytesRead;
+use crate::BlockingBytesWrite;
 use crate::BytesWrite;
+use crate::ObjectMultipart;
 
 /// Handler for all object related operations.
+///
+/// # Blocking operations
+///
+/// `blocking_*` methods may fall back to running async operations on an
+/// internal runtime via [`block_on`][crate::io_util::block_on], which panics
+/// if called within an async execution context (like a tokio worker thread).
+/// This happens when:
+///
+/// - The service doesn't support blocking operations natively, like s3 and
+///   azblob. Readers, writers and iterators returned by them also call
+///   `block_on` on every read, write or next.
+/// - The operation has no native blocking implementation in the service,
+///   like recursive [`Accessor::blocking_list`] in fs and hdfs.
+///
+/// Call them via `tokio::task::spawn_blocking` or from threads outside the
+/// runtime to be safe.
 #[derive(Clone, Debug)]
 pub struct Object {
     acc: Arc<dyn Accessor>,
@@ -213,6 +243,34 @@ impl Object {
         }
     }
 
+    /// Create an empty object in blocking way.
+    ///
+    /// Read [`Object::create`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// let o = op.object("path/to/file");
+    /// let _ = o.blocking_create()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_create(&self) -> Result<()> {
+        if self.meta.path.ends_with('/') {
+            let op = OpCreate::new(self.meta.path(), ObjectMode::DIR)?;
+            self.acc.blocking_create(&op)
+        } else {
+            let op = OpCreate::new(self.meta.path(), ObjectMode::FILE)?;
+            self.acc.blocking_create(&op)
+        }
+    }
+
     /// Read the whole object into a bytes.
     ///
     /// This function will allocate a new bytes internally. For more precise memory control or
@@ -238,6 +296,61 @@ impl Object {
         self.range_read(..).await
     }
 
+    /// Read the whole object into a bytes in blocking way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// # let o = op.object("path/to/file");
+    /// # o.blocking_write(&vec![0; 4096])?;
+    /// let bs = o.blocking_read()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_read(&self) -> Result<Vec<u8>> {
+        self.blocking_range_read(..)
+    }
+
+    /// Read the whole object into a bytes with extra options like preconditions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::Preconditions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// # let o = op.object("path/to/file");
+    /// # o.write(&vec![0; 4096]).await?;
+    /// let meta = o.metadata().await?;
+    /// let bs = o
+    ///     .read_with(|op| {
+    ///         op.with_preconditions(Preconditions::new().with_if_match(meta.etag().unwrap()))
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_with(&self, f: impl FnOnce(OpRead) -> OpRead) -> Result<Vec<u8>> {
+        let op = f(OpRead::new(self.meta.path(), ..)?);
+        let s = self.acc.read(&op).await?;
+
+        let mut bs = Cursor::new(Vec::new());
+
+        io::copy(s, &mut bs).await?;
+
+        Ok(bs.into_inner())
+    }
+
     /// Read the specified range of object into a bytes.
     ///
     /// This function will allocate a new bytes internally. For more precise memory control or
@@ -270,6 +383,33 @@ impl Object {
         Ok(bs.into_inner())
     }
 
+    /// Read the specified range of object into a bytes in blocking way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// # let o = op.object("path/to/file");
+    /// # o.blocking_write(&vec![0; 4096])?;
+    /// let bs = o.blocking_range_read(1024..2048)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_range_read(&self, range: impl RangeBounds<u64>) -> Result<Vec<u8>> {
+        let op = OpRead::new(self.meta.path(), range)?;
+        let mut s = self.acc.blocking_read(&op)?;
+
+        let mut bs = Vec::new();
+        s.read_to_end(&mut bs)?;
+
+        Ok(bs)
+    }
+
     /// Create a new reader which can read the whole object.
     ///
     /// # Examples
@@ -315,6 +455,31 @@ impl Object {
         self.acc.read(&op).await
     }
 
+    /// Create a new blocking reader which can read the specified range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// # let o = op.object("path/to/file");
+    /// # o.blocking_write(&vec![0; 4096])?;
+    /// let r = o.blocking_range_reader(1024..2048)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_range_reader(
+        &self,
+        range: impl RangeBounds<u64>,
+    ) -> Result<impl BlockingBytesRead> {
+        let op = OpRead::new(self.meta.path(), range)?;
+        self.acc.blocking_read(&op)
+    }
+
     /// Create a reader which implements AsyncRead and AsyncSeek inside specified range.
     ///
     /// # Notes
@@ -514,6 +679,68 @@ impl Object {
         Ok(())
     }
 
+    /// Write bytes into object in blocking way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// let o = op.object("path/to/file");
+    /// let _ = o.blocking_write(vec![0; 4096])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_write(&self, bs: impl AsRef<[u8]>) -> Result<()> {
+        let op = OpWrite::new(self.meta.path(), bs.as_ref().len() as u64)?;
+        let mut s = self.acc.blocking_write(&op)?;
+
+        std::io::Write::write_all(&mut s, bs.as_ref())?;
+        s.close()?;
+
+        Ok(())
+    }
+
+    /// Write bytes into object with extra options like preconditions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::Preconditions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let o = op.object("path/to/file");
+    /// // Only write if the object doesn't exist.
+    /// let _ = o
+    ///     .write_with(vec![0; 4096], |op| {
+    ///         op.with_preconditions(Preconditions::new().with_if_none_match("*"))
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_with(
+        &self,
+        bs: impl AsRef<[u8]>,
+        f: impl FnOnce(OpWrite) -> OpWrite,
+    ) -> Result<()> {
+        let op = f(OpWrite::new(self.meta.path(), bs.as_ref().len() as u64)?);
+        let mut s = self.acc.write(&op).await?;
+
+        s.write_all(bs.as_ref()).await?;
+        s.close().await?;
+
+        Ok(())
+    }
+
     /// Create a new writer which can write data into the object.
     ///
     /// # Examples
@@ -544,6 +771,205 @@ impl Object {
         Ok(s)
     }
 
+    /// Create a new blocking writer which can write data into the object.
+    ///
+    /// Data is only guaranteed to be persisted after
+    /// [`BlockingBytesWrite::close`] returns successfully.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// use std::io::Write;
+    ///
+    /// use opendal::BlockingBytesWrite;
+    ///
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// let o = op.object("path/to/file");
+    /// let mut w = o.blocking_writer(4096)?;
+    /// w.write_all(&[1; 4096])?;
+    /// w.close()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_writer(&self, size: u64) -> Result<impl BlockingBytesWrite> {
+        let op = OpWrite::new(self.meta.path(), size)?;
+        self.acc.blocking_write(&op)
+    }
+
+    /// Create a new writer with extra options like content type and user metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # use futures::AsyncWriteExt;
+    /// let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let o = op.object("path/to/file.json");
+    /// let mut w = o
+    ///     .writer_with(2, |op| {
+    ///         op.with_content_type("application/json")
+    ///             .with_cache_control("max-age=3600")
+    ///             .with_user_metadata("author", "opendal")
+    ///     })
+    ///     .await?;
+    /// w.write_all(b"{}").await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn writer_with(
+        &self,
+        size: u64,
+        f: impl FnOnce(OpWrite) -> OpWrite,
+    ) -> Result<impl BytesWrite> {
+        let op = f(OpWrite::new(self.meta.path(), size)?);
+        let s = self.acc.write(&op).await?;
+
+        Ok(s)
+    }
+
+    /// Create a new writer without knowing the size of data up front.
+    ///
+    /// Data will be buffered or uploaded in parts by services, and the
+    /// object will be visible only after the writer has been closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # use futures::AsyncWriteExt;
+    /// let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let o = op.object("path/to/file");
+    /// let mut w = o.unsized_writer().await?;
+    /// w.write_all(b"hello, ").await?;
+    /// w.write_all(b"world!").await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn unsized_writer(&self) -> Result<impl BytesWrite> {
+        let op = OpWrite::new_unsized(self.meta.path())?;
+        let s = self.acc.write(&op).await?;
+
+        Ok(s)
+    }
+
+    /// Append bytes to the end of object, object will be created if not exist.
+    ///
+    /// # Notes
+    ///
+    /// - Not all services support append, check [`AccessorCapability::APPEND`][crate::AccessorCapability::APPEND]
+    ///   before using it. Services that don't support it will return
+    ///   [`ErrorKind::Unsupported`][crate::ErrorKind::Unsupported].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let o = op.object("path/to/audit.log");
+    /// o.append("hello, ").await?;
+    /// o.append("world!").await?;
+    /// # assert_eq!(o.read().await?, b"hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn append(&self, bs: impl Into<Vec<u8>>) -> Result<()> {
+        let bs = bs.into();
+        let op = OpAppend::new(self.meta.path(), bs.len() as u64)?;
+
+        self.acc.append(&op, Box::new(Cursor::new(bs))).await
+    }
+
+    /// Create a new multipart upload for this object.
+    ///
+    /// Read [`ObjectMultipart`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::services::s3;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(s3::Backend::build().bucket("test").finish().await?);
+    /// let mp = op.object("path/to/file").create_multipart().await?;
+    /// let part = mp.write(1, vec![0; 4096]).await?;
+    /// mp.complete(vec![part]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_multipart(&self) -> Result<ObjectMultipart> {
+        let op = OpCreateMultipart::new(self.meta.path())?;
+        let upload_id = self.acc.create_multipart(&op).await?;
+
+        Ok(ObjectMultipart::new(
+            self.acc.clone(),
+            self.meta.path(),
+            &upload_id,
+        ))
+    }
+
+    /// Construct a multipart upload handle with existing `upload_id`.
+    ///
+    /// This function is used to resume a multipart upload created before,
+    /// no request will be sent.
+    pub fn multipart(&self, upload_id: &str) -> ObjectMultipart {
+        ObjectMultipart::new(self.acc.clone(), self.meta.path(), upload_id)
+    }
+
+    /// Create a new multipart upload and return a writer on it.
+    ///
+    /// The writer will split data into parts of `part_size`, and upload them
+    /// in parallel. Read [`MultipartWriter`] for more details.
+    ///
+    /// # Notes
+    ///
+    /// Services may have limits on part size, for example, all parts except
+    /// the last one MUST be at least 5 MiB on AWS S3.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::services::s3;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use futures::AsyncWriteExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(s3::Backend::build().bucket("test").finish().await?);
+    /// let o = op.object("path/to/file");
+    /// let mut w = o.multipart_writer(8 * 1024 * 1024).await?;
+    /// w.write_all(&vec![0; 32 * 1024 * 1024]).await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn multipart_writer(&self, part_size: usize) -> Result<MultipartWriter> {
+        let mp = self.create_multipart().await?;
+
+        Ok(mp.writer(part_size))
+    }
+
     /// Delete object.
     ///
     /// # Notes
@@ -570,6 +996,168 @@ impl Object {
         self.acc.delete(op).await
     }
 
+    /// Delete object in blocking way.
+    ///
+    /// Read [`Object::delete`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// op.object("test").blocking_delete()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_delete(&self) -> Result<()> {
+        let op = &OpDelete::new(self.meta.path())?;
+
+        self.acc.blocking_delete(op)
+    }
+
+    /// Copy current object to the specified path.
+    ///
+    /// `to` is relative to operator's root, the same as [`Operator::object`][crate::Operator::object].
+    ///
+    /// # Notes
+    ///
+    /// - Copy will overwrite the existing object at `to`.
+    /// - Copy a not existing object will return `NotFound` error.
+    /// - Copy an object to itself will return an `InvalidInput` error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let o = op.object("path/to/file");
+    /// # o.write(&vec![0; 4096]).await?;
+    /// o.copy_to("path/to/file.bak").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_to(&self, to: &str) -> Result<()> {
+        let op = &OpCopy::new(self.meta.path(), &Object::normalize_path(to))?;
+
+        self.acc.copy(op).await
+    }
+
+    /// Rename current object to the specified path.
+    ///
+    /// `to` is relative to operator's root, the same as [`Operator::object`][crate::Operator::object].
+    ///
+    /// # Notes
+    ///
+    /// - Rename will overwrite the existing object at `to`.
+    /// - Rename a not existing object will return `NotFound` error.
+    /// - Services without native rename support will copy and then delete the object,
+    ///   so rename is not guaranteed to be atomic.
+    /// - HDFS moves the existing destination aside before renaming and restores
+    ///   it if the rename failed.
+    /// - Rename an object to itself will return an `InvalidInput` error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let o = op.object("path/to/file");
+    /// # o.write(&vec![0; 4096]).await?;
+    /// o.rename_to("path/to/new_file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_to(&self, to: &str) -> Result<()> {
+        let op = &OpRename::new(self.meta.path(), &Object::normalize_path(to))?;
+
+        self.acc.rename(op).await
+    }
+
+    /// Presign an operation for read.
+    ///
+    /// The returned [`PresignedRequest`] carries method, uri and headers,
+    /// users can send it without credential before it expired, for example,
+    /// hand it to clients as a short-lived download link.
+    ///
+    /// Only services with [`AccessorCapability::PRESIGN`][crate::AccessorCapability::PRESIGN]
+    /// support presign, others will return [`ErrorKind::Unsupported`][crate::ErrorKind::Unsupported].
+    ///
+    /// # Notes
+    ///
+    /// SSE-C headers are not included in presigned requests since they
+    /// carry the customer key, clients need to send them by themselves for
+    /// objects encrypted with SSE-C.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::services::s3;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(s3::Backend::build().bucket("test").finish().await?);
+    /// let signed_req = op.object("test").presign_read(Duration::hours(1))?;
+    /// let req = http::Request::builder()
+    ///     .method(signed_req.method())
+    ///     .uri(signed_req.uri())
+    ///     .body(())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn presign_read(&self, expire: Duration) -> Result<PresignedRequest> {
+        let op = &OpPresign::new(self.meta.path(), PresignOperation::Read, expire)?;
+
+        self.acc.presign(op)
+    }
+
+    /// Presign an operation for write.
+    ///
+    /// All headers in the returned [`PresignedRequest`] MUST be sent along
+    /// with the request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::services::s3;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(s3::Backend::build().bucket("test").finish().await?);
+    /// let signed_req = op.object("test").presign_write(Duration::hours(1))?;
+    /// let mut req = http::Request::builder()
+    ///     .method(signed_req.method())
+    ///     .uri(signed_req.uri());
+    /// for (k, v) in signed_req.header() {
+    ///     req = req.header(k, v);
+    /// }
+    /// let req = req.body("Hello, World!")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn presign_write(&self, expire: Duration) -> Result<PresignedRequest> {
+        let op = &OpPresign::new(self.meta.path(), PresignOperation::Write, expire)?;
+
+        self.acc.presign(op)
+    }
+
     /// List current dir object.
     ///
     /// This function will create a new [`ObjectStreamer`][crate::ObjectStreamer] handle
@@ -616,6 +1204,76 @@ impl Object {
         self.acc.list(op).await
     }
 
+    /// List current dir object in blocking way.
+    ///
+    /// An error will be returned if object path doesn't end with `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// let o = op.object("path/to/dir/");
+    /// for o in o.blocking_list()? {
+    ///     let o = o?;
+    ///     println!("Got object {}", o.path());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_list(&self) -> Result<ObjectIterator> {
+        let op = &OpList::new(self.meta.path())?;
+
+        self.acc.blocking_list(op)
+    }
+
+    /// Walk current dir object recursively.
+    ///
+    /// Different from [`Object::list`], all descendants of current dir will
+    /// be returned as a flat stream.
+    ///
+    /// An error will be returned if object path doesn't end with `/`.
+    ///
+    /// # Notes
+    ///
+    /// - The order of returned objects is not guaranteed.
+    /// - Services that don't have real dirs (like s3) will only return dirs
+    ///   created by [`Object::create`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use futures::io;
+    /// # use opendal::Operator;
+    /// # use opendal::ObjectMode;
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let o = op.object("path/to/dir/");
+    /// let mut obs = o.walk().await?;
+    /// while let Some(o) = obs.next().await {
+    ///     let mut o = o?;
+    ///     let meta = o.metadata_cached().await?;
+    ///     if meta.mode() == ObjectMode::FILE {
+    ///         println!("Handling file {}", meta.path())
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn walk(&self) -> Result<ObjectStreamer> {
+        let op = &OpList::new(self.meta.path())?.with_recursive(true);
+
+        self.acc.list(op).await
+    }
+
     pub(crate) fn metadata_ref(&self) -> &Metadata {
         &self.meta
     }
@@ -652,6 +1310,70 @@ impl Object {
         self.acc.stat(op).await
     }
 
+    /// Get current object's metadata in blocking way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # use opendal::io_util::block_on;
+    /// use std::io::ErrorKind;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    /// if let Err(e) = op.object("test").blocking_metadata() {
+    ///     if e.kind() == ErrorKind::NotFound {
+    ///         println!("object not exist")
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn blocking_metadata(&self) -> Result<Metadata> {
+        let op = &OpStat::new(self.meta.path())?;
+
+        self.acc.blocking_stat(op)
+    }
+
+    /// Get current object's metadata with extra options like preconditions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// use opendal::ops::Preconditions;
+    /// use opendal::ErrorKind;
+    /// use time::OffsetDateTime;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// # let o = op.object("test");
+    /// # o.write("Hello, World!").await?;
+    /// let since = OffsetDateTime::now_utc();
+    /// if let Err(e) = o
+    ///     .metadata_with(|op| {
+    ///         op.with_preconditions(Preconditions::new().with_if_modified_since(since))
+    ///     })
+    ///     .await
+    /// {
+    ///     if ErrorKind::of(&e) == ErrorKind::ConditionNotMatch {
+    ///         println!("object not modified")
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn metadata_with(&self, f: impl FnOnce(OpStat) -> OpStat) -> Result<Metadata> {
+        let op = &f(OpStat::new(self.meta.path())?);
+
+        self.acc.stat(op).await
+    }
+
     /// Use local cached metadata if possible.
     ///
     /// # Example
@@ -713,6 +1435,34 @@ impl Object {
             },
         }
     }
+
+    /// Check if this object exist or not in blocking way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use opendal::io_util::block_on;
+    /// use opendal::services::memory;
+    /// use opendal::Operator;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let op = Operator::new(block_on(memory::Backend::build().finish())?);
+    ///     let _ = op.object("test").blocking_is_exist()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn blocking_is_exist(&self) -> Result<bool> {
+        let r = self.blocking_metadata();
+        match r {
+            Ok(_) => Ok(true),
+            Err(err) => match err.kind() {
+                ErrorKind::NotFound => Ok(false),
+                _ => Err(err),
+            },
+        }
+    }
 }
 
 /// Metadata carries all object metadata.
@@ -726,6 +1476,12 @@ pub struct Metadata {
     content_length: Option<u64>,
     content_md5: Option<String>,
     last_modified: Option<OffsetDateTime>,
+    etag: Option<String>,
+    content_type: Option<String>,
+    content_disposition: Option<String>,
+    cache_control: Option<String>,
+    storage_class: Option<String>,
+    user_metadata: HashMap<String, String>,
 }
 
 impl Metadata {
@@ -797,6 +1553,79 @@ impl Metadata {
         self.last_modified = Some(last_modified);
         self
     }
+
+    /// ETag of this object.
+    ///
+    /// ETag is returned as is, which could be quoted like `"abc"` or
+    /// weak like `W/"abc"`. Users can pass it to conditional operations
+    /// like [`Object::write_with`] directly.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    pub(crate) fn set_etag(&mut self, etag: &str) -> &mut Self {
+        self.etag = Some(etag.to_string());
+        self
+    }
+
+    /// Content type of this object, like `application/json`.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    pub(crate) fn set_content_type(&mut self, content_type: &str) -> &mut Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Content disposition of this object, like `attachment; filename="abc.txt"`.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    pub(crate) fn set_content_disposition(&mut self, content_disposition: &str) -> &mut Self {
+        self.content_disposition = Some(content_disposition.to_string());
+        self
+    }
+
+    /// Cache control of this object, like `max-age=3600`.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    pub(crate) fn set_cache_control(&mut self, cache_control: &str) -> &mut Self {
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    /// Storage class of this object.
+    ///
+    /// The value is service specific, like `STANDARD_IA` for s3 and `Cool`
+    /// for azblob.
+    pub fn storage_class(&self) -> Option<&str> {
+        self.storage_class.as_deref()
+    }
+
+    pub(crate) fn set_storage_class(&mut self, storage_class: &str) -> &mut Self {
+        self.storage_class = Some(storage_class.to_string());
+        self
+    }
+
+    /// User defined metadata of this object.
+    ///
+    /// Keys are returned without service specific prefix like `x-amz-meta-`
+    /// and always in lowercase.
+    pub fn user_metadata(&self) -> &HashMap<String, String> {
+        &self.user_metadata
+    }
+
+    pub(crate) fn set_user_metadata(
+        &mut self,
+        user_metadata: HashMap<String, String>,
+    ) -> &mut Self {
+        self.user_metadata = user_metadata;
+        self
+    }
 }
 
 /// ObjectMode represents the corresponding object's mode.
@@ -833,6 +1662,13 @@ impl<T> ObjectStream for T where T: futures::Stream<Item = Result<Object>> + Unp
 /// ObjectStreamer is a boxed dyn [`ObjectStream`]
 pub type ObjectStreamer = Box<dyn ObjectStream>;
 
+/// ObjectIterate represents a blocking iterator of object.
+pub trait ObjectIterate: Iterator<Item = Result<Object>> + Send {}
+impl<T> ObjectIterate for T where T: Iterator<Item = Result<Object>> + Send {}
+
+/// ObjectIterator is a boxed dyn [`ObjectIterate`]
+pub type ObjectIterator = Box<dyn ObjectIterate>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
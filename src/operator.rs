@@ -12,21 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 #[cfg(feature = "retry")]
 use std::fmt::Debug;
 use std::io::Result;
+use std::str::FromStr;
 use std::sync::Arc;
 
+use anyhow::anyhow;
 #[cfg(feature = "retry")]
 use backon::Backoff;
+use percent_encoding::percent_decode_str;
 
+use crate::error::other;
+use crate::error::BackendError;
+use crate::ops::OpBatchDelete;
+use crate::scheme::scheme_factory;
+use crate::services;
 use crate::Accessor;
 use crate::AccessorMetadata;
+use crate::Config;
 use crate::Layer;
 use crate::Object;
+use crate::Scheme;
 
 /// User-facing APIs for object and object streams.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Operator {
     accessor: Arc<dyn Accessor>,
 }
@@ -73,6 +84,105 @@ impl Operator {
         Self { accessor }
     }
 
+    /// Create a new operator from a key/value config map.
+    ///
+    /// Keys are the same as the setter names of the service's `Builder`,
+    /// for example `bucket`, `endpoint` and `region` for [`s3`][crate::services::s3].
+    /// Unknown keys will be rejected.
+    ///
+    /// For custom schemes, the map will be passed to the registered
+    /// [`SchemeFactory`][crate::SchemeFactory] as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    /// use opendal::Scheme;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let map = HashMap::from([("root".to_string(), "/tmp".to_string())]);
+    /// let op = Operator::from_map(Scheme::Fs, map).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_map(scheme: Scheme, map: HashMap<String, String>) -> Result<Self> {
+        let accessor = match scheme {
+            Scheme::Azblob => services::azblob::Builder::from_map(&map)?.finish().await?,
+            Scheme::Fs => services::fs::Builder::from_map(&map)?.finish().await?,
+            #[cfg(feature = "services-hdfs")]
+            Scheme::Hdfs => services::hdfs::Builder::from_map(&map)?.finish().await?,
+            Scheme::Memory => services::memory::Builder::from_map(&map)?.finish().await?,
+            Scheme::S3 => services::s3::Builder::from_map(&map)?.finish().await?,
+            Scheme::Custom(name) => scheme_factory(name)?.build(map).await?,
+        };
+
+        Ok(Self::new(accessor))
+    }
+
+    /// Create a new operator from a deserialized [`Config`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Config;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let config: Config = serde_json::from_str(r#"{"scheme": "fs", "root": "/tmp"}"#)?;
+    /// let op = Operator::from_config(config).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_config(config: Config) -> Result<Self> {
+        let accessor = match config {
+            Config::Azblob(v) => services::azblob::Builder::from_config(v).finish().await?,
+            Config::Fs(v) => services::fs::Builder::from_config(v).finish().await?,
+            #[cfg(feature = "services-hdfs")]
+            Config::Hdfs(v) => services::hdfs::Builder::from_config(v).finish().await?,
+            Config::Memory(v) => services::memory::Builder::from_config(v).finish().await?,
+            Config::S3(v) => services::s3::Builder::from_config(v).finish().await?,
+        };
+
+        Ok(Self::new(accessor))
+    }
+
+    /// Create a new operator from an uri like `s3://bucket/path/to/root?region=us-east-1`.
+    ///
+    /// The host and path of uri will be mapped to config keys by scheme:
+    ///
+    /// - `azblob://<container>/<root>`
+    /// - `fs:///<root>`
+    /// - `hdfs://<name_node>/<root>`, `name_node` will be `default` if host is empty.
+    /// - `memory://`
+    /// - `s3://<bucket>/<root>`
+    /// - `<custom>://<host>/<root>` for schemes registered via [`register_scheme`][crate::register_scheme].
+    ///
+    /// All query pairs will be percent-decoded and passed as config keys,
+    /// read [`Operator::from_map`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let op = Operator::from_uri("fs:///tmp").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_uri(uri: &str) -> Result<Self> {
+        let (scheme, map) = parse_uri(uri)?;
+
+        Self::from_map(scheme, map).await
+    }
+
     /// Create a new layer.
     #[must_use]
     pub fn layer(self, layer: impl Layer) -> Self {
@@ -142,6 +252,11 @@ impl Operator {
         Object::new(self.inner(), path)
     }
 
+    /// Create a new [`BatchOperator`] to take batch operations.
+    pub fn batch(&self) -> BatchOperator {
+        BatchOperator::new(self.inner())
+    }
+
     /// Check if this operator can work correctly.
     ///
     /// We will send a real `stat` request to `.opendal` and return any errors
@@ -169,3 +284,200 @@ impl Operator {
         Ok(())
     }
 }
+
+/// BatchOperator is used to take batch operations like batch delete.
+#[derive(Clone, Debug)]
+pub struct BatchOperator {
+    acc: Arc<dyn Accessor>,
+}
+
+impl BatchOperator {
+    pub(crate) fn new(acc: Arc<dyn Accessor>) -> Self {
+        Self { acc }
+    }
+
+    /// Delete given paths, returns the delete result of every path in the
+    /// same order of input.
+    ///
+    /// Services that support batch delete natively (like s3) will delete
+    /// them in as few requests as possible, other services will delete them
+    /// concurrently.
+    ///
+    /// # Notes
+    ///
+    /// - Delete a not existing object will be treated as succeeded.
+    /// - Paths in results are normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use opendal::services::memory;
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// # let op = Operator::new(memory::Backend::build().finish().await?);
+    /// let results = op
+    ///     .batch()
+    ///     .delete(["path/to/file_a", "path/to/file_b"])
+    ///     .await?;
+    /// for (path, res) in results {
+    ///     if let Err(e) = res {
+    ///         println!("delete {path} failed: {e}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete(
+        &self,
+        paths: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let paths = paths
+            .into_iter()
+            .map(|p| Object::normalize_path(p.as_ref()))
+            .collect();
+        let op = &OpBatchDelete::new(paths)?;
+
+        self.acc.batch_delete(op).await
+    }
+}
+
+/// Parse uri into scheme and config map.
+fn parse_uri(uri: &str) -> Result<(Scheme, HashMap<String, String>)> {
+    let invalid = |msg: &str| {
+        other(BackendError::new(
+            HashMap::from([("uri".to_string(), uri.to_string())]),
+            anyhow!("uri is invalid: {msg}"),
+        ))
+    };
+
+    let (scheme, rest) = uri
+        .split_once("://")
+        .ok_or_else(|| invalid("scheme is missing"))?;
+    let scheme = Scheme::from_str(scheme)?;
+
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (host, path) = match rest.find('/') {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, ""),
+    };
+    let decode = |v: &str| {
+        percent_decode_str(v)
+            .decode_utf8()
+            .map(|v| v.to_string())
+            .map_err(|e| invalid(&e.to_string()))
+    };
+    let host = decode(host)?;
+    let path = decode(path)?;
+
+    let mut map = HashMap::new();
+    let mut insert = |k: &str, v: String| {
+        if !v.is_empty() {
+            map.insert(k.to_string(), v);
+        }
+    };
+    match scheme {
+        Scheme::Azblob => {
+            insert("container", host);
+            insert("root", path);
+        }
+        Scheme::Fs => {
+            if !host.is_empty() {
+                return Err(invalid("fs uri must not have host, use fs:///path instead"));
+            }
+            insert("root", path);
+        }
+        #[cfg(feature = "services-hdfs")]
+        Scheme::Hdfs => {
+            let name_node = if host.is_empty() {
+                "default".to_string()
+            } else {
+                format!("hdfs://{host}")
+            };
+            insert("name_node", name_node);
+            insert("root", path);
+        }
+        Scheme::Memory => {}
+        Scheme::S3 => {
+            insert("bucket", host);
+            insert("root", path);
+        }
+        Scheme::Custom(_) => {
+            insert("host", host);
+            insert("root", path);
+        }
+    }
+
+    for pair in query.split('&').filter(|v| !v.is_empty()) {
+        let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+        map.insert(decode(k)?, decode(v)?);
+    }
+
+    Ok((scheme, map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn test_parse_uri() -> Result<()> {
+        let cases =
+            vec![
+            (
+                "s3://bucket/path/to/root?region=us-east-1&endpoint=http%3A%2F%2F127.0.0.1%3A9000",
+                Scheme::S3,
+                vec![
+                    ("bucket", "bucket"),
+                    ("root", "/path/to/root"),
+                    ("region", "us-east-1"),
+                    ("endpoint", "http://127.0.0.1:9000"),
+                ],
+            ),
+            ("s3://bucket", Scheme::S3, vec![("bucket", "bucket")]),
+            (
+                "azblob://container/root?account_name=test",
+                Scheme::Azblob,
+                vec![
+                    ("container", "container"),
+                    ("root", "/root"),
+                    ("account_name", "test"),
+                ],
+            ),
+            ("fs:///tmp/hello%20world", Scheme::Fs, vec![("root", "/tmp/hello world")]),
+            ("memory://", Scheme::Memory, vec![]),
+        ];
+
+        for (uri, scheme, expected) in cases {
+            let (actual_scheme, actual) = parse_uri(uri)?;
+            let expected: HashMap<String, String> = expected
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+
+            assert_eq!(actual_scheme, scheme, "{uri}");
+            assert_eq!(actual, expected, "{uri}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_uri_invalid() {
+        for uri in ["/tmp", "fs://tmp/data", "unknown://bucket"] {
+            assert!(parse_uri(uri).is_err(), "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_from_map_unknown_key() {
+        let map = HashMap::from([("bucket".to_string(), "test".to_string())]);
+
+        let err = Operator::from_map(Scheme::Fs, map)
+            .await
+            .expect_err("unknown key must be rejected");
+        assert_eq!(ErrorKind::of(&err), ErrorKind::ConfigInvalid);
+    }
+}
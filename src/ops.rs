@@ -17,14 +17,25 @@
 //! Users should not use struct or functions here, use [`Operator`][crate::Operator] instead
 
 use std::collections::Bound;
+use std::collections::HashMap;
+use std::io;
 use std::io::Result;
 use std::ops::RangeBounds;
 
 use anyhow::anyhow;
+use http::HeaderMap;
+use http::Method;
+use http::Uri;
+use time::Duration;
+use time::OffsetDateTime;
+use time::UtcOffset;
 
 use crate::error::other;
 use crate::error::ObjectError;
+use crate::ErrorKind;
+use crate::Metadata;
 use crate::ObjectMode;
+use crate::ObjectPart;
 
 /// Args for `create` operation.
 ///
@@ -95,6 +106,7 @@ pub struct OpRead {
     path: String,
     offset: Option<u64>,
     size: Option<u64>,
+    preconditions: Preconditions,
 }
 
 impl OpRead {
@@ -116,6 +128,7 @@ impl OpRead {
             path: path.to_string(),
             offset: br.offset(),
             size: br.size(),
+            preconditions: Preconditions::default(),
         })
     }
 
@@ -136,9 +149,20 @@ impl OpRead {
             path: path.to_string(),
             offset,
             size,
+            preconditions: Preconditions::default(),
         })
     }
 
+    /// Set preconditions for this option.
+    ///
+    /// The operation will fail with [`ErrorKind::ConditionNotMatch`][crate::ErrorKind::ConditionNotMatch]
+    /// if any of the preconditions is not met.
+    #[must_use]
+    pub fn with_preconditions(mut self, preconditions: Preconditions) -> Self {
+        self.preconditions = preconditions;
+        self
+    }
+
     /// Get path from option.
     pub fn path(&self) -> &str {
         &self.path
@@ -153,6 +177,11 @@ impl OpRead {
     pub fn size(&self) -> Option<u64> {
         self.size
     }
+
+    /// Get preconditions from option.
+    pub fn preconditions(&self) -> &Preconditions {
+        &self.preconditions
+    }
 }
 
 /// Args for `stat` operation.
@@ -161,6 +190,7 @@ impl OpRead {
 #[derive(Debug, Clone, Default)]
 pub struct OpStat {
     path: String,
+    preconditions: Preconditions,
 }
 
 impl OpStat {
@@ -168,13 +198,29 @@ impl OpStat {
     pub fn new(path: &str) -> Result<Self> {
         Ok(Self {
             path: path.to_string(),
+            preconditions: Preconditions::default(),
         })
     }
 
+    /// Set preconditions for this option.
+    ///
+    /// The operation will fail with [`ErrorKind::ConditionNotMatch`][crate::ErrorKind::ConditionNotMatch]
+    /// if any of the preconditions is not met.
+    #[must_use]
+    pub fn with_preconditions(mut self, preconditions: Preconditions) -> Self {
+        self.preconditions = preconditions;
+        self
+    }
+
     /// Get path from option.
     pub fn path(&self) -> &str {
         &self.path
     }
+
+    /// Get preconditions from option.
+    pub fn preconditions(&self) -> &Preconditions {
+        &self.preconditions
+    }
 }
 
 /// Args for `write` operation.
@@ -183,7 +229,13 @@ impl OpStat {
 #[derive(Debug, Clone, Default)]
 pub struct OpWrite {
     path: String,
-    size: u64,
+    size: Option<u64>,
+    preconditions: Preconditions,
+
+    content_type: Option<String>,
+    content_disposition: Option<String>,
+    cache_control: Option<String>,
+    user_metadata: HashMap<String, String>,
 }
 
 impl OpWrite {
@@ -191,6 +243,20 @@ impl OpWrite {
     ///
     /// If input path is not a file path, an error will be returned.
     pub fn new(path: &str, size: u64) -> Result<Self> {
+        Self::new_with_size(path, Some(size))
+    }
+
+    /// Create a new `OpWrite` without knowing the size up front.
+    ///
+    /// Services will decide how to write data, for example, s3 will use
+    /// multipart upload and azblob will use staged blocks.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    pub fn new_unsized(path: &str) -> Result<Self> {
+        Self::new_with_size(path, None)
+    }
+
+    fn new_with_size(path: &str, size: Option<u64>) -> Result<Self> {
         if path.ends_with('/') {
             return Err(other(ObjectError::new(
                 "write",
@@ -199,6 +265,128 @@ impl OpWrite {
             )));
         }
 
+        Ok(Self {
+            path: path.to_string(),
+            size,
+            preconditions: Preconditions::default(),
+
+            content_type: None,
+            content_disposition: None,
+            cache_control: None,
+            user_metadata: HashMap::new(),
+        })
+    }
+
+    /// Set content type for this option, like `application/json`.
+    #[must_use]
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Set content disposition for this option, like `attachment; filename="abc.txt"`.
+    #[must_use]
+    pub fn with_content_disposition(mut self, content_disposition: &str) -> Self {
+        self.content_disposition = Some(content_disposition.to_string());
+        self
+    }
+
+    /// Set cache control for this option, like `max-age=3600`.
+    #[must_use]
+    pub fn with_cache_control(mut self, cache_control: &str) -> Self {
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    /// Add a user defined metadata for this option.
+    ///
+    /// Keys will be sent with service specific prefix like `x-amz-meta-`,
+    /// so they should be valid HTTP header names and are case-insensitive.
+    #[must_use]
+    pub fn with_user_metadata(mut self, key: &str, value: &str) -> Self {
+        self.user_metadata
+            .insert(key.to_lowercase(), value.to_string());
+        self
+    }
+
+    /// Set preconditions for this option.
+    ///
+    /// The operation will fail with [`ErrorKind::ConditionNotMatch`][crate::ErrorKind::ConditionNotMatch]
+    /// if any of the preconditions is not met.
+    #[must_use]
+    pub fn with_preconditions(mut self, preconditions: Preconditions) -> Self {
+        self.preconditions = preconditions;
+        self
+    }
+
+    /// Get path from option.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get size from option.
+    ///
+    /// Returns `0` for unsized writes, use [`OpWrite::is_unsized`] to check
+    /// whether the size is known.
+    pub fn size(&self) -> u64 {
+        self.size.unwrap_or_default()
+    }
+
+    /// Check if the size is unknown up front.
+    ///
+    /// Read [`OpWrite::new_unsized`] for more details.
+    pub fn is_unsized(&self) -> bool {
+        self.size.is_none()
+    }
+
+    /// Get preconditions from option.
+    pub fn preconditions(&self) -> &Preconditions {
+        &self.preconditions
+    }
+
+    /// Get content type from option.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Get content disposition from option.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    /// Get cache control from option.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Get user defined metadata from option.
+    pub fn user_metadata(&self) -> &HashMap<String, String> {
+        &self.user_metadata
+    }
+}
+
+/// Args for `append` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpAppend {
+    path: String,
+    size: u64,
+}
+
+impl OpAppend {
+    /// Create a new `OpAppend`.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    pub fn new(path: &str, size: u64) -> Result<Self> {
+        if path.ends_with('/') {
+            return Err(other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("Is a directory"),
+            )));
+        }
+
         Ok(Self {
             path: path.to_string(),
             size,
@@ -238,12 +426,33 @@ impl OpDelete {
     }
 }
 
+/// Args for `batch_delete` operation.
+///
+/// The paths must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpBatchDelete {
+    paths: Vec<String>,
+}
+
+impl OpBatchDelete {
+    /// Create a new `OpBatchDelete`.
+    pub fn new(paths: Vec<String>) -> Result<Self> {
+        Ok(Self { paths })
+    }
+
+    /// Get paths from option.
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+}
+
 /// Args for `list` operation.
 ///
 /// The path must be normalized.
 #[derive(Debug, Clone, Default)]
 pub struct OpList {
     path: String,
+    recursive: bool,
 }
 
 impl OpList {
@@ -259,6 +468,160 @@ impl OpList {
             )));
         }
 
+        Ok(Self {
+            path: path.to_string(),
+            recursive: false,
+        })
+    }
+
+    /// Set recursive for this option.
+    ///
+    /// If recursive is set, all descendants of the path will be listed as
+    /// a flat stream instead of only the direct children.
+    #[must_use]
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Get path from option.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get recursive from option.
+    pub fn recursive(&self) -> bool {
+        self.recursive
+    }
+}
+
+/// Args for `copy` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpCopy {
+    from: String,
+    to: String,
+}
+
+impl OpCopy {
+    /// Create a new `OpCopy`.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    ///
+    /// Copy an object to itself will return an error too, since services
+    /// like fs will truncate the destination before reading the source.
+    pub fn new(from: &str, to: &str) -> Result<Self> {
+        for path in [from, to] {
+            if path.ends_with('/') {
+                return Err(other(ObjectError::new(
+                    "copy",
+                    path,
+                    anyhow!("Is a directory"),
+                )));
+            }
+        }
+
+        if from == to {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                ObjectError::new("copy", from, anyhow!("source and destination are the same")),
+            ));
+        }
+
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Get from path from option.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Get to path from option.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+}
+
+/// Args for `rename` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpRename {
+    from: String,
+    to: String,
+}
+
+impl OpRename {
+    /// Create a new `OpRename`.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    ///
+    /// Rename an object to itself will return an error too, since services
+    /// without native rename will remove the only copy of it.
+    pub fn new(from: &str, to: &str) -> Result<Self> {
+        for path in [from, to] {
+            if path.ends_with('/') {
+                return Err(other(ObjectError::new(
+                    "rename",
+                    path,
+                    anyhow!("Is a directory"),
+                )));
+            }
+        }
+
+        if from == to {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                ObjectError::new(
+                    "rename",
+                    from,
+                    anyhow!("source and destination are the same"),
+                ),
+            ));
+        }
+
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Get from path from option.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Get to path from option.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+}
+
+/// Args for `create_multipart` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpCreateMultipart {
+    path: String,
+}
+
+impl OpCreateMultipart {
+    /// Create a new `OpCreateMultipart`.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    pub fn new(path: &str) -> Result<Self> {
+        if path.ends_with('/') {
+            return Err(other(ObjectError::new(
+                "create_multipart",
+                path,
+                anyhow!("Is a directory"),
+            )));
+        }
+
         Ok(Self {
             path: path.to_string(),
         })
@@ -270,6 +633,460 @@ impl OpList {
     }
 }
 
+/// Max part number of multipart upload, the same as s3's limit.
+const MAX_PART_NUMBER: usize = 10000;
+
+/// Args for `write_multipart` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpWriteMultipart {
+    path: String,
+    upload_id: String,
+    part_number: usize,
+    size: u64,
+}
+
+impl OpWriteMultipart {
+    /// Create a new `OpWriteMultipart`.
+    ///
+    /// If input path is not a file path or part number is not in
+    /// `1..=10000`, an error will be returned.
+    pub fn new(path: &str, upload_id: &str, part_number: usize, size: u64) -> Result<Self> {
+        if path.ends_with('/') {
+            return Err(other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("Is a directory"),
+            )));
+        }
+        if !(1..=MAX_PART_NUMBER).contains(&part_number) {
+            return Err(other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("part number {part_number} is out of range 1..={MAX_PART_NUMBER}"),
+            )));
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+            part_number,
+            size,
+        })
+    }
+
+    /// Get path from option.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get upload_id from option.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Get part_number from option.
+    pub fn part_number(&self) -> usize {
+        self.part_number
+    }
+
+    /// Get size from option.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Args for `complete_multipart` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpCompleteMultipart {
+    path: String,
+    upload_id: String,
+    parts: Vec<ObjectPart>,
+}
+
+impl OpCompleteMultipart {
+    /// Create a new `OpCompleteMultipart`.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    pub fn new(path: &str, upload_id: &str, parts: Vec<ObjectPart>) -> Result<Self> {
+        if path.ends_with('/') {
+            return Err(other(ObjectError::new(
+                "complete_multipart",
+                path,
+                anyhow!("Is a directory"),
+            )));
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+            parts,
+        })
+    }
+
+    /// Get path from option.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get upload_id from option.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Get parts from option.
+    pub fn parts(&self) -> &[ObjectPart] {
+        &self.parts
+    }
+}
+
+/// Args for `abort_multipart` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpAbortMultipart {
+    path: String,
+    upload_id: String,
+}
+
+impl OpAbortMultipart {
+    /// Create a new `OpAbortMultipart`.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    pub fn new(path: &str, upload_id: &str) -> Result<Self> {
+        if path.ends_with('/') {
+            return Err(other(ObjectError::new(
+                "abort_multipart",
+                path,
+                anyhow!("Is a directory"),
+            )));
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+        })
+    }
+
+    /// Get path from option.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get upload_id from option.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+}
+
+/// Args for `presign` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone, Default)]
+pub struct OpPresign {
+    path: String,
+    op: PresignOperation,
+    expire: Duration,
+}
+
+impl OpPresign {
+    /// Create a new `OpPresign`.
+    ///
+    /// If input path is not a file path, an error will be returned.
+    pub fn new(path: &str, op: PresignOperation, expire: Duration) -> Result<Self> {
+        if path.ends_with('/') {
+            return Err(other(ObjectError::new(
+                "presign",
+                path,
+                anyhow!("Is a directory"),
+            )));
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            op,
+            expire,
+        })
+    }
+
+    /// Get path from option.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get operation from option.
+    pub fn operation(&self) -> PresignOperation {
+        self.op
+    }
+
+    /// Get expire from option.
+    pub fn expire(&self) -> Duration {
+        self.expire
+    }
+}
+
+/// Presign operation used for presign.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PresignOperation {
+    /// Presign a read (GET) request.
+    Read,
+    /// Presign a write (PUT) request.
+    Write,
+}
+
+impl Default for PresignOperation {
+    fn default() -> Self {
+        PresignOperation::Read
+    }
+}
+
+/// PresignedRequest is a presigned request returned by `presign`.
+///
+/// Users can send this request without holding any credential before it
+/// expires. All headers returned here MUST be sent along with the request,
+/// otherwise services will reject it.
+#[derive(Debug, Clone)]
+pub struct PresignedRequest {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+}
+
+impl PresignedRequest {
+    /// Create a new `PresignedRequest`.
+    pub fn new(method: Method, uri: Uri, headers: HeaderMap) -> Self {
+        Self {
+            method,
+            uri,
+            headers,
+        }
+    }
+
+    /// Return method of this request.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Return uri of this request.
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Return headers of this request.
+    pub fn header(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+/// Preconditions carried by `read`, `stat` and `write`.
+///
+/// Services that support conditional requests will send them as HTTP
+/// headers, others will check them against the object's metadata.
+///
+/// # Examples
+///
+/// ```
+/// use opendal::ops::Preconditions;
+///
+/// let preconditions = Preconditions::new().with_if_none_match("*");
+/// assert_eq!(preconditions.if_none_match(), Some("*"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Preconditions {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_modified_since: Option<OffsetDateTime>,
+    if_unmodified_since: Option<OffsetDateTime>,
+}
+
+impl Preconditions {
+    /// Create a new `Preconditions` without any condition.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set if-match.
+    ///
+    /// The operation will fail with [`ErrorKind::ConditionNotMatch`][crate::ErrorKind::ConditionNotMatch]
+    /// if the object's etag doesn't match any of the given etags.
+    #[must_use]
+    pub fn with_if_match(mut self, etag: &str) -> Self {
+        self.if_match = Some(etag.to_string());
+        self
+    }
+
+    /// Set if-none-match.
+    ///
+    /// The operation will fail with [`ErrorKind::ConditionNotMatch`][crate::ErrorKind::ConditionNotMatch]
+    /// if the object's etag matches any of the given etags. Use `*` to
+    /// make sure the object doesn't exist.
+    #[must_use]
+    pub fn with_if_none_match(mut self, etag: &str) -> Self {
+        self.if_none_match = Some(etag.to_string());
+        self
+    }
+
+    /// Set if-modified-since.
+    ///
+    /// The operation will fail with [`ErrorKind::ConditionNotMatch`][crate::ErrorKind::ConditionNotMatch]
+    /// if the object has not been modified since the given time.
+    #[must_use]
+    pub fn with_if_modified_since(mut self, t: OffsetDateTime) -> Self {
+        self.if_modified_since = Some(t);
+        self
+    }
+
+    /// Set if-unmodified-since.
+    ///
+    /// The operation will fail with [`ErrorKind::ConditionNotMatch`][crate::ErrorKind::ConditionNotMatch]
+    /// if the object has been modified since the given time.
+    #[must_use]
+    pub fn with_if_unmodified_since(mut self, t: OffsetDateTime) -> Self {
+        self.if_unmodified_since = Some(t);
+        self
+    }
+
+    /// Get if-match from preconditions.
+    pub fn if_match(&self) -> Option<&str> {
+        self.if_match.as_deref()
+    }
+
+    /// Get if-none-match from preconditions.
+    pub fn if_none_match(&self) -> Option<&str> {
+        self.if_none_match.as_deref()
+    }
+
+    /// Get if-modified-since from preconditions.
+    pub fn if_modified_since(&self) -> Option<OffsetDateTime> {
+        self.if_modified_since
+    }
+
+    /// Get if-unmodified-since from preconditions.
+    pub fn if_unmodified_since(&self) -> Option<OffsetDateTime> {
+        self.if_unmodified_since
+    }
+
+    /// Returns `true` if no precondition is set.
+    pub fn is_empty(&self) -> bool {
+        self.if_match.is_none()
+            && self.if_none_match.is_none()
+            && self.if_modified_since.is_none()
+            && self.if_unmodified_since.is_none()
+    }
+
+    /// Insert preconditions into the request as HTTP headers.
+    pub(crate) fn insert_headers(&self, mut req: http::request::Builder) -> http::request::Builder {
+        if let Some(v) = &self.if_match {
+            req = req.header(http::header::IF_MATCH, v);
+        }
+        if let Some(v) = &self.if_none_match {
+            req = req.header(http::header::IF_NONE_MATCH, v);
+        }
+        if let Some(v) = self.if_modified_since {
+            req = req.header(http::header::IF_MODIFIED_SINCE, format_http_date(v));
+        }
+        if let Some(v) = self.if_unmodified_since {
+            req = req.header(http::header::IF_UNMODIFIED_SINCE, format_http_date(v));
+        }
+        req
+    }
+
+    /// Check preconditions against the metadata of the object, `None`
+    /// means the object doesn't exist.
+    ///
+    /// We follow the evaluation order of [RFC 7232](https://httpwg.org/specs/rfc7232.html#precedence):
+    /// `if-unmodified-since` is ignored if `if-match` is set and
+    /// `if-modified-since` is ignored if `if-none-match` is set.
+    pub(crate) fn check(
+        &self,
+        op: &'static str,
+        path: &str,
+        meta: Option<&Metadata>,
+    ) -> Result<()> {
+        let failed = |cond: &str| {
+            other(
+                ObjectError::new(op, path, anyhow!("precondition {cond} not match"))
+                    .with_kind(ErrorKind::ConditionNotMatch),
+            )
+        };
+
+        let meta = match meta {
+            Some(meta) => meta,
+            None if self.if_match.is_some() => return Err(failed("if-match")),
+            None => return Ok(()),
+        };
+        let etag = meta.etag();
+        // HTTP dates only have the precision of seconds.
+        let last_modified = meta.last_modified().map(|t| t.unix_timestamp());
+
+        if let Some(v) = &self.if_match {
+            if !match_etag(v, etag) {
+                return Err(failed("if-match"));
+            }
+        } else if let (Some(t), Some(lm)) = (self.if_unmodified_since, last_modified) {
+            if lm > t.unix_timestamp() {
+                return Err(failed("if-unmodified-since"));
+            }
+        }
+
+        if let Some(v) = &self.if_none_match {
+            if match_etag(v, etag) {
+                return Err(failed("if-none-match"));
+            }
+        } else if let (Some(t), Some(lm)) = (self.if_modified_since, last_modified) {
+            if lm <= t.unix_timestamp() {
+                return Err(failed("if-modified-since"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check if etag matches the condition which could be `*` or a list of
+/// etags separated by `,`. Weak etags are compared as strong ones.
+fn match_etag(cond: &str, etag: Option<&str>) -> bool {
+    if cond.trim() == "*" {
+        return true;
+    }
+
+    let normalize = |v: &str| {
+        v.trim()
+            .trim_start_matches("W/")
+            .trim_matches('"')
+            .to_string()
+    };
+    let etag = match etag {
+        Some(etag) => normalize(etag),
+        None => return false,
+    };
+
+    cond.split(',').any(|v| normalize(v) == etag)
+}
+
+/// Format time into HTTP date like `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn format_http_date(t: OffsetDateTime) -> String {
+    const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let t = t.to_offset(UtcOffset::UTC);
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[t.weekday().number_days_from_monday() as usize],
+        t.day(),
+        MONTHS[u8::from(t.month()) as usize - 1],
+        t.year(),
+        t.hour(),
+        t.minute(),
+        t.second()
+    )
+}
+
 /// BytesRange(offset, size) carries a range of content.
 ///
 /// BytesRange implements `ToString` which can be used as `Range` HTTP header directly.
@@ -357,4 +1174,134 @@ mod tests {
             BytesRange::from(10..20)
         );
     }
+
+    #[test]
+    fn test_op_write_multipart_part_number() {
+        assert!(OpWriteMultipart::new("x", "id", 1, 0).is_ok());
+        assert!(OpWriteMultipart::new("x", "id", 10000, 0).is_ok());
+        assert!(OpWriteMultipart::new("x", "id", 0, 0).is_err());
+        assert!(OpWriteMultipart::new("x", "id", 10001, 0).is_err());
+    }
+
+    #[test]
+    fn test_op_copy_same_path() {
+        assert!(OpCopy::new("a", "b").is_ok());
+
+        let err = OpCopy::new("a", "a").expect_err("copy to itself must fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_op_rename_same_path() {
+        assert!(OpRename::new("a", "b").is_ok());
+
+        let err = OpRename::new("a", "a").expect_err("rename to itself must fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_op_write_size() -> anyhow::Result<()> {
+        let op = OpWrite::new("a", 13)?;
+        assert_eq!(op.size(), 13);
+        assert!(!op.is_unsized());
+
+        let op = OpWrite::new_unsized("a")?;
+        assert_eq!(op.size(), 0);
+        assert!(op.is_unsized());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_http_date() -> anyhow::Result<()> {
+        // 1994-11-06T08:49:37Z
+        let t = OffsetDateTime::from_unix_timestamp(784111777)?;
+        assert_eq!(format_http_date(t), "Sun, 06 Nov 1994 08:49:37 GMT");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_preconditions_check() -> anyhow::Result<()> {
+        let t = OffsetDateTime::from_unix_timestamp(784111777)?;
+        let mut meta = Metadata::default();
+        meta.set_etag("\"abc\"").set_last_modified(t);
+
+        let cases = vec![
+            ("empty", Preconditions::new(), Some(&meta), true),
+            ("empty not exist", Preconditions::new(), None, true),
+            (
+                "if-match",
+                Preconditions::new().with_if_match("\"abc\""),
+                Some(&meta),
+                true,
+            ),
+            (
+                "if-match in list",
+                Preconditions::new().with_if_match("\"xyz\", W/\"abc\""),
+                Some(&meta),
+                true,
+            ),
+            (
+                "if-match not match",
+                Preconditions::new().with_if_match("\"xyz\""),
+                Some(&meta),
+                false,
+            ),
+            (
+                "if-match not exist",
+                Preconditions::new().with_if_match("*"),
+                None,
+                false,
+            ),
+            (
+                "if-none-match",
+                Preconditions::new().with_if_none_match("\"abc\""),
+                Some(&meta),
+                false,
+            ),
+            (
+                "if-none-match any",
+                Preconditions::new().with_if_none_match("*"),
+                None,
+                true,
+            ),
+            (
+                "if-modified-since",
+                Preconditions::new().with_if_modified_since(t),
+                Some(&meta),
+                false,
+            ),
+            (
+                "if-modified-since before",
+                Preconditions::new().with_if_modified_since(t - Duration::seconds(1)),
+                Some(&meta),
+                true,
+            ),
+            (
+                "if-unmodified-since before",
+                Preconditions::new().with_if_unmodified_since(t - Duration::seconds(1)),
+                Some(&meta),
+                false,
+            ),
+            (
+                "if-unmodified-since ignored by if-match",
+                Preconditions::new()
+                    .with_if_match("\"abc\"")
+                    .with_if_unmodified_since(t - Duration::seconds(1)),
+                Some(&meta),
+                true,
+            ),
+        ];
+
+        for (name, preconditions, meta, ok) in cases {
+            let res = preconditions.check("stat", "x", meta);
+            assert_eq!(res.is_ok(), ok, "{name}");
+            if let Err(e) = res {
+                assert_eq!(ErrorKind::of(&e), ErrorKind::ConditionNotMatch, "{name}");
+            }
+        }
+
+        Ok(())
+    }
 }
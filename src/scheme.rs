@@ -11,16 +11,33 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::io;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::anyhow;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 
 use crate::error::other;
 use crate::error::BackendError;
+use crate::Accessor;
+use crate::ErrorKind;
+
+/// Factories of custom schemes registered via [`register_scheme`].
+static REGISTRY: Lazy<RwLock<HashMap<&'static str, Arc<dyn SchemeFactory>>>> =
+    Lazy::new(Default::default);
 
 /// Backends that OpenDAL supports
+///
+/// New schemes could be added in minor releases, so it's marked as
+/// `#[non_exhaustive]`.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Scheme {
     /// [azblob][crate::services::azblob]: Azure Storage Blob services.
     Azblob,
@@ -33,6 +50,29 @@ pub enum Scheme {
     Memory,
     /// [s3][crate::services::s3]: AWS S3 alike services.
     S3,
+    /// Custom services registered via [`register_scheme`].
+    Custom(&'static str),
+}
+
+impl Scheme {
+    /// Convert scheme into static str, like `s3`.
+    pub fn into_static(self) -> &'static str {
+        match self {
+            Scheme::Azblob => "azblob",
+            Scheme::Fs => "fs",
+            #[cfg(feature = "services-hdfs")]
+            Scheme::Hdfs => "hdfs",
+            Scheme::Memory => "memory",
+            Scheme::S3 => "s3",
+            Scheme::Custom(v) => v,
+        }
+    }
+}
+
+impl Display for Scheme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_static())
+    }
 }
 
 impl Default for Scheme {
@@ -46,17 +86,184 @@ impl FromStr for Scheme {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.to_lowercase();
-        match s.as_str() {
-            "azblob" => Ok(Scheme::Azblob),
-            "fs" => Ok(Scheme::Fs),
-            #[cfg(feature = "services-hdfs")]
-            "hdfs" => Ok(Scheme::Hdfs),
-            "memory" => Ok(Scheme::Memory),
-            "s3" => Ok(Scheme::S3),
-            v => Err(other(BackendError::new(
-                Default::default(),
-                anyhow!("{} is not supported", v),
-            ))),
+        if let Some(v) = builtin_scheme(&s) {
+            return Ok(v);
+        }
+        if let Some((name, _)) = REGISTRY.read().get_key_value(s.as_str()) {
+            return Ok(Scheme::Custom(name));
         }
+
+        Err(other(
+            BackendError::new(Default::default(), anyhow!("{} is not supported", s))
+                .with_kind(ErrorKind::Unsupported),
+        ))
+    }
+}
+
+/// Names of all builtin schemes, including those disabled by features.
+///
+/// They are reserved so that custom schemes registered by users won't
+/// change meaning after enabling more features.
+const BUILTIN_SCHEMES: &[&str] = &["azblob", "fs", "hdfs", "memory", "s3"];
+
+fn builtin_scheme(s: &str) -> Option<Scheme> {
+    match s {
+        "azblob" => Some(Scheme::Azblob),
+        "fs" => Some(Scheme::Fs),
+        #[cfg(feature = "services-hdfs")]
+        "hdfs" => Some(Scheme::Hdfs),
+        "memory" => Some(Scheme::Memory),
+        "s3" => Some(Scheme::S3),
+        _ => None,
+    }
+}
+
+/// Factory to build accessors for custom schemes.
+///
+/// Read [`register_scheme`] for more details.
+#[async_trait]
+pub trait SchemeFactory: Send + Sync + 'static {
+    /// Build an accessor from the key/value config map.
+    ///
+    /// The map comes from [`Operator::from_map`][crate::Operator::from_map]
+    /// or the uri passed to [`Operator::from_uri`][crate::Operator::from_uri].
+    async fn build(&self, map: HashMap<String, String>) -> io::Result<Arc<dyn Accessor>>;
+}
+
+/// Register a custom scheme, so that [`Scheme::from_str`] and the uri or
+/// config map constructors of [`Operator`][crate::Operator] can build
+/// accessors defined outside OpenDAL.
+///
+/// `name` must be lowercase ascii letters, digits, `+`, `-` or `.`, and must
+/// not conflict with builtin schemes. Registering the same name again will
+/// replace the previous factory.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::io::Result;
+/// use std::sync::Arc;
+///
+/// use async_trait::async_trait;
+/// use opendal::register_scheme;
+/// use opendal::services::memory;
+/// use opendal::Accessor;
+/// use opendal::Operator;
+/// use opendal::SchemeFactory;
+///
+/// struct MyFactory;
+///
+/// #[async_trait]
+/// impl SchemeFactory for MyFactory {
+///     async fn build(&self, _: HashMap<String, String>) -> Result<Arc<dyn Accessor>> {
+///         memory::Backend::build().finish().await
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     register_scheme("myfs", MyFactory)?;
+///
+///     let op = Operator::from_uri("myfs://host/path").await?;
+///     Ok(())
+/// }
+/// ```
+pub fn register_scheme(name: &'static str, factory: impl SchemeFactory) -> io::Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+-.".contains(c));
+    if !valid {
+        return Err(other(BackendError::new(
+            HashMap::from([("scheme".to_string(), name.to_string())]),
+            anyhow!("scheme name is invalid"),
+        )));
+    }
+    if BUILTIN_SCHEMES.contains(&name) {
+        return Err(other(BackendError::new(
+            HashMap::from([("scheme".to_string(), name.to_string())]),
+            anyhow!("scheme conflicts with builtin services"),
+        )));
+    }
+
+    REGISTRY.write().insert(name, Arc::new(factory));
+    Ok(())
+}
+
+/// Get the factory of registered custom scheme.
+pub(crate) fn scheme_factory(name: &str) -> io::Result<Arc<dyn SchemeFactory>> {
+    REGISTRY.read().get(name).cloned().ok_or_else(|| {
+        other(
+            BackendError::new(
+                HashMap::from([("scheme".to_string(), name.to_string())]),
+                anyhow!("scheme is not registered"),
+            )
+            .with_kind(ErrorKind::Unsupported),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::Mutex;
+
+    use super::*;
+    use crate::services::memory;
+    use crate::Operator;
+
+    #[derive(Default)]
+    struct Recorder {
+        map: Arc<Mutex<HashMap<String, String>>>,
+    }
+
+    #[async_trait]
+    impl SchemeFactory for Recorder {
+        async fn build(&self, map: HashMap<String, String>) -> io::Result<Arc<dyn Accessor>> {
+            *self.map.lock() = map;
+            memory::Backend::build().finish().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_scheme() -> io::Result<()> {
+        let factory = Recorder::default();
+        let map = factory.map.clone();
+        register_scheme("test-recorder", factory)?;
+
+        assert_eq!(
+            Scheme::from_str("Test-Recorder")?,
+            Scheme::Custom("test-recorder")
+        );
+
+        let op = Operator::from_uri("test-recorder://host/path/to/root?key=value").await?;
+        op.object("test").write("Hello, World!").await?;
+        assert_eq!(
+            *map.lock(),
+            HashMap::from([
+                ("host".to_string(), "host".to_string()),
+                ("root".to_string(), "/path/to/root".to_string()),
+                ("key".to_string(), "value".to_string()),
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_scheme_invalid() {
+        // `hdfs` is reserved even if `services-hdfs` is not enabled.
+        for name in ["", "s3", "memory", "hdfs", "My-FS", "my_fs"] {
+            assert!(
+                register_scheme(name, Recorder::default()).is_err(),
+                "{name}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_custom_scheme() {
+        let res = Operator::from_map(Scheme::Custom("test-unregistered"), HashMap::new()).await;
+        assert!(res.is_err());
     }
 }
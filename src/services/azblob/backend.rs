@@ -14,57 +14,138 @@
 
 use std::cmp::min;
 use std::collections::HashMap;
+use std::env;
 use std::fmt::Debug;
 use std::fmt::Formatter;
-use std::io;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::io::Result;
-use std::mem;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
 use bytes::BufMut;
+use bytes::Bytes;
+use futures::AsyncReadExt;
 use futures::TryStreamExt;
 use http::header::HeaderName;
+use http::header::CACHE_CONTROL;
+use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_TYPE;
 use http::Response;
 use http::StatusCode;
 use hyper::body::HttpBody;
 use hyper::Body;
 use log::debug;
-use log::error;
 use log::info;
-use metrics::increment_counter;
+use log::trace;
 use minitrace::trace;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
 use reqsign::services::azure::storage::Signer;
+use serde::Deserialize;
 use time::format_description::well_known::Rfc2822;
 use time::OffsetDateTime;
 
+use crate::accessor::AccessorCapability;
 use crate::accessor::AccessorMetadata;
 use crate::error::other;
+use crate::error::parse_http_status;
+use crate::error::unknown_config_key;
 use crate::error::BackendError;
 use crate::error::ObjectError;
+use crate::io_util::into_stream;
 use crate::io_util::new_http_channel;
+use crate::io_util::parse_header_to_str;
+use crate::io_util::parse_user_metadata;
+use crate::io_util::percent_encode_path;
+use crate::io_util::BfsWalker;
 use crate::io_util::HttpBodyWriter;
+use crate::io_util::MultipartWriter;
 use crate::object::Metadata;
 use crate::ops::BytesRange;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpAppend;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
+use crate::ops::OpPresign;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::Preconditions;
+use crate::ops::PresignOperation;
+use crate::ops::PresignedRequest;
 use crate::services::azblob::object_stream::AzblobObjectStream;
+use crate::services::azblob::presign;
+use crate::services::azblob::presign::Credential;
 use crate::Accessor;
 use crate::BytesReader;
 use crate::BytesWriter;
 use crate::ObjectMode;
+use crate::ObjectMultipart;
+use crate::ObjectPart;
 use crate::ObjectStreamer;
 use crate::Scheme;
 
 const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
+const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
+const X_MS_COPY_STATUS: &str = "x-ms-copy-status";
+const X_MS_ACCESS_TIER: &str = "x-ms-access-tier";
+const X_MS_BLOB_CONTENT_TYPE: &str = "x-ms-blob-content-type";
+const X_MS_BLOB_CONTENT_DISPOSITION: &str = "x-ms-blob-content-disposition";
+const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+const X_MS_META_PREFIX: &str = "x-ms-meta-";
+const X_MS_ERROR_CODE: &str = "x-ms-error-code";
+
+/// Block size of staged blocks used by unsized write.
+const UNSIZED_WRITE_PART_SIZE: usize = 8 * 1024 * 1024;
+/// Interval to poll the status of pending copy.
+const COPY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Config for azblob services, which can be deserialized via serde.
+///
+/// Use [`Builder::from_config`] to convert it into a builder.
+#[derive(Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// See [`Builder::root`].
+    pub root: Option<String>,
+    /// See [`Builder::container`].
+    pub container: Option<String>,
+    /// See [`Builder::endpoint`].
+    pub endpoint: Option<String>,
+    /// See [`Builder::account_name`].
+    pub account_name: Option<String>,
+    /// See [`Builder::account_key`].
+    pub account_key: Option<String>,
+}
+
+impl Debug for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("Config");
+
+        d.field("root", &self.root)
+            .field("container", &self.container)
+            .field("endpoint", &self.endpoint);
+
+        if self.account_name.is_some() {
+            d.field("account_name", &"<redacted>");
+        }
+        if self.account_key.is_some() {
+            d.field("account_key", &"<redacted>");
+        }
+
+        d.finish()
+    }
+}
 
 /// Builder for azblob services
 #[derive(Default, Clone)]
@@ -96,6 +177,79 @@ impl Debug for Builder {
 }
 
 impl Builder {
+    /// Create a builder from [`Config`].
+    pub fn from_config(config: Config) -> Self {
+        let mut builder = Builder::default();
+        if let Some(v) = &config.root {
+            builder.root(v);
+        }
+        if let Some(v) = &config.container {
+            builder.container(v);
+        }
+        if let Some(v) = &config.endpoint {
+            builder.endpoint(v);
+        }
+        if let Some(v) = &config.account_name {
+            builder.account_name(v);
+        }
+        if let Some(v) = &config.account_key {
+            builder.account_key(v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from environment variables.
+    ///
+    /// Following variables will be loaded if set:
+    ///
+    /// - `OPENDAL_AZBLOB_ROOT`: [`Builder::root`]
+    /// - `OPENDAL_AZBLOB_CONTAINER`: [`Builder::container`]
+    /// - `OPENDAL_AZBLOB_ENDPOINT`: [`Builder::endpoint`]
+    /// - `OPENDAL_AZBLOB_ACCOUNT_NAME`: [`Builder::account_name`]
+    /// - `OPENDAL_AZBLOB_ACCOUNT_KEY`: [`Builder::account_key`]
+    ///
+    /// Other settings can still be changed via setters after loading.
+    pub fn from_env() -> Self {
+        let mut builder = Builder::default();
+        if let Ok(v) = env::var("OPENDAL_AZBLOB_ROOT") {
+            builder.root(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_AZBLOB_CONTAINER") {
+            builder.container(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_AZBLOB_ENDPOINT") {
+            builder.endpoint(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_AZBLOB_ACCOUNT_NAME") {
+            builder.account_name(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_AZBLOB_ACCOUNT_KEY") {
+            builder.account_key(&v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from a key/value config map.
+    ///
+    /// Keys are the same as the setter names, like `root`.
+    pub(crate) fn from_map(map: &HashMap<String, String>) -> Result<Self> {
+        let mut builder = Builder::default();
+        for (k, v) in map {
+            match k.as_str() {
+                "root" => builder.root(v),
+                "container" => builder.container(v),
+                "endpoint" => builder.endpoint(v),
+                "account_name" => builder.account_name(v),
+                "account_key" => builder.account_key(v),
+                _ => return Err(unknown_config_key(k)),
+            };
+        }
+
+        Ok(builder)
+    }
+
     /// Set root of this backend.
     ///
     /// All operations will happen under this root.
@@ -213,6 +367,12 @@ impl Builder {
             .build()
             .map_err(|e| other(BackendError::new(context, e)))?;
 
+        // Credential is kept for presign, which is not supported by signer.
+        let credential = match (&self.account_name, &self.account_key) {
+            (Some(name), Some(key)) => Some(Credential::new(name, key)),
+            _ => None,
+        };
+
         info!("backend build finished: {:?}", &self);
         Ok(Arc::new(Backend {
             root,
@@ -220,7 +380,7 @@ impl Builder {
             signer: Arc::new(signer),
             container: self.container.clone(),
             client,
-            _account_name: mem::take(&mut self.account_name).unwrap_or_default(),
+            credential,
         }))
     }
 }
@@ -232,7 +392,7 @@ pub struct Backend {
     root: String, // root will be "/" or /abc/
     endpoint: String,
     signer: Arc<Signer>,
-    _account_name: String,
+    credential: Option<Credential>,
 }
 
 impl Backend {
@@ -270,25 +430,35 @@ impl Accessor for Backend {
         let mut am = AccessorMetadata::default();
         am.set_scheme(Scheme::Azblob)
             .set_root(&self.root)
-            .set_name(&self.container);
+            .set_name(&self.container)
+            .set_capabilities(
+                AccessorCapability::READ
+                    | AccessorCapability::WRITE
+                    | AccessorCapability::LIST
+                    | AccessorCapability::RANGE_READ
+                    | AccessorCapability::COPY
+                    | AccessorCapability::RENAME
+                    | AccessorCapability::PRESIGN
+                    | AccessorCapability::MULTIPART
+                    | AccessorCapability::APPEND,
+            );
 
         am
     }
 
     #[trace("create")]
     async fn create(&self, args: &OpCreate) -> Result<()> {
-        increment_counter!("opendal_azblob_create_requests");
         let p = self.get_abs_path(args.path());
 
-        let req = self.put_blob(&p, 0, Body::empty()).await?;
+        let req = self.put_blob(&p, 0, None, Body::empty()).await?;
         let resp = self.client.request(req).await.map_err(|e| {
-            error!("object {} put_object: {:?}", args.path(), e);
+            debug!("object {} put_object: {:?}", args.path(), e);
             other(ObjectError::new("read", args.path(), e))
         })?;
 
         match resp.status() {
             StatusCode::CREATED | StatusCode::OK => {
-                debug!("object {} create finished", args.path());
+                trace!("object {} create finished", args.path());
                 Ok(())
             }
             _ => Err(parse_error_response_without_body(
@@ -301,20 +471,20 @@ impl Accessor for Backend {
 
     #[trace("read")]
     async fn read(&self, args: &OpRead) -> Result<BytesReader> {
-        increment_counter!("opendal_azblob_read_requests");
-
         let p = self.get_abs_path(args.path());
-        debug!(
+        trace!(
             "object {} read start: offset {:?}, size {:?}",
             &p,
             args.offset(),
             args.size()
         );
 
-        let resp = self.get_blob(&p, args.offset(), args.size()).await?;
+        let resp = self
+            .get_blob(&p, args.offset(), args.size(), args.preconditions())
+            .await?;
         match resp.status() {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                debug!(
+                trace!(
                     "object {} reader created: offset {:?}, size {:?}",
                     &p,
                     args.offset(),
@@ -335,16 +505,35 @@ impl Accessor for Backend {
     #[trace("write")]
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
         let p = self.get_abs_path(args.path());
-        debug!("object {} write start: size {}", &p, args.size());
+
+        if args.is_unsized() {
+            // Stage blocks and commit them while closing if size is unknown.
+            let upload_id = new_upload_id();
+            trace!(
+                "object {} write started as staged blocks: upload_id {}",
+                &p,
+                &upload_id
+            );
+
+            let acc = Arc::new(UnsizedWriteAccessor {
+                backend: self.clone(),
+                args: args.clone(),
+            });
+            let mp = ObjectMultipart::new(acc, args.path(), &upload_id);
+            return Ok(Box::new(MultipartWriter::new(mp, UNSIZED_WRITE_PART_SIZE)));
+        }
+
+        let size = args.size();
+        trace!("object {} write start: size {}", &p, size);
 
         let (tx, body) = new_http_channel();
 
-        let req = self.put_blob(&p, args.size(), body).await?;
+        let req = self.put_blob(&p, size, Some(args), body).await?;
 
         let bs = HttpBodyWriter::new(args, tx, self.client.request(req), |op, resp| {
             match resp.status() {
                 http::StatusCode::CREATED | http::StatusCode::OK => {
-                    debug!("object {} write finished: size {:?}", op.path(), op.size());
+                    trace!("object {} write finished: size {}", op.path(), op.size());
                     Ok(())
                 }
                 _ => Err(parse_error_response_without_body(resp, "write", op.path())),
@@ -354,12 +543,58 @@ impl Accessor for Backend {
         Ok(Box::new(bs))
     }
 
+    #[trace("append")]
+    async fn append(&self, args: &OpAppend, mut r: BytesReader) -> Result<()> {
+        let p = self.get_abs_path(args.path());
+        trace!("object {} append start: size {}", &p, args.size());
+
+        // The block will be sent again if the append blob is not exist, so
+        // we need to buffer it in memory.
+        let mut buf = Vec::with_capacity(args.size() as usize);
+        r.read_to_end(&mut buf).await?;
+        if buf.len() as u64 != args.size() {
+            return Err(other(ObjectError::new(
+                "append",
+                &p,
+                anyhow!("append short, expect {} actual {}", args.size(), buf.len()),
+            )));
+        }
+        let bs = Bytes::from(buf);
+
+        let resp = self.append_block(&p, bs.clone()).await?;
+        match resp.status() {
+            StatusCode::CREATED => {
+                trace!("object {} append finished: size {}", &p, args.size());
+                return Ok(());
+            }
+            StatusCode::NOT_FOUND => {
+                trace!("object {} append: blob not exist, create it", &p);
+            }
+            _ => return Err(parse_error_response_with_body(resp, "append", &p).await),
+        }
+
+        let resp = self.put_append_blob(&p).await?;
+        match resp.status() {
+            // The blob could be created by others concurrently, it's fine
+            // to append to it directly.
+            StatusCode::CREATED | StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED => {}
+            _ => return Err(parse_error_response_with_body(resp, "append", &p).await),
+        }
+
+        let resp = self.append_block(&p, bs).await?;
+        match resp.status() {
+            StatusCode::CREATED => {
+                trace!("object {} append finished: size {}", &p, args.size());
+                Ok(())
+            }
+            _ => Err(parse_error_response_with_body(resp, "append", &p).await),
+        }
+    }
+
     #[trace("stat")]
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
-        increment_counter!("opendal_azure_stat_requests");
-
         let p = self.get_abs_path(args.path());
-        debug!("object {} stat start", &p);
+        trace!("object {} stat start", &p);
 
         // Stat root always returns a DIR.
         if self.get_rel_path(&p).is_empty() {
@@ -369,11 +604,11 @@ impl Accessor for Backend {
             m.set_mode(ObjectMode::DIR);
             m.set_complete();
 
-            debug!("backed root object stat finished");
+            trace!("backed root object stat finished");
             return Ok(m);
         }
 
-        let resp = self.get_blob_properties(&p).await?;
+        let resp = self.get_blob_properties(&p, args.preconditions()).await?;
         match resp.status() {
             http::StatusCode::OK => {
                 let mut m = Metadata::default();
@@ -412,6 +647,19 @@ impl Accessor for Backend {
                     m.set_content_md5(v);
                 }
 
+                // Parse etag
+                if let Some(v) = resp.headers().get(http::header::ETAG) {
+                    let v = v.to_str().map_err(|e| {
+                        other(ObjectError::new(
+                            "stat",
+                            &p,
+                            anyhow!("parse etag header: {:?}", e),
+                        ))
+                    })?;
+
+                    m.set_etag(v);
+                }
+
                 // Parse last_modified
                 if let Some(v) = resp.headers().get(http::header::LAST_MODIFIED) {
                     let v = v.to_str().map_err(|e| {
@@ -432,6 +680,41 @@ impl Accessor for Backend {
                     m.set_last_modified(t);
                 }
 
+                // Parse content_type
+                if let Some(v) =
+                    parse_header_to_str(resp.headers(), CONTENT_TYPE.as_str(), "stat", &p)?
+                {
+                    m.set_content_type(v);
+                }
+
+                // Parse content_disposition
+                if let Some(v) =
+                    parse_header_to_str(resp.headers(), CONTENT_DISPOSITION.as_str(), "stat", &p)?
+                {
+                    m.set_content_disposition(v);
+                }
+
+                // Parse cache_control
+                if let Some(v) =
+                    parse_header_to_str(resp.headers(), CACHE_CONTROL.as_str(), "stat", &p)?
+                {
+                    m.set_cache_control(v);
+                }
+
+                // Parse storage_class
+                if let Some(v) = parse_header_to_str(resp.headers(), X_MS_ACCESS_TIER, "stat", &p)?
+                {
+                    m.set_storage_class(v);
+                }
+
+                // Parse user_metadata
+                m.set_user_metadata(parse_user_metadata(
+                    resp.headers(),
+                    X_MS_META_PREFIX,
+                    "stat",
+                    &p,
+                )?);
+
                 if p.ends_with('/') {
                     m.set_mode(ObjectMode::DIR);
                 } else {
@@ -440,7 +723,7 @@ impl Accessor for Backend {
 
                 m.set_complete();
 
-                debug!("object {} stat finished: {:?}", &p, m);
+                trace!("object {} stat finished: {:?}", &p, m);
                 Ok(m)
             }
             StatusCode::NOT_FOUND if p.ends_with('/') => {
@@ -450,7 +733,7 @@ impl Accessor for Backend {
                 m.set_mode(ObjectMode::DIR);
                 m.set_complete();
 
-                debug!("object {} stat finished", &p);
+                trace!("object {} stat finished", &p);
                 Ok(m)
             }
             _ => Err(parse_error_response_with_body(resp, "stat", &p).await),
@@ -459,15 +742,13 @@ impl Accessor for Backend {
 
     #[trace("delete")]
     async fn delete(&self, args: &OpDelete) -> Result<()> {
-        increment_counter!("opendal_azure_delete_requests");
-
         let p = self.get_abs_path(args.path());
-        debug!("object {} delete start", &p);
+        trace!("object {} delete start", &p);
 
         let resp = self.delete_blob(&p).await?;
         match resp.status() {
             StatusCode::ACCEPTED | StatusCode::NOT_FOUND => {
-                debug!("object {} delete finished", &p);
+                trace!("object {} delete finished", &p);
                 Ok(())
             }
             _ => Err(parse_error_response_with_body(resp, "delete", &p).await),
@@ -476,13 +757,173 @@ impl Accessor for Backend {
 
     #[trace("list")]
     async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
-        increment_counter!("opendal_azblob_list_requests");
-
         let path = self.get_abs_path(args.path());
-        debug!("object {} list start", &path);
+        trace!("object {} list start", &path);
+
+        if args.recursive() {
+            return Ok(Box::new(BfsWalker::new(
+                Arc::new(self.clone()),
+                args.path(),
+            )));
+        }
 
         Ok(Box::new(AzblobObjectStream::new(self.clone(), path)))
     }
+
+    #[trace("copy")]
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} copy start: to {}", &from, &to);
+
+        self.copy_and_wait("copy", &from, &to).await?;
+
+        trace!("object {} copy finished: to {}", &from, &to);
+        Ok(())
+    }
+
+    #[trace("rename")]
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} rename start: to {}", &from, &to);
+
+        // Azure Blob doesn't support rename natively, we need to copy and
+        // then delete the source blob.
+        //
+        // The source blob must only be deleted after the copy succeeded.
+        self.copy_and_wait("rename", &from, &to).await?;
+        trace!("object {} rename copied: to {}", &from, &to);
+
+        let resp = self.delete_blob(&from).await?;
+        match resp.status() {
+            StatusCode::ACCEPTED | StatusCode::NOT_FOUND => {
+                trace!("object {} rename finished: to {}", &from, &to);
+                Ok(())
+            }
+            _ => Err(parse_error_response_with_body(resp, "rename", &from).await),
+        }
+    }
+
+    #[trace("create_multipart")]
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        // Blocks are staged with the upload id as prefix of block id, there
+        // is no need to send requests here.
+        let upload_id = new_upload_id();
+
+        trace!(
+            "object {} create_multipart finished: upload_id {}",
+            args.path(),
+            &upload_id
+        );
+        Ok(upload_id)
+    }
+
+    #[trace("write_multipart")]
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        let p = self.get_abs_path(args.path());
+        trace!(
+            "object {} write_multipart start: upload_id {}, part_number {}, size {}",
+            &p,
+            args.upload_id(),
+            args.part_number(),
+            args.size()
+        );
+
+        let block_id = format_block_id(args.upload_id(), args.part_number());
+        let resp = self
+            .put_block(
+                &p,
+                &block_id,
+                args.size(),
+                Body::wrap_stream(into_stream(r, 64 * 1024)),
+            )
+            .await?;
+
+        match resp.status() {
+            StatusCode::CREATED => {
+                trace!(
+                    "object {} write_multipart finished: part_number {}, block_id {}",
+                    &p,
+                    args.part_number(),
+                    &block_id
+                );
+                Ok(ObjectPart::new(args.part_number(), &block_id))
+            }
+            _ => Err(parse_error_response_with_body(resp, "write_multipart", &p).await),
+        }
+    }
+
+    #[trace("complete_multipart")]
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        self.commit_blocks(args, None).await
+    }
+
+    #[trace("abort_multipart")]
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        // Azure doesn't support deleting uncommitted blocks, they will be
+        // garbage collected after a week.
+        trace!(
+            "object {} abort_multipart finished: upload_id {}",
+            args.path(),
+            args.upload_id()
+        );
+        Ok(())
+    }
+
+    #[trace("presign")]
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        let p = self.get_abs_path(args.path());
+        trace!(
+            "object {} presign start: operation {:?}, expire {}",
+            &p,
+            args.operation(),
+            args.expire()
+        );
+
+        let cred = self.credential.as_ref().ok_or_else(|| {
+            Error::new(
+                ErrorKind::PermissionDenied,
+                ObjectError::new("presign", &p, anyhow!("account key is required")),
+            )
+        })?;
+
+        if args.expire() <= time::Duration::ZERO {
+            return Err(other(ObjectError::new(
+                "presign",
+                &p,
+                anyhow!("expire must be positive, but got {}", args.expire()),
+            )));
+        }
+
+        let (method, permissions, headers) = match args.operation() {
+            PresignOperation::Read => (http::Method::GET, "r", http::HeaderMap::new()),
+            PresignOperation::Write => {
+                // Put Blob requires blob type, clients must send it too.
+                let mut headers = http::HeaderMap::new();
+                headers.insert(
+                    HeaderName::from_static(X_MS_BLOB_TYPE),
+                    http::HeaderValue::from_static("BlockBlob"),
+                );
+                (http::Method::PUT, "cw", headers)
+            }
+        };
+
+        let req = presign::presign(
+            cred,
+            method,
+            &self.endpoint,
+            &self.container,
+            &p,
+            permissions,
+            headers,
+            OffsetDateTime::now_utc() + args.expire(),
+        )
+        .map_err(|e| other(ObjectError::new("presign", &p, e)))?;
+
+        trace!("object {} presign finished", &p);
+        Ok(req)
+    }
 }
 
 impl Backend {
@@ -492,11 +933,14 @@ impl Backend {
         path: &str,
         offset: Option<u64>,
         size: Option<u64>,
+        preconditions: &Preconditions,
     ) -> Result<hyper::Response<hyper::Body>> {
         let url = format!("{}/{}/{}", self.endpoint, self.container, path);
 
         let mut req = hyper::Request::get(&url);
 
+        req = preconditions.insert_headers(req);
+
         if offset.is_some() || size.is_some() {
             req = req.header(
                 http::header::RANGE,
@@ -505,7 +949,7 @@ impl Backend {
         }
 
         let mut req = req.body(hyper::Body::empty()).map_err(|e| {
-            error!("object {path} get_blob: {url} {e:?}");
+            debug!("object {path} get_blob: {url} {e:?}");
             other(ObjectError::new(
                 "read",
                 path,
@@ -514,7 +958,7 @@ impl Backend {
         })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} get_blob: {url} {e:?}");
+            debug!("object {path} get_blob: {url} {e:?}");
             other(ObjectError::new(
                 "read",
                 path,
@@ -523,7 +967,7 @@ impl Backend {
         })?;
 
         self.client.request(req).await.map_err(|e| {
-            error!("object {path} get_blob: {url} {e:?}");
+            debug!("object {path} get_blob: {url} {e:?}");
             other(ObjectError::new(
                 "read",
                 path,
@@ -537,6 +981,7 @@ impl Backend {
         &self,
         path: &str,
         size: u64,
+        args: Option<&OpWrite>,
         body: Body,
     ) -> Result<hyper::Request<hyper::Body>> {
         let url = format!("{}/{}/{}", self.endpoint, self.container, path);
@@ -545,11 +990,16 @@ impl Backend {
 
         req = req.header(http::header::CONTENT_LENGTH, size.to_string());
 
+        if let Some(args) = args {
+            req = args.preconditions().insert_headers(req);
+            req = insert_write_headers(req, args);
+        }
+
         req = req.header(HeaderName::from_static(X_MS_BLOB_TYPE), "BlockBlob");
 
         // Set body
         let mut req = req.body(body).map_err(|e| {
-            error!("object {path} put_blob: {url} {e:?}");
+            debug!("object {path} put_blob: {url} {e:?}");
             other(ObjectError::new(
                 "write",
                 path,
@@ -558,7 +1008,7 @@ impl Backend {
         })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} put_blob: {url} {e:?}");
+            debug!("object {path} put_blob: {url} {e:?}");
             other(ObjectError::new(
                 "write",
                 path,
@@ -569,17 +1019,154 @@ impl Backend {
         Ok(req)
     }
 
+    #[trace("put_block")]
+    pub(crate) async fn put_block(
+        &self,
+        path: &str,
+        block_id: &str,
+        size: u64,
+        body: Body,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!(
+            "{}/{}/{}?comp=block&blockid={}",
+            self.endpoint,
+            self.container,
+            path,
+            utf8_percent_encode(block_id, NON_ALPHANUMERIC)
+        );
+
+        let mut req = hyper::Request::put(&url);
+
+        req = req.header(http::header::CONTENT_LENGTH, size.to_string());
+
+        let mut req = req.body(body).map_err(|e| {
+            debug!("object {path} put_block: {url} {e:?}");
+            other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} put_block: {url} {e:?}");
+            other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} put_block: {url} {e:?}");
+            other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    #[trace("put_block_list")]
+    pub(crate) async fn put_block_list(
+        &self,
+        path: &str,
+        block_ids: &[String],
+        args: Option<&OpWrite>,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!(
+            "{}/{}/{}?comp=blocklist",
+            self.endpoint, self.container, path
+        );
+
+        let content = put_block_list_request(block_ids);
+
+        let mut req = hyper::Request::put(&url);
+
+        req = req.header(http::header::CONTENT_LENGTH, content.len());
+
+        if let Some(args) = args {
+            req = args.preconditions().insert_headers(req);
+            req = insert_write_headers(req, args);
+        }
+
+        let mut req = req.body(hyper::Body::from(content)).map_err(|e| {
+            debug!("object {path} put_block_list: {url} {e:?}");
+            other(ObjectError::new(
+                "complete_multipart",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} put_block_list: {url} {e:?}");
+            other(ObjectError::new(
+                "complete_multipart",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} put_block_list: {url} {e:?}");
+            other(ObjectError::new(
+                "complete_multipart",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    /// Commit staged blocks into a blob.
+    ///
+    /// `options` is used to set headers like content type for the blob,
+    /// which is only available for unsized write.
+    async fn commit_blocks(
+        &self,
+        args: &OpCompleteMultipart,
+        options: Option<&OpWrite>,
+    ) -> Result<()> {
+        let p = self.get_abs_path(args.path());
+        trace!(
+            "object {} complete_multipart start: upload_id {}, parts {}",
+            &p,
+            args.upload_id(),
+            args.parts().len()
+        );
+
+        // Rebuild block ids instead of using the returned parts' etag, so
+        // that users can resume the upload with part numbers only.
+        let block_ids: Vec<String> = args
+            .parts()
+            .iter()
+            .map(|part| format_block_id(args.upload_id(), part.part_number()))
+            .collect();
+        let resp = self.put_block_list(&p, &block_ids, options).await?;
+
+        match resp.status() {
+            StatusCode::CREATED => {
+                trace!("object {} complete_multipart finished", &p);
+                Ok(())
+            }
+            _ => Err(parse_error_response_with_body(resp, "complete_multipart", &p).await),
+        }
+    }
+
     #[trace("get_blob_properties")]
     pub(crate) async fn get_blob_properties(
         &self,
         path: &str,
+        preconditions: &Preconditions,
     ) -> Result<hyper::Response<hyper::Body>> {
         let url = format!("{}/{}/{}", self.endpoint, self.container, path);
 
-        let req = hyper::Request::head(&url);
+        let mut req = hyper::Request::head(&url);
+
+        req = preconditions.insert_headers(req);
 
         let mut req = req.body(hyper::Body::empty()).map_err(|e| {
-            error!("object {path} get_blob_properties: {url} {e:?}");
+            debug!("object {path} get_blob_properties: {url} {e:?}");
             other(ObjectError::new(
                 "stat",
                 path,
@@ -588,7 +1175,7 @@ impl Backend {
         })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} get_blob_properties: {url} {e:?}");
+            debug!("object {path} get_blob_properties: {url} {e:?}");
             other(ObjectError::new(
                 "stat",
                 path,
@@ -597,7 +1184,7 @@ impl Backend {
         })?;
 
         self.client.request(req).await.map_err(|e| {
-            error!("object {path} get_blob_properties: {url} {e:?}");
+            debug!("object {path} get_blob_properties: {url} {e:?}");
             other(ObjectError::new(
                 "stat",
                 path,
@@ -613,7 +1200,7 @@ impl Backend {
         let req = hyper::Request::delete(&url);
 
         let mut req = req.body(hyper::Body::empty()).map_err(|e| {
-            error!("object {path} delete_blob: {url} {e:?}");
+            debug!("object {path} delete_blob: {url} {e:?}");
             other(ObjectError::new(
                 "delete",
                 path,
@@ -622,7 +1209,7 @@ impl Backend {
         })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} delete_blob: {url} {e:?}");
+            debug!("object {path} delete_blob: {url} {e:?}");
             other(ObjectError::new(
                 "delete",
                 path,
@@ -631,7 +1218,7 @@ impl Backend {
         })?;
 
         self.client.request(req).await.map_err(|e| {
-            error!("object {path} delete_object: {url} {e:?}");
+            debug!("object {path} delete_object: {url} {e:?}");
             other(ObjectError::new(
                 "delete",
                 path,
@@ -640,6 +1227,173 @@ impl Backend {
         })
     }
 
+    #[trace("put_append_blob")]
+    pub(crate) async fn put_append_blob(&self, path: &str) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!("{}/{}/{}", self.endpoint, self.container, path);
+
+        let mut req = hyper::Request::put(&url);
+
+        req = req.header(http::header::CONTENT_LENGTH, 0);
+        req = req.header(HeaderName::from_static(X_MS_BLOB_TYPE), "AppendBlob");
+        // Don't overwrite existing blob which could be appended by others.
+        req = req.header(http::header::IF_NONE_MATCH, "*");
+
+        let mut req = req.body(hyper::Body::empty()).map_err(|e| {
+            debug!("object {path} put_append_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} put_append_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} put_append_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    #[trace("append_block")]
+    pub(crate) async fn append_block(
+        &self,
+        path: &str,
+        bs: Bytes,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!(
+            "{}/{}/{}?comp=appendblock",
+            self.endpoint, self.container, path
+        );
+
+        let mut req = hyper::Request::put(&url);
+
+        req = req.header(http::header::CONTENT_LENGTH, bs.len());
+
+        let mut req = req.body(hyper::Body::from(bs)).map_err(|e| {
+            debug!("object {path} append_block: {url} {e:?}");
+            other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} append_block: {url} {e:?}");
+            other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} append_block: {url} {e:?}");
+            other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    /// Copy blob and wait until the copy finished.
+    ///
+    /// Azure copies blobs asynchronously, `x-ms-copy-status` could be
+    /// `pending` after the copy request returned, we need to poll the
+    /// properties of destination blob until the status becomes `success`.
+    async fn copy_and_wait(&self, op: &'static str, from: &str, to: &str) -> Result<()> {
+        let resp = self.copy_blob(from, to).await?;
+        if resp.status() != StatusCode::ACCEPTED {
+            return Err(parse_error_response_with_body(resp, op, from).await);
+        }
+
+        let mut status =
+            parse_header_to_str(resp.headers(), X_MS_COPY_STATUS, op, to)?.map(|v| v.to_string());
+        loop {
+            match status.as_deref() {
+                Some("success") => return Ok(()),
+                Some("pending") => {}
+                v => {
+                    return Err(other(ObjectError::new(
+                        op,
+                        to,
+                        anyhow!("copy from {from} failed with status {v:?}"),
+                    )))
+                }
+            }
+
+            tokio::time::sleep(COPY_POLL_INTERVAL).await;
+
+            let resp = self
+                .get_blob_properties(to, &Preconditions::default())
+                .await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error_response_without_body(resp, op, to));
+            }
+            status = parse_header_to_str(resp.headers(), X_MS_COPY_STATUS, op, to)?
+                .map(|v| v.to_string());
+        }
+    }
+
+    #[trace("copy_blob")]
+    pub(crate) async fn copy_blob(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let source = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.container,
+            percent_encode_path(from)
+        );
+        let url = format!("{}/{}/{}", self.endpoint, self.container, to);
+
+        let mut req = hyper::Request::put(&url);
+
+        req = req.header(HeaderName::from_static(X_MS_COPY_SOURCE), source);
+
+        let mut req = req.body(hyper::Body::empty()).map_err(|e| {
+            debug!("object {from} copy_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "copy",
+                from,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {from} copy_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "copy",
+                from,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {from} copy_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "copy",
+                from,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
     #[trace("list_blobs")]
     pub(crate) async fn list_blobs(
         &self,
@@ -660,7 +1414,7 @@ impl Backend {
         let mut req = hyper::Request::get(&url)
             .body(hyper::Body::empty())
             .map_err(|e| {
-                error!("object {path} list_blobs: {url} {e:?}");
+                debug!("object {path} list_blobs: {url} {e:?}");
                 other(ObjectError::new(
                     "list",
                     path,
@@ -669,7 +1423,7 @@ impl Backend {
             })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {} list_blobs: {url} {:?}", path, e);
+            debug!("object {} list_blobs: {url} {:?}", path, e);
             other(ObjectError::new(
                 "list",
                 path,
@@ -678,7 +1432,7 @@ impl Backend {
         })?;
 
         self.client.request(req).await.map_err(|e| {
-            error!("object {path} list_blobs: {url} {e:?}");
+            debug!("object {path} list_blobs: {url} {e:?}");
             other(ObjectError::new(
                 "list",
                 path,
@@ -688,21 +1442,86 @@ impl Backend {
     }
 }
 
+/// Insert headers like content type and user metadata for write.
+fn insert_write_headers(mut req: http::request::Builder, args: &OpWrite) -> http::request::Builder {
+    if let Some(v) = args.content_type() {
+        req = req.header(HeaderName::from_static(X_MS_BLOB_CONTENT_TYPE), v);
+    }
+    if let Some(v) = args.content_disposition() {
+        req = req.header(HeaderName::from_static(X_MS_BLOB_CONTENT_DISPOSITION), v);
+    }
+    if let Some(v) = args.cache_control() {
+        req = req.header(HeaderName::from_static(X_MS_BLOB_CACHE_CONTROL), v);
+    }
+    for (k, v) in args.user_metadata() {
+        req = req.header(format!("{}{k}", X_MS_META_PREFIX), v);
+    }
+    req
+}
+
+/// Generate a new upload id which is used as the prefix of block ids.
+fn new_upload_id() -> String {
+    format!("{:032x}", OffsetDateTime::now_utc().unix_timestamp_nanos())
+}
+
+/// Build block id from upload id and part number.
+///
+/// Azure requires all block ids of a blob to have the same length before
+/// base64 encoded, so we pad the part number here.
+fn format_block_id(upload_id: &str, part_number: usize) -> String {
+    base64::encode(format!("{upload_id}-{part_number:06}"))
+}
+
+/// Build the request body of Put Block List.
+fn put_block_list_request(block_ids: &[String]) -> String {
+    let mut content = String::from(r#"<?xml version="1.0" encoding="utf-8"?><BlockList>"#);
+    for block_id in block_ids {
+        content.push_str(&format!("<Latest>{block_id}</Latest>"));
+    }
+    content.push_str("</BlockList>");
+    content
+}
+
+/// UnsizedWriteAccessor is used by unsized write to commit staged blocks
+/// with the options of the write.
+#[derive(Debug)]
+struct UnsizedWriteAccessor {
+    backend: Backend,
+    args: OpWrite,
+}
+
+#[async_trait]
+impl Accessor for UnsizedWriteAccessor {
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        self.backend.write_multipart(args, r).await
+    }
+
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        self.backend.commit_blocks(args, Some(&self.args)).await
+    }
+
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        self.backend.abort_multipart(args).await
+    }
+}
+
 fn parse_error_response_without_body(resp: Response<Body>, op: &'static str, path: &str) -> Error {
     let (part, _) = resp.into_parts();
-    let kind = match part.status {
-        StatusCode::NOT_FOUND => ErrorKind::NotFound,
-        StatusCode::FORBIDDEN => ErrorKind::PermissionDenied,
-        StatusCode::INTERNAL_SERVER_ERROR
-        | StatusCode::BAD_GATEWAY
-        | StatusCode::SERVICE_UNAVAILABLE
-        | StatusCode::GATEWAY_TIMEOUT => ErrorKind::Interrupted,
-        _ => ErrorKind::Other,
-    };
-
-    io::Error::new(
-        kind,
-        ObjectError::new(op, path, anyhow!("response part: {:?}", part)),
+    let (io_kind, kind) = parse_http_status(part.status);
+    // Responses of requests like `HEAD` don't have body, but azblob will
+    // still return the error code in header.
+    let code = part
+        .headers
+        .get(X_MS_ERROR_CODE)
+        .and_then(|v| v.to_str().ok());
+    let (io_kind, kind) = parse_error_code(code, io_kind, kind);
+
+    Error::new(
+        io_kind,
+        ObjectError::new(op, path, anyhow!("response part: {:?}", part))
+            .with_kind(kind)
+            .with_scheme(Scheme::Azblob)
+            .with_response(format!("{:?}", part)),
     )
 }
 
@@ -713,15 +1532,7 @@ async fn parse_error_response_with_body(
     path: &str,
 ) -> Error {
     let (part, mut body) = resp.into_parts();
-    let kind = match part.status {
-        StatusCode::NOT_FOUND => ErrorKind::NotFound,
-        StatusCode::FORBIDDEN => ErrorKind::PermissionDenied,
-        StatusCode::INTERNAL_SERVER_ERROR
-        | StatusCode::BAD_GATEWAY
-        | StatusCode::SERVICE_UNAVAILABLE
-        | StatusCode::GATEWAY_TIMEOUT => ErrorKind::Interrupted,
-        _ => ErrorKind::Other,
-    };
+    let (io_kind, kind) = parse_http_status(part.status);
 
     // Only read 4KiB from the response to avoid broken services.
     let mut bs = Vec::new();
@@ -740,16 +1551,108 @@ async fn parse_error_response_with_body(
         }
     }
 
+    let body = String::from_utf8_lossy(&bs);
+    let code = part
+        .headers
+        .get(X_MS_ERROR_CODE)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            let (_, s) = body.split_once("<Code>")?;
+            let (code, _) = s.split_once("</Code>")?;
+            Some(code)
+        });
+    let (io_kind, kind) = parse_error_code(code, io_kind, kind);
+
     Error::new(
-        kind,
+        io_kind,
         ObjectError::new(
             op,
             path,
-            anyhow!(
-                "response part: {:?}, body: {:?}",
-                part,
-                String::from_utf8_lossy(&bs)
-            ),
-        ),
+            anyhow!("response part: {:?}, body: {:?}", part, body),
+        )
+        .with_kind(kind)
+        .with_scheme(Scheme::Azblob)
+        .with_response(format!("{:?}, body: {}", part, body)),
     )
 }
+
+/// Refine error kinds parsed from status code with azblob error code.
+fn parse_error_code(
+    code: Option<&str>,
+    io_kind: ErrorKind,
+    kind: crate::ErrorKind,
+) -> (ErrorKind, crate::ErrorKind) {
+    match code {
+        // Services will return `503` with special code while throttling.
+        Some("ServerBusy") => (io_kind, crate::ErrorKind::RateLimited),
+        // `409` is also returned for conflicts like `LeaseIdMissing`, so
+        // only these codes mean the target already exists.
+        Some("BlobAlreadyExists" | "ContainerAlreadyExists") => {
+            (ErrorKind::AlreadyExists, crate::ErrorKind::AlreadyExists)
+        }
+        _ => (io_kind, kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_response_without_body() {
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .header(X_MS_ERROR_CODE, "BlobAlreadyExists")
+            .body(Body::empty())
+            .expect("must be valid response");
+        let err = parse_error_response_without_body(resp, "write", "x");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::AlreadyExists);
+
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .header(X_MS_ERROR_CODE, "LeaseIdMissing")
+            .body(Body::empty())
+            .expect("must be valid response");
+        let err = parse_error_response_without_body(resp, "write", "x");
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::Unexpected);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_response_with_body() {
+        let resp = Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(Body::from("<Error><Code>ServerBusy</Code></Error>"))
+            .expect("must be valid response");
+        let err = parse_error_response_with_body(resp, "read", "x").await;
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::RateLimited);
+
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from("<Error><Code>BlobAlreadyExists</Code></Error>"))
+            .expect("must be valid response");
+        let err = parse_error_response_with_body(resp, "rename", "x").await;
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn test_format_block_id() {
+        let upload_id = new_upload_id();
+        assert_eq!(upload_id.len(), 32);
+
+        let a = format_block_id(&upload_id, 1);
+        let b = format_block_id(&upload_id, 10000);
+        assert_ne!(a, b);
+        assert_eq!(a.len(), b.len(), "block ids must have the same length");
+    }
+
+    #[test]
+    fn test_put_block_list_request() {
+        let block_ids = vec!["YmxvY2stMQ==".to_string(), "YmxvY2stMg==".to_string()];
+
+        assert_eq!(
+            put_block_list_request(&block_ids),
+            r#"<?xml version="1.0" encoding="utf-8"?><BlockList><Latest>YmxvY2stMQ==</Latest><Latest>YmxvY2stMg==</Latest></BlockList>"#
+        );
+    }
+}
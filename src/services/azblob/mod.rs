@@ -75,8 +75,10 @@
 mod backend;
 pub use backend::Backend;
 pub use backend::Builder;
+pub use backend::Config;
 
 mod object_stream;
+mod presign;
 
 #[doc(hidden)]
 #[cfg(feature = "testing")]
@@ -25,6 +25,7 @@ use futures::future::BoxFuture;
 use futures::ready;
 use futures::StreamExt;
 use log::debug;
+use log::trace;
 use quick_xml::de;
 use serde::Deserialize;
 
@@ -122,7 +123,7 @@ impl futures::Stream for AzblobObjectStream {
                             .set_content_length(0)
                             .set_complete();
 
-                        debug!(
+                        trace!(
                             "object {} got entry, path: {}, mode: {}",
                             &self.path,
                             meta.path(),
@@ -145,7 +146,7 @@ impl futures::Stream for AzblobObjectStream {
                     meta.set_mode(ObjectMode::FILE)
                         .set_content_length(object.properties.content_length as u64);
 
-                    debug!(
+                    trace!(
                         "object {} got entry, path: {}, mode: {}",
                         &self.path,
                         meta.path(),
@@ -155,7 +156,7 @@ impl futures::Stream for AzblobObjectStream {
                 }
 
                 if self.done {
-                    debug!("object {} list done", &self.path);
+                    trace!("object {} list done", &self.path);
                     return Poll::Ready(None);
                 }
 
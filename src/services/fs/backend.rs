@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::env;
 use std::io::ErrorKind;
 use std::io::Result;
 use std::io::SeekFrom;
@@ -24,34 +25,56 @@ use async_compat::Compat;
 use async_trait::async_trait;
 use futures::AsyncReadExt;
 use futures::AsyncSeekExt;
+use futures::AsyncWriteExt;
 use log::debug;
-use log::error;
 use log::info;
-use metrics::increment_counter;
+use log::trace;
 use minitrace::trace;
+use serde::Deserialize;
 use time::OffsetDateTime;
 use tokio::fs;
 
 use super::error::parse_io_error;
 use super::object_stream::Readdir;
+use crate::accessor::AccessorCapability;
 use crate::accessor::AccessorMetadata;
 use crate::error::other;
+use crate::error::unknown_config_key;
 use crate::error::BackendError;
 use crate::error::ObjectError;
+use crate::io_util::block_on;
+use crate::io_util::into_blocking_iterator;
+use crate::io_util::BfsWalker;
 use crate::object::Metadata;
+use crate::object::ObjectIterator;
 use crate::object::ObjectMode;
 use crate::object::ObjectStreamer;
+use crate::ops::OpAppend;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
 use crate::Accessor;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWriter;
 use crate::BytesReader;
 use crate::BytesWriter;
 use crate::Scheme;
 
+/// Config for fs services, which can be deserialized via serde.
+///
+/// Use [`Builder::from_config`] to convert it into a builder.
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// See [`Builder::root`].
+    pub root: Option<String>,
+}
+
 /// Builder for fs backend.
 #[derive(Default, Debug)]
 pub struct Builder {
@@ -59,6 +82,47 @@ pub struct Builder {
 }
 
 impl Builder {
+    /// Create a builder from [`Config`].
+    pub fn from_config(config: Config) -> Self {
+        let mut builder = Builder::default();
+        if let Some(v) = &config.root {
+            builder.root(v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from environment variables.
+    ///
+    /// Following variables will be loaded if set:
+    ///
+    /// - `OPENDAL_FS_ROOT`: [`Builder::root`]
+    ///
+    /// Other settings can still be changed via setters after loading.
+    pub fn from_env() -> Self {
+        let mut builder = Builder::default();
+        if let Ok(v) = env::var("OPENDAL_FS_ROOT") {
+            builder.root(&v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from a key/value config map.
+    ///
+    /// Keys are the same as the setter names, like `root`.
+    pub(crate) fn from_map(map: &HashMap<String, String>) -> Result<Self> {
+        let mut builder = Builder::default();
+        for (k, v) in map {
+            match k.as_str() {
+                "root" => builder.root(v),
+                _ => return Err(unknown_config_key(k)),
+            };
+        }
+
+        Ok(builder)
+    }
+
     /// Set root for backend.
     pub fn root(&mut self, root: &str) -> &mut Self {
         self.root = Some(root.to_string());
@@ -126,7 +190,18 @@ impl Backend {
 impl Accessor for Backend {
     fn metadata(&self) -> AccessorMetadata {
         let mut am = AccessorMetadata::default();
-        am.set_scheme(Scheme::Fs).set_root(&self.root);
+        am.set_scheme(Scheme::Fs)
+            .set_root(&self.root)
+            .set_capabilities(
+                AccessorCapability::READ
+                    | AccessorCapability::WRITE
+                    | AccessorCapability::LIST
+                    | AccessorCapability::RANGE_READ
+                    | AccessorCapability::COPY
+                    | AccessorCapability::RENAME
+                    | AccessorCapability::APPEND
+                    | AccessorCapability::BLOCKING,
+            );
 
         am
     }
@@ -148,7 +223,7 @@ impl Accessor for Backend {
 
             fs::create_dir_all(&parent).await.map_err(|e| {
                 let e = parse_io_error(e, "create", &parent.to_string_lossy());
-                error!(
+                debug!(
                     "object {} create_dir_all for parent {:?}: {:?}",
                     &path, &parent, e
                 );
@@ -162,7 +237,7 @@ impl Accessor for Backend {
                 .await
                 .map_err(|e| {
                     let e = parse_io_error(e, "create", &path);
-                    error!("object {} create: {:?}", &path, e);
+                    debug!("object {} create: {:?}", &path, e);
                     e
                 })?;
 
@@ -172,7 +247,7 @@ impl Accessor for Backend {
         if args.mode() == ObjectMode::DIR {
             fs::create_dir_all(&path).await.map_err(|e| {
                 let e = parse_io_error(e, "create", &path);
-                error!("object {} create: {:?}", &path, e);
+                debug!("object {} create: {:?}", &path, e);
                 e
             })?;
 
@@ -184,10 +259,8 @@ impl Accessor for Backend {
 
     #[trace("read")]
     async fn read(&self, args: &OpRead) -> Result<BytesReader> {
-        increment_counter!("opendal_fs_read_requests");
-
         let path = self.get_abs_path(args.path());
-        debug!(
+        trace!(
             "object {} read start: offset {:?}, size {:?}",
             &path,
             args.offset(),
@@ -200,16 +273,25 @@ impl Accessor for Backend {
             .await
             .map_err(|e| {
                 let e = parse_io_error(e, "read", &path);
-                error!("object {} open: {:?}", &path, e);
+                debug!("object {} open: {:?}", &path, e);
                 e
             })?;
 
+        if !args.preconditions().is_empty() {
+            let m = f
+                .metadata()
+                .await
+                .and_then(|meta| parse_metadata(args.path(), &meta))
+                .map_err(|e| parse_io_error(e, "read", &path))?;
+            args.preconditions().check("read", &path, Some(&m))?;
+        }
+
         let mut f = Compat::new(f);
 
         if let Some(offset) = args.offset() {
             f.seek(SeekFrom::Start(offset)).await.map_err(|e| {
                 let e = parse_io_error(e, "read", &path);
-                error!("object {} seek: {:?}", &path, e);
+                debug!("object {} seek: {:?}", &path, e);
                 e
             })?;
         };
@@ -219,7 +301,7 @@ impl Accessor for Backend {
             None => Box::new(f),
         };
 
-        debug!(
+        trace!(
             "object {} reader created: offset {:?}, size {:?}",
             &path,
             args.offset(),
@@ -230,10 +312,8 @@ impl Accessor for Backend {
 
     #[trace("write")]
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
-        increment_counter!("opendal_fs_write_requests");
-
         let path = self.get_abs_path(args.path());
-        debug!("object {} write start: size {}", &path, args.size());
+        trace!("object {} write start: size {}", &path, args.size());
 
         // Create dir before write path.
         //
@@ -254,7 +334,7 @@ impl Accessor for Backend {
 
         fs::create_dir_all(&parent).await.map_err(|e| {
             let e = parse_io_error(e, "write", &parent.to_string_lossy());
-            error!(
+            debug!(
                 "object {} create_dir_all for parent {}: {:?}",
                 &path,
                 &parent.to_string_lossy(),
@@ -263,67 +343,119 @@ impl Accessor for Backend {
             e
         })?;
 
+        // Preconditions are checked before open, so they can't prevent
+        // concurrent writers from racing with each other.
+        if !args.preconditions().is_empty() {
+            let m = match fs::metadata(&path).await {
+                Ok(meta) => Some(
+                    parse_metadata(args.path(), &meta)
+                        .map_err(|e| parse_io_error(e, "write", &path))?,
+                ),
+                Err(e) if e.kind() == ErrorKind::NotFound => None,
+                Err(e) => return Err(parse_io_error(e, "write", &path)),
+            };
+            args.preconditions().check("write", &path, m.as_ref())?;
+        }
+
         let f = fs::OpenOptions::new()
             .create(true)
             .write(true)
+            .truncate(true)
             .open(&path)
             .await
             .map_err(|e| {
                 let e = parse_io_error(e, "write", &path);
-                error!("object {} open: {:?}", &path, e);
+                debug!("object {} open: {:?}", &path, e);
                 e
             })?;
 
-        debug!("object {} write finished: size {:?}", &path, args.size());
+        trace!("object {} write finished: size {}", &path, args.size());
         Ok(Box::new(Compat::new(f)))
     }
 
+    #[trace("append")]
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let path = self.get_abs_path(args.path());
+        trace!("object {} append start: size {}", &path, args.size());
+
+        let parent = PathBuf::from(&path)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "append",
+                    &path,
+                    anyhow!("malformed path: {:?}", &path),
+                ))
+            })?
+            .to_path_buf();
+
+        fs::create_dir_all(&parent).await.map_err(|e| {
+            let e = parse_io_error(e, "append", &parent.to_string_lossy());
+            debug!(
+                "object {} create_dir_all for parent {}: {:?}",
+                &path,
+                &parent.to_string_lossy(),
+                e
+            );
+            e
+        })?;
+
+        // Open with `O_APPEND` so that every write goes to the end of file.
+        let f = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| {
+                let e = parse_io_error(e, "append", &path);
+                debug!("object {} open: {:?}", &path, e);
+                e
+            })?;
+
+        let mut f = Compat::new(f);
+        let n = futures::io::copy(r, &mut f).await.map_err(|e| {
+            let e = parse_io_error(e, "append", &path);
+            debug!("object {} append: {:?}", &path, e);
+            e
+        })?;
+        f.flush()
+            .await
+            .map_err(|e| parse_io_error(e, "append", &path))?;
+
+        if n != args.size() {
+            return Err(other(ObjectError::new(
+                "append",
+                &path,
+                anyhow!("append short, expect {} actual {}", args.size(), n),
+            )));
+        }
+
+        trace!("object {} append finished: size {}", &path, n);
+        Ok(())
+    }
+
     #[trace("stat")]
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
-        increment_counter!("opendal_fs_stat_requests");
-
         let path = self.get_abs_path(args.path());
-        debug!("object {} stat start", &path);
+        trace!("object {} stat start", &path);
 
         let meta = fs::metadata(&path).await.map_err(|e| {
             let e = parse_io_error(e, "stat", &path);
-            error!("object {} stat: {:?}", &path, e);
+            debug!("object {} stat: {:?}", &path, e);
             e
         })?;
 
-        let mut m = Metadata::default();
-        if meta.is_dir() {
-            let mut p = args.path().to_string();
-            if !p.ends_with('/') {
-                p.push('/')
-            }
-            m.set_path(&p);
-            m.set_mode(ObjectMode::DIR);
-        } else if meta.is_file() {
-            m.set_path(args.path());
-            m.set_mode(ObjectMode::FILE);
-        } else {
-            m.set_path(args.path());
-            m.set_mode(ObjectMode::Unknown);
-        }
-        m.set_content_length(meta.len() as u64);
-        m.set_last_modified(
-            meta.modified()
-                .map(OffsetDateTime::from)
-                .map_err(|e| parse_io_error(e, "stat", &path))?,
-        );
-        m.set_complete();
+        let m = parse_metadata(args.path(), &meta).map_err(|e| parse_io_error(e, "stat", &path))?;
+        args.preconditions().check("stat", &path, Some(&m))?;
 
-        debug!("object {} stat finished", &path);
+        trace!("object {} stat finished", &path);
         Ok(m)
     }
 
     #[trace("delete")]
     async fn delete(&self, args: &OpDelete) -> Result<()> {
-        increment_counter!("opendal_fs_delete_requests");
-
         let path = self.get_abs_path(args.path());
-        debug!("object {} delete start", &path);
+        trace!("object {} delete start", &path);
 
         // PathBuf.is_dir() is not free, call metadata directly instead.
         let meta = fs::metadata(&path).await;
@@ -333,7 +465,7 @@ impl Accessor for Backend {
                 Ok(())
             } else {
                 let e = parse_io_error(err, "delete", &path);
-                error!("object {} delete: {:?}", &path, e);
+                debug!("object {} delete: {:?}", &path, e);
                 Err(e)
             };
         }
@@ -349,20 +481,326 @@ impl Accessor for Backend {
 
         f.map_err(|e| parse_io_error(e, "delete", &path))?;
 
-        debug!("object {} delete finished", &path);
+        trace!("object {} delete finished", &path);
         Ok(())
     }
 
     #[trace("list")]
     async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
-        increment_counter!("opendal_fs_list_requests");
+        let path = self.get_abs_path(args.path());
+        trace!("object {} list start", &path);
+
+        if args.recursive() {
+            return Ok(Box::new(BfsWalker::new(
+                Arc::new(self.clone()),
+                args.path(),
+            )));
+        }
+
+        let f = std::fs::read_dir(&path).map_err(|e| {
+            let e = parse_io_error(e, "list", &path);
+            debug!("object {} list: {:?}", &path, e);
+            e
+        })?;
+
+        let rd = Readdir::new(Arc::new(self.clone()), &self.root, args.path(), f);
+
+        Ok(Box::new(rd))
+    }
+
+    #[trace("copy")]
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} copy start: to {}", &from, &to);
+
+        let parent = PathBuf::from(&to)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "copy",
+                    &to,
+                    anyhow!("malformed path: {:?}", &to),
+                ))
+            })?
+            .to_path_buf();
+
+        fs::create_dir_all(&parent).await.map_err(|e| {
+            let e = parse_io_error(e, "copy", &parent.to_string_lossy());
+            debug!(
+                "object {} create_dir_all for parent {}: {:?}",
+                &to,
+                &parent.to_string_lossy(),
+                e
+            );
+            e
+        })?;
+
+        fs::copy(&from, &to).await.map_err(|e| {
+            let e = parse_io_error(e, "copy", &from);
+            debug!("object {} copy: {:?}", &from, e);
+            e
+        })?;
+
+        trace!("object {} copy finished: to {}", &from, &to);
+        Ok(())
+    }
+
+    #[trace("rename")]
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} rename start: to {}", &from, &to);
+
+        let parent = PathBuf::from(&to)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "rename",
+                    &to,
+                    anyhow!("malformed path: {:?}", &to),
+                ))
+            })?
+            .to_path_buf();
+
+        fs::create_dir_all(&parent).await.map_err(|e| {
+            let e = parse_io_error(e, "rename", &parent.to_string_lossy());
+            debug!(
+                "object {} create_dir_all for parent {}: {:?}",
+                &to,
+                &parent.to_string_lossy(),
+                e
+            );
+            e
+        })?;
 
+        fs::rename(&from, &to).await.map_err(|e| {
+            let e = parse_io_error(e, "rename", &from);
+            debug!("object {} rename: {:?}", &from, e);
+            e
+        })?;
+
+        trace!("object {} rename finished: to {}", &from, &to);
+        Ok(())
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
         let path = self.get_abs_path(args.path());
-        debug!("object {} list start", &path);
+
+        if args.mode() == ObjectMode::FILE {
+            let parent = PathBuf::from(&path)
+                .parent()
+                .ok_or_else(|| {
+                    other(ObjectError::new(
+                        "create",
+                        &path,
+                        anyhow!("malformed path: {:?}", &path),
+                    ))
+                })?
+                .to_path_buf();
+
+            std::fs::create_dir_all(&parent).map_err(|e| {
+                let e = parse_io_error(e, "create", &parent.to_string_lossy());
+                debug!(
+                    "object {} create_dir_all for parent {:?}: {:?}",
+                    &path, &parent, e
+                );
+                e
+            })?;
+
+            std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .open(&path)
+                .map_err(|e| {
+                    let e = parse_io_error(e, "create", &path);
+                    debug!("object {} create: {:?}", &path, e);
+                    e
+                })?;
+
+            return Ok(());
+        }
+
+        if args.mode() == ObjectMode::DIR {
+            std::fs::create_dir_all(&path).map_err(|e| {
+                let e = parse_io_error(e, "create", &path);
+                debug!("object {} create: {:?}", &path, e);
+                e
+            })?;
+
+            return Ok(());
+        }
+
+        unreachable!()
+    }
+
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let path = self.get_abs_path(args.path());
+        trace!(
+            "object {} blocking_read start: offset {:?}, size {:?}",
+            &path,
+            args.offset(),
+            args.size()
+        );
+
+        let mut f = std::fs::OpenOptions::new()
+            .read(true)
+            .open(&path)
+            .map_err(|e| {
+                let e = parse_io_error(e, "read", &path);
+                debug!("object {} open: {:?}", &path, e);
+                e
+            })?;
+
+        if !args.preconditions().is_empty() {
+            let m = f
+                .metadata()
+                .and_then(|meta| parse_metadata(args.path(), &meta))
+                .map_err(|e| parse_io_error(e, "read", &path))?;
+            args.preconditions().check("read", &path, Some(&m))?;
+        }
+
+        if let Some(offset) = args.offset() {
+            std::io::Seek::seek(&mut f, SeekFrom::Start(offset)).map_err(|e| {
+                let e = parse_io_error(e, "read", &path);
+                debug!("object {} seek: {:?}", &path, e);
+                e
+            })?;
+        };
+
+        let r: BlockingBytesReader = match args.size() {
+            Some(size) => Box::new(std::io::Read::take(f, size)),
+            None => Box::new(f),
+        };
+
+        trace!(
+            "object {} blocking reader created: offset {:?}, size {:?}",
+            &path,
+            args.offset(),
+            args.size()
+        );
+        Ok(r)
+    }
+
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        let path = self.get_abs_path(args.path());
+        trace!(
+            "object {} blocking_write start: size {}",
+            &path,
+            args.size()
+        );
+
+        let parent = PathBuf::from(&path)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "write",
+                    &path,
+                    anyhow!("malformed path: {:?}", &path),
+                ))
+            })?
+            .to_path_buf();
+
+        std::fs::create_dir_all(&parent).map_err(|e| {
+            let e = parse_io_error(e, "write", &parent.to_string_lossy());
+            debug!(
+                "object {} create_dir_all for parent {}: {:?}",
+                &path,
+                &parent.to_string_lossy(),
+                e
+            );
+            e
+        })?;
+
+        if !args.preconditions().is_empty() {
+            let m = match std::fs::metadata(&path) {
+                Ok(meta) => Some(
+                    parse_metadata(args.path(), &meta)
+                        .map_err(|e| parse_io_error(e, "write", &path))?,
+                ),
+                Err(e) if e.kind() == ErrorKind::NotFound => None,
+                Err(e) => return Err(parse_io_error(e, "write", &path)),
+            };
+            args.preconditions().check("write", &path, m.as_ref())?;
+        }
+
+        let f = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|e| {
+                let e = parse_io_error(e, "write", &path);
+                debug!("object {} open: {:?}", &path, e);
+                e
+            })?;
+
+        trace!(
+            "object {} blocking_write finished: size {}",
+            &path,
+            args.size()
+        );
+        Ok(Box::new(f))
+    }
+
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
+        let path = self.get_abs_path(args.path());
+        trace!("object {} blocking_stat start", &path);
+
+        let meta = std::fs::metadata(&path).map_err(|e| {
+            let e = parse_io_error(e, "stat", &path);
+            debug!("object {} stat: {:?}", &path, e);
+            e
+        })?;
+
+        let m = parse_metadata(args.path(), &meta).map_err(|e| parse_io_error(e, "stat", &path))?;
+        args.preconditions().check("stat", &path, Some(&m))?;
+
+        trace!("object {} blocking_stat finished", &path);
+        Ok(m)
+    }
+
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
+        let path = self.get_abs_path(args.path());
+        trace!("object {} blocking_delete start", &path);
+
+        // PathBuf.is_dir() is not free, call metadata directly instead.
+        let meta = match std::fs::metadata(&path) {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => {
+                let e = parse_io_error(err, "delete", &path);
+                debug!("object {} delete: {:?}", &path, e);
+                return Err(e);
+            }
+        };
+
+        let f = if meta.is_dir() {
+            std::fs::remove_dir(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+
+        f.map_err(|e| parse_io_error(e, "delete", &path))?;
+
+        trace!("object {} blocking_delete finished", &path);
+        Ok(())
+    }
+
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        let path = self.get_abs_path(args.path());
+        trace!("object {} blocking_list start", &path);
+
+        // Recursive list relies on the async walker, run it on the internal
+        // runtime instead.
+        if args.recursive() {
+            let s = block_on(self.list(args))?;
+            return Ok(Box::new(into_blocking_iterator(s)));
+        }
 
         let f = std::fs::read_dir(&path).map_err(|e| {
             let e = parse_io_error(e, "list", &path);
-            error!("object {} list: {:?}", &path, e);
+            debug!("object {} list: {:?}", &path, e);
             e
         })?;
 
@@ -371,3 +809,37 @@ impl Accessor for Backend {
         Ok(Box::new(rd))
     }
 }
+
+/// Parse metadata of object from fs metadata.
+///
+/// ETag is built from the last modified time and length of the file, the
+/// same way as nginx does.
+fn parse_metadata(path: &str, meta: &std::fs::Metadata) -> Result<Metadata> {
+    let mut m = Metadata::default();
+    if meta.is_dir() {
+        let mut p = path.to_string();
+        if !p.ends_with('/') {
+            p.push('/')
+        }
+        m.set_path(&p);
+        m.set_mode(ObjectMode::DIR);
+    } else if meta.is_file() {
+        m.set_path(path);
+        m.set_mode(ObjectMode::FILE);
+    } else {
+        m.set_path(path);
+        m.set_mode(ObjectMode::Unknown);
+    }
+    m.set_content_length(meta.len());
+
+    let last_modified = OffsetDateTime::from(meta.modified()?);
+    m.set_last_modified(last_modified);
+    m.set_etag(&format!(
+        "\"{:x}-{:x}\"",
+        last_modified.unix_timestamp_nanos(),
+        meta.len()
+    ));
+    m.set_complete();
+
+    Ok(m)
+}
@@ -15,6 +15,7 @@
 use std::io::Error;
 
 use crate::error::ObjectError;
+use crate::Scheme;
 
 /// Parse all path related errors.
 ///
@@ -22,5 +23,8 @@ use crate::error::ObjectError;
 ///
 /// Skip utf-8 check to allow invalid path input.
 pub fn parse_io_error(err: Error, op: &'static str, path: &str) -> Error {
-    Error::new(err.kind(), ObjectError::new(op, path, err))
+    Error::new(
+        err.kind(),
+        ObjectError::new(op, path, err).with_scheme(Scheme::Fs),
+    )
 }
@@ -19,7 +19,7 @@ use std::task::Context;
 use std::task::Poll;
 
 use log::debug;
-use log::error;
+use log::trace;
 
 use super::error::parse_io_error;
 use crate::error::other;
@@ -47,52 +47,64 @@ impl Readdir {
     }
 }
 
-impl futures::Stream for Readdir {
+impl Readdir {
+    fn parse_entry(&self, de: std::fs::DirEntry) -> Result<Object> {
+        let de_path = de.path();
+        let de_path = de_path.strip_prefix(&self.root).map_err(|e| {
+            let e = other(ObjectError::new("list", &de.path().to_string_lossy(), e));
+            debug!("object {:?} path strip_prefix: {:?}", &de.path(), e);
+            e
+        })?;
+        let path = de_path.to_string_lossy();
+
+        let mut o = Object::new(self.acc.clone(), &path);
+
+        // On Windows and most Unix platforms this function is free
+        // (no extra system calls needed), but some Unix platforms may
+        // require the equivalent call to symlink_metadata to learn about
+        // the target file type.
+        let de = de.file_type()?;
+
+        let meta = o.metadata_mut();
+        if de.is_file() {
+            meta.set_mode(ObjectMode::FILE);
+            meta.set_path(&path);
+        } else if de.is_dir() {
+            // Make sure we are returning the correct path.
+            meta.set_path(&format!("{}/", &path));
+            meta.set_mode(ObjectMode::DIR);
+        } else {
+            meta.set_path(&path);
+            meta.set_mode(ObjectMode::Unknown);
+        }
+
+        trace!("object {} got entry, path: {}", &self.path, meta.path());
+        Ok(o)
+    }
+}
+
+impl Iterator for Readdir {
     type Item = Result<Object>;
 
-    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    fn next(&mut self) -> Option<Self::Item> {
         match self.rd.next() {
             None => {
-                debug!("object {} list done", &self.path);
-                Poll::Ready(None)
+                trace!("object {} list done", &self.path);
+                None
             }
             Some(Err(e)) => {
-                error!("object {} stream poll_next: {:?}", &self.path, e);
-                Poll::Ready(Some(Err(parse_io_error(e, "list", &self.path))))
+                debug!("object {} stream poll_next: {:?}", &self.path, e);
+                Some(Err(parse_io_error(e, "list", &self.path)))
             }
-            Some(Ok(de)) => {
-                let de_path = de.path();
-                let de_path = de_path.strip_prefix(&self.root).map_err(|e| {
-                    let e = other(ObjectError::new("list", &de.path().to_string_lossy(), e));
-                    error!("object {:?} path strip_prefix: {:?}", &de.path(), e);
-                    e
-                })?;
-                let path = de_path.to_string_lossy();
-
-                let mut o = Object::new(self.acc.clone(), &path);
-
-                // On Windows and most Unix platforms this function is free
-                // (no extra system calls needed), but some Unix platforms may
-                // require the equivalent call to symlink_metadata to learn about
-                // the target file type.
-                let de = de.file_type()?;
+            Some(Ok(de)) => Some(self.parse_entry(de)),
+        }
+    }
+}
 
-                let meta = o.metadata_mut();
-                if de.is_file() {
-                    meta.set_mode(ObjectMode::FILE);
-                    meta.set_path(&path);
-                } else if de.is_dir() {
-                    // Make sure we are returning the correct path.
-                    meta.set_path(&format!("{}/", &path));
-                    meta.set_mode(ObjectMode::DIR);
-                } else {
-                    meta.set_path(&path);
-                    meta.set_mode(ObjectMode::Unknown);
-                }
+impl futures::Stream for Readdir {
+    type Item = Result<Object>;
 
-                debug!("object {} got entry, path: {}", &self.path, meta.path());
-                Poll::Ready(Some(Ok(o)))
-            }
-        }
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.next())
     }
 }
@@ -33,7 +33,7 @@ pub async fn new() -> Result<Option<Arc<dyn Accessor>>> {
     let root = PathBuf::from(root).join(uuid::Uuid::new_v4().to_string());
 
     Ok(Some(
-        super::Backend::build()
+        super::Builder::from_env()
             .root(root.to_str().unwrap())
             .finish()
             .await?,
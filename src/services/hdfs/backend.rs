@@ -13,11 +13,13 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::env;
 use std::fmt::Debug;
 use std::io::ErrorKind;
 use std::io::Result;
 use std::io::Seek;
 use std::io::SeekFrom;
+use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -25,31 +27,58 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use futures::AsyncReadExt;
 use log::debug;
-use log::error;
 use log::info;
+use log::trace;
+use log::warn;
 use minitrace::trace;
+use serde::Deserialize;
 use time::OffsetDateTime;
 
 use super::error::parse_io_error;
 use super::object_stream::Readdir;
 use crate::error::other;
+use crate::error::unknown_config_key;
+use crate::error::unsupported;
 use crate::error::BackendError;
 use crate::error::ObjectError;
+use crate::io_util::block_on;
+use crate::io_util::into_blocking_iterator;
+use crate::io_util::BfsWalker;
+use crate::ops::OpAppend;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
 use crate::Accessor;
+use crate::AccessorCapability;
 use crate::AccessorMetadata;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWrite;
+use crate::BlockingBytesWriter;
 use crate::BytesReader;
 use crate::BytesWriter;
 use crate::Metadata;
+use crate::ObjectIterator;
 use crate::ObjectMode;
 use crate::ObjectStreamer;
 use crate::Scheme;
 
+/// Config for hdfs services, which can be deserialized via serde.
+///
+/// Use [`Builder::from_config`] to convert it into a builder.
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// See [`Builder::root`].
+    pub root: Option<String>,
+    /// See [`Builder::name_node`].
+    pub name_node: Option<String>,
+}
+
 /// Builder for hdfs services
 #[derive(Debug, Default)]
 pub struct Builder {
@@ -58,6 +87,55 @@ pub struct Builder {
 }
 
 impl Builder {
+    /// Create a builder from [`Config`].
+    pub fn from_config(config: Config) -> Self {
+        let mut builder = Builder::default();
+        if let Some(v) = &config.root {
+            builder.root(v);
+        }
+        if let Some(v) = &config.name_node {
+            builder.name_node(v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from environment variables.
+    ///
+    /// Following variables will be loaded if set:
+    ///
+    /// - `OPENDAL_HDFS_ROOT`: [`Builder::root`]
+    /// - `OPENDAL_HDFS_NAME_NODE`: [`Builder::name_node`]
+    ///
+    /// Other settings can still be changed via setters after loading.
+    pub fn from_env() -> Self {
+        let mut builder = Builder::default();
+        if let Ok(v) = env::var("OPENDAL_HDFS_ROOT") {
+            builder.root(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_HDFS_NAME_NODE") {
+            builder.name_node(&v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from a key/value config map.
+    ///
+    /// Keys are the same as the setter names, like `root`.
+    pub(crate) fn from_map(map: &HashMap<String, String>) -> Result<Self> {
+        let mut builder = Builder::default();
+        for (k, v) in map {
+            match k.as_str() {
+                "root" => builder.root(v),
+                "name_node" => builder.name_node(v),
+                _ => return Err(unknown_config_key(k)),
+            };
+        }
+
+        Ok(builder)
+    }
+
     /// Set root of this backend.
     ///
     /// All operations will happen under this root.
@@ -177,19 +255,390 @@ impl Backend {
             format!("{}/{}", &self.root, path)
         }
     }
+
+    /// Open file for read and seek to the offset, which is shared by both
+    /// `read` and `blocking_read`.
+    fn open_reader(&self, args: &OpRead) -> Result<hdrs::File> {
+        let path = self.get_abs_path(args.path());
+
+        // hdfs doesn't support conditional operations, reject them instead
+        // of ignoring silently.
+        if !args.preconditions().is_empty() {
+            return Err(unsupported("read", args.path()));
+        }
+
+        trace!(
+            "object {} read start: offset {:?}, size {:?}",
+            &path,
+            args.offset(),
+            args.size()
+        );
+
+        let mut f = self.client.open_file().read(true).open(&path)?;
+
+        if let Some(offset) = args.offset() {
+            f.seek(SeekFrom::Start(offset)).map_err(|e| {
+                let e = parse_io_error(e, "read", &path);
+                debug!("object {} seek: {:?}", &path, e);
+                e
+            })?;
+        };
+
+        trace!(
+            "object {} reader created: offset {:?}, size {:?}",
+            &path,
+            args.offset(),
+            args.size()
+        );
+        Ok(f)
+    }
+
+    /// Open file for write, which is shared by both `write` and `blocking_write`.
+    fn open_writer(&self, args: &OpWrite) -> Result<hdrs::File> {
+        let path = self.get_abs_path(args.path());
+
+        // hdfs doesn't support conditional operations, reject them instead
+        // of ignoring silently.
+        if !args.preconditions().is_empty() {
+            return Err(unsupported("write", args.path()));
+        }
+
+        trace!("object {} write start: size {}", &path, args.size());
+
+        let parent = PathBuf::from(&path)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "write",
+                    &path,
+                    anyhow!("malformed path: {:?}", &path),
+                ))
+            })?
+            .to_path_buf();
+
+        self.client
+            .create_dir(&parent.to_string_lossy())
+            .map_err(|e| {
+                let e = parse_io_error(e, "write", &parent.to_string_lossy());
+                debug!(
+                    "object {} create_dir_all for parent {}: {:?}",
+                    &path,
+                    &parent.to_string_lossy(),
+                    e
+                );
+                e
+            })?;
+
+        let f = self
+            .client
+            .open_file()
+            .create(true)
+            .write(true)
+            .open(&path)?;
+
+        trace!("object {} write finished: size {}", &path, args.size());
+        Ok(f)
+    }
+}
+
+impl BlockingBytesWrite for hdrs::File {
+    fn close(&mut self) -> Result<()> {
+        self.flush()
+    }
 }
 
 #[async_trait]
 impl Accessor for Backend {
     fn metadata(&self) -> AccessorMetadata {
         let mut am = AccessorMetadata::default();
-        am.set_scheme(Scheme::Hdfs).set_root(&self.root);
+        am.set_scheme(Scheme::Hdfs)
+            .set_root(&self.root)
+            .set_capabilities(
+                AccessorCapability::READ
+                    | AccessorCapability::WRITE
+                    | AccessorCapability::LIST
+                    | AccessorCapability::RANGE_READ
+                    | AccessorCapability::COPY
+                    | AccessorCapability::RENAME
+                    | AccessorCapability::APPEND
+                    | AccessorCapability::BLOCKING,
+            );
 
         am
     }
 
     #[trace("create")]
     async fn create(&self, args: &OpCreate) -> Result<()> {
+        self.blocking_create(args)
+    }
+
+    #[trace("read")]
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let f = self.open_reader(args)?;
+
+        let r: BytesReader = match args.size() {
+            None => Box::new(f),
+            Some(size) => Box::new(f.take(size)),
+        };
+        Ok(r)
+    }
+
+    #[trace("write")]
+    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        let f = self.open_writer(args)?;
+
+        Ok(Box::new(f))
+    }
+
+    #[trace("append")]
+    async fn append(&self, args: &OpAppend, r: BytesReader) -> Result<()> {
+        let path = self.get_abs_path(args.path());
+        trace!("object {} append start: size {}", &path, args.size());
+
+        let parent = PathBuf::from(&path)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "append",
+                    &path,
+                    anyhow!("malformed path: {:?}", &path),
+                ))
+            })?
+            .to_path_buf();
+
+        self.client
+            .create_dir(&parent.to_string_lossy())
+            .map_err(|e| {
+                let e = parse_io_error(e, "append", &parent.to_string_lossy());
+                debug!(
+                    "object {} create_dir_all for parent {}: {:?}",
+                    &path,
+                    &parent.to_string_lossy(),
+                    e
+                );
+                e
+            })?;
+
+        // hdfs can't open a not exist file in append mode, create it instead.
+        let exist = match self.client.metadata(&path) {
+            Ok(_) => true,
+            Err(e) if e.kind() == ErrorKind::NotFound => false,
+            Err(e) => return Err(parse_io_error(e, "append", &path)),
+        };
+        let mut f = if exist {
+            self.client.open_file().append(true).open(&path)
+        } else {
+            self.client.open_file().create(true).write(true).open(&path)
+        }
+        .map_err(|e| {
+            let e = parse_io_error(e, "append", &path);
+            debug!("object {} open: {:?}", &path, e);
+            e
+        })?;
+
+        let n = futures::io::copy(r, &mut f).await.map_err(|e| {
+            let e = parse_io_error(e, "append", &path);
+            debug!("object {} append: {:?}", &path, e);
+            e
+        })?;
+        // `std::io::Write` is in scope too, call `AsyncWriteExt` explicitly.
+        futures::AsyncWriteExt::close(&mut f)
+            .await
+            .map_err(|e| parse_io_error(e, "append", &path))?;
+
+        if n != args.size() {
+            return Err(other(ObjectError::new(
+                "append",
+                &path,
+                anyhow!("append short, expect {} actual {}", args.size(), n),
+            )));
+        }
+
+        trace!("object {} append finished: size {}", &path, n);
+        Ok(())
+    }
+
+    #[trace("stat")]
+    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        self.blocking_stat(args)
+    }
+
+    #[trace("delete")]
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        self.blocking_delete(args)
+    }
+
+    #[trace("list")]
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        let path = self.get_abs_path(args.path());
+        trace!("object {} list start", &path);
+
+        if args.recursive() {
+            return Ok(Box::new(BfsWalker::new(
+                Arc::new(self.clone()),
+                args.path(),
+            )));
+        }
+
+        let f = self.client.read_dir(&path).map_err(|e| {
+            let e = parse_io_error(e, "list", &path);
+            debug!("object {} list: {:?}", &path, e);
+            e
+        })?;
+
+        let rd = Readdir::new(Arc::new(self.clone()), &self.root, args.path(), f);
+
+        Ok(Box::new(rd))
+    }
+
+    #[trace("copy")]
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} copy start: to {}", &from, &to);
+
+        let parent = PathBuf::from(&to)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "copy",
+                    &to,
+                    anyhow!("malformed path: {:?}", &to),
+                ))
+            })?
+            .to_path_buf();
+
+        self.client
+            .create_dir(&parent.to_string_lossy())
+            .map_err(|e| {
+                let e = parse_io_error(e, "copy", &parent.to_string_lossy());
+                debug!(
+                    "object {} create_dir_all for parent {}: {:?}",
+                    &to,
+                    &parent.to_string_lossy(),
+                    e
+                );
+                e
+            })?;
+
+        // HDFS doesn't provide a copy API, we will copy the content between
+        // two files instead.
+        let mut r = self
+            .client
+            .open_file()
+            .read(true)
+            .open(&from)
+            .map_err(|e| {
+                let e = parse_io_error(e, "copy", &from);
+                debug!("object {} open: {:?}", &from, e);
+                e
+            })?;
+        let mut w = self
+            .client
+            .open_file()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&to)
+            .map_err(|e| {
+                let e = parse_io_error(e, "copy", &to);
+                debug!("object {} open: {:?}", &to, e);
+                e
+            })?;
+
+        std::io::copy(&mut r, &mut w).map_err(|e| {
+            let e = parse_io_error(e, "copy", &from);
+            debug!("object {} copy: {:?}", &from, e);
+            e
+        })?;
+        w.flush().map_err(|e| parse_io_error(e, "copy", &to))?;
+
+        trace!("object {} copy finished: to {}", &from, &to);
+        Ok(())
+    }
+
+    #[trace("rename")]
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} rename start: to {}", &from, &to);
+
+        let parent = PathBuf::from(&to)
+            .parent()
+            .ok_or_else(|| {
+                other(ObjectError::new(
+                    "rename",
+                    &to,
+                    anyhow!("malformed path: {:?}", &to),
+                ))
+            })?
+            .to_path_buf();
+
+        self.client
+            .create_dir(&parent.to_string_lossy())
+            .map_err(|e| {
+                let e = parse_io_error(e, "rename", &parent.to_string_lossy());
+                debug!(
+                    "object {} create_dir_all for parent {}: {:?}",
+                    &to,
+                    &parent.to_string_lossy(),
+                    e
+                );
+                e
+            })?;
+
+        // Make sure the source exists before touching the destination, so
+        // that a failed rename will not remove the existing destination.
+        self.client.metadata(&from).map_err(|e| {
+            let e = parse_io_error(e, "rename", &from);
+            debug!("object {} metadata: {:?}", &from, e);
+            e
+        })?;
+
+        // HDFS rename will fail if the destination exists, move it aside
+        // first to keep the same behavior with other services, and restore
+        // it if the rename failed.
+        let backup = format!(
+            "{}.opendal-rename-{:x}",
+            &to,
+            OffsetDateTime::now_utc().unix_timestamp_nanos()
+        );
+        let has_backup = match self.client.rename_file(&to, &backup) {
+            Ok(_) => true,
+            Err(e) if e.kind() == ErrorKind::NotFound => false,
+            Err(e) => {
+                let e = parse_io_error(e, "rename", &to);
+                debug!("object {} move existing aside: {:?}", &to, e);
+                return Err(e);
+            }
+        };
+
+        if let Err(e) = self.client.rename_file(&from, &to) {
+            let e = parse_io_error(e, "rename", &from);
+            debug!("object {} rename: {:?}", &from, e);
+
+            if has_backup {
+                if let Err(re) = self.client.rename_file(&backup, &to) {
+                    warn!(
+                        "object {} restore existing from {} failed: {:?}",
+                        &to, &backup, re
+                    );
+                }
+            }
+            return Err(e);
+        }
+
+        if has_backup {
+            if let Err(e) = self.client.remove_file(&backup) {
+                warn!("object {} remove backup {} failed: {:?}", &to, &backup, e);
+            }
+        }
+
+        trace!("object {} rename finished: to {}", &from, &to);
+        Ok(())
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
         let path = self.get_abs_path(args.path());
 
         match args.mode() {
@@ -209,7 +658,7 @@ impl Accessor for Backend {
                     .create_dir(&parent.to_string_lossy())
                     .map_err(|e| {
                         let e = parse_io_error(e, "create", &parent.to_string_lossy());
-                        error!("object {} mkdir for parent {:?}: {:?}", &path, &parent, e);
+                        debug!("object {} mkdir for parent {:?}: {:?}", &path, &parent, e);
                         e
                     })?;
 
@@ -221,7 +670,7 @@ impl Accessor for Backend {
                     .open(&path)
                     .map_err(|e| {
                         let e = parse_io_error(e, "create", &path);
-                        error!("object {} create: {:?}", &path, e);
+                        debug!("object {} create: {:?}", &path, e);
                         e
                     })?;
 
@@ -230,7 +679,7 @@ impl Accessor for Backend {
             ObjectMode::DIR => {
                 self.client.create_dir(&path).map_err(|e| {
                     let e = parse_io_error(e, "create", &path);
-                    error!("object {} create: {:?}", &path, e);
+                    debug!("object {} create: {:?}", &path, e);
                     e
                 })?;
 
@@ -240,89 +689,36 @@ impl Accessor for Backend {
         }
     }
 
-    #[trace("read")]
-    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
-        let path = self.get_abs_path(args.path());
-
-        debug!(
-            "object {} read start: offset {:?}, size {:?}",
-            &path,
-            args.offset(),
-            args.size()
-        );
-
-        let mut f = self.client.open_file().read(true).open(&path)?;
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let f = self.open_reader(args)?;
 
-        if let Some(offset) = args.offset() {
-            f.seek(SeekFrom::Start(offset)).map_err(|e| {
-                let e = parse_io_error(e, "read", &path);
-                error!("object {} seek: {:?}", &path, e);
-                e
-            })?;
-        };
-
-        let f: BytesReader = match args.size() {
+        let r: BlockingBytesReader = match args.size() {
             None => Box::new(f),
-            Some(size) => Box::new(f.take(size)),
+            Some(size) => Box::new(std::io::Read::take(f, size)),
         };
-
-        debug!(
-            "object {} reader created: offset {:?}, size {:?}",
-            &path,
-            args.offset(),
-            args.size()
-        );
-        Ok(f)
+        Ok(r)
     }
 
-    #[trace("write")]
-    async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
-        let path = self.get_abs_path(args.path());
-        debug!("object {} write start: size {}", &path, args.size());
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        let f = self.open_writer(args)?;
 
-        let parent = PathBuf::from(&path)
-            .parent()
-            .ok_or_else(|| {
-                other(ObjectError::new(
-                    "write",
-                    &path,
-                    anyhow!("malformed path: {:?}", &path),
-                ))
-            })?
-            .to_path_buf();
-
-        self.client
-            .create_dir(&parent.to_string_lossy())
-            .map_err(|e| {
-                let e = parse_io_error(e, "write", &parent.to_string_lossy());
-                error!(
-                    "object {} create_dir_all for parent {}: {:?}",
-                    &path,
-                    &parent.to_string_lossy(),
-                    e
-                );
-                e
-            })?;
-
-        let f = self
-            .client
-            .open_file()
-            .create(true)
-            .write(true)
-            .open(&path)?;
-
-        debug!("object {} write finished: size {:?}", &path, args.size());
         Ok(Box::new(f))
     }
 
-    #[trace("stat")]
-    async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
         let path = self.get_abs_path(args.path());
-        debug!("object {} stat start", &path);
+
+        // hdfs doesn't support conditional operations, reject them instead
+        // of ignoring silently.
+        if !args.preconditions().is_empty() {
+            return Err(unsupported("stat", args.path()));
+        }
+
+        trace!("object {} stat start", &path);
 
         let meta = self.client.metadata(&path).map_err(|e| {
             let e = parse_io_error(e, "stat", &path);
-            error!("object {} stat: {:?}", &path, e);
+            debug!("object {} stat: {:?}", &path, e);
             e
         })?;
 
@@ -340,16 +736,21 @@ impl Accessor for Backend {
         }
         m.set_content_length(meta.len());
         m.set_last_modified(OffsetDateTime::from(meta.modified()));
+        // Build etag from last modified time and length, the same as fs.
+        m.set_etag(&format!(
+            "\"{:x}-{:x}\"",
+            OffsetDateTime::from(meta.modified()).unix_timestamp_nanos(),
+            meta.len()
+        ));
         m.set_complete();
 
-        debug!("object {} stat finished: {:?}", &path, m);
+        trace!("object {} stat finished: {:?}", &path, m);
         Ok(m)
     }
 
-    #[trace("delete")]
-    async fn delete(&self, args: &OpDelete) -> Result<()> {
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
         let path = self.get_abs_path(args.path());
-        debug!("object {} delete start", &path);
+        trace!("object {} delete start", &path);
 
         let meta = self.client.metadata(&path);
 
@@ -358,7 +759,7 @@ impl Accessor for Backend {
                 Ok(())
             } else {
                 let e = parse_io_error(err, "delete", &path);
-                error!("object {} delete: {:?}", &path, e);
+                debug!("object {} delete: {:?}", &path, e);
                 Err(e)
             };
         }
@@ -374,18 +775,24 @@ impl Accessor for Backend {
 
         result.map_err(|e| parse_io_error(e, "delete", &path))?;
 
-        debug!("object {} delete finished", &path);
+        trace!("object {} delete finished", &path);
         Ok(())
     }
 
-    #[trace("list")]
-    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
         let path = self.get_abs_path(args.path());
-        debug!("object {} list start", &path);
+        trace!("object {} blocking_list start", &path);
+
+        // Recursive list relies on the async walker, run it on the internal
+        // runtime instead.
+        if args.recursive() {
+            let s = block_on(self.list(args))?;
+            return Ok(Box::new(into_blocking_iterator(s)));
+        }
 
         let f = self.client.read_dir(&path).map_err(|e| {
             let e = parse_io_error(e, "list", &path);
-            error!("object {} list: {:?}", &path, e);
+            debug!("object {} list: {:?}", &path, e);
             e
         })?;
 
@@ -89,6 +89,7 @@
 mod backend;
 pub use backend::Backend;
 pub use backend::Builder;
+pub use backend::Config;
 
 mod error;
 mod object_stream;
@@ -20,7 +20,7 @@ use std::task::Poll;
 
 use anyhow::anyhow;
 use log::debug;
-use log::error;
+use log::trace;
 
 use crate::error::other;
 use crate::error::ObjectError;
@@ -47,46 +47,58 @@ impl Readdir {
     }
 }
 
-impl futures::Stream for Readdir {
-    type Item = Result<Object>;
+impl Readdir {
+    fn parse_entry(&self, de: hdrs::Metadata) -> Result<Object> {
+        let de_path = de.path();
 
-    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        match self.rd.next() {
-            None => {
-                debug!("object {} list done", &self.path);
-                Poll::Ready(None)
-            }
-            Some(de) => {
-                let de_path = de.path();
+        let path = de_path.strip_prefix(&self.root).ok_or_else(|| {
+            let e = other(ObjectError::new(
+                "list",
+                de_path,
+                anyhow!("path doesn't have specified prefix"),
+            ));
+            debug!("object {:?} path strip_prefix: {:?}", &de_path, e);
+            e
+        })?;
 
-                let path = de_path.strip_prefix(&self.root).ok_or_else(|| {
-                    let e = other(ObjectError::new(
-                        "list",
-                        de_path,
-                        anyhow!("path doesn't have specified prefix"),
-                    ));
-                    error!("object {:?} path strip_prefix: {:?}", &de_path, e);
-                    e
-                })?;
+        let mut o = Object::new(self.acc.clone(), path);
 
-                let mut o = Object::new(self.acc.clone(), path);
+        let meta = o.metadata_mut();
+        if de.is_file() {
+            meta.set_mode(ObjectMode::FILE);
+            meta.set_path(path);
+        } else if de.is_dir() {
+            // Make sure we are returning the correct path.
+            meta.set_path(&format!("{}/", path));
+            meta.set_mode(ObjectMode::DIR);
+        } else {
+            meta.set_path(path);
+            meta.set_mode(ObjectMode::Unknown);
+        }
 
-                let meta = o.metadata_mut();
-                if de.is_file() {
-                    meta.set_mode(ObjectMode::FILE);
-                    meta.set_path(path);
-                } else if de.is_dir() {
-                    // Make sure we are returning the correct path.
-                    meta.set_path(&format!("{}/", path));
-                    meta.set_mode(ObjectMode::DIR);
-                } else {
-                    meta.set_path(path);
-                    meta.set_mode(ObjectMode::Unknown);
-                }
+        trace!("object {} got entry, path: {}", &self.path, meta.path());
+        Ok(o)
+    }
+}
 
-                debug!("object {} got entry, path: {}", &self.path, meta.path());
-                Poll::Ready(Some(Ok(o)))
+impl Iterator for Readdir {
+    type Item = Result<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.rd.next() {
+            None => {
+                trace!("object {} list done", &self.path);
+                None
             }
+            Some(de) => Some(self.parse_entry(de)),
         }
     }
 }
+
+impl futures::Stream for Readdir {
+    type Item = Result<Object>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.next())
+    }
+}
@@ -30,10 +30,7 @@ pub async fn new() -> Result<Option<Arc<dyn Accessor>>> {
     let root = &env::var("OPENDAL_HDFS_ROOT").unwrap_or_else(|_| "/".to_string());
     let root = format!("{}{}/", root, uuid::Uuid::new_v4());
 
-    let mut builder = super::Backend::build();
+    let mut builder = super::Builder::from_env();
     builder.root(&root);
-    builder.name_node(
-        &env::var("OPENDAL_HDFS_NAME_NODE").expect("OPENDAL_HDFS_NAME_NODE must be set"),
-    );
     Ok(Some(builder.finish().await?))
 }
@@ -27,21 +27,34 @@ use async_trait::async_trait;
 use bytes::BufMut;
 use bytes::Bytes;
 use futures::io::Cursor;
+use futures::AsyncReadExt;
 use futures::AsyncWrite;
 use minitrace::trace;
 use parking_lot::Mutex;
+use serde::Deserialize;
+use time::OffsetDateTime;
 
 use crate::error::other;
+use crate::error::unknown_config_key;
 use crate::error::ObjectError;
+use crate::object::ObjectIterator;
 use crate::object::ObjectStreamer;
+use crate::ops::OpAppend;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::Preconditions;
 use crate::Accessor;
+use crate::AccessorCapability;
 use crate::AccessorMetadata;
+use crate::BlockingBytesReader;
+use crate::BlockingBytesWrite;
+use crate::BlockingBytesWriter;
 use crate::BytesReader;
 use crate::BytesWriter;
 use crate::Metadata;
@@ -49,11 +62,42 @@ use crate::Object;
 use crate::ObjectMode;
 use crate::Scheme;
 
+/// Config for memory backend, which can be deserialized via serde.
+///
+/// Memory backend doesn't have any config, this is only provided for
+/// consistency with other services.
+#[derive(Default, Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {}
+
 /// Builder for memory backend
 #[derive(Default)]
 pub struct Builder {}
 
 impl Builder {
+    /// Create a builder from [`Config`].
+    pub fn from_config(_: Config) -> Self {
+        Builder::default()
+    }
+
+    /// Create a builder from environment variables.
+    ///
+    /// Memory backend doesn't have any config, this is the same as
+    /// [`Builder::default`] and only provided for consistency with other services.
+    pub fn from_env() -> Self {
+        Builder::default()
+    }
+
+    /// Create a builder from a key/value config map.
+    ///
+    /// Memory backend doesn't have any config, so the map must be empty.
+    pub(crate) fn from_map(map: &HashMap<String, String>) -> Result<Self> {
+        match map.keys().next() {
+            Some(k) => Err(unknown_config_key(k)),
+            None => Ok(Builder::default()),
+        }
+    }
+
     /// Consume builder to build a memory backend.
     pub async fn finish(&mut self) -> Result<Arc<dyn Accessor>> {
         Ok(Arc::new(Backend::default()))
@@ -63,7 +107,7 @@ impl Builder {
 /// Backend is used to serve `Accessor` support in memory.
 #[derive(Debug, Clone, Default)]
 pub struct Backend {
-    inner: Arc<Mutex<HashMap<String, bytes::Bytes>>>,
+    inner: Arc<Mutex<HashMap<String, Entry>>>,
 }
 
 impl Backend {
@@ -71,54 +115,24 @@ impl Backend {
     pub fn build() -> Builder {
         Builder::default()
     }
-}
-
-#[async_trait]
-impl Accessor for Backend {
-    fn metadata(&self) -> AccessorMetadata {
-        let mut am = AccessorMetadata::default();
-        am.set_scheme(Scheme::Memory)
-            .set_root("/")
-            .set_name("memory");
-
-        am
-    }
-
-    #[trace("create")]
-    async fn create(&self, args: &OpCreate) -> Result<()> {
-        let path = args.path();
-
-        match args.mode() {
-            ObjectMode::FILE => {
-                let mut map = self.inner.lock();
-                map.insert(path.to_string(), Bytes::new());
-
-                Ok(())
-            }
-            ObjectMode::DIR => {
-                let mut map = self.inner.lock();
-                map.insert(path.to_string(), Bytes::new());
-
-                Ok(())
-            }
-            _ => unreachable!(),
-        }
-    }
 
-    #[trace("read")]
-    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+    fn read_data(&self, args: &OpRead) -> Result<Bytes> {
         let path = args.path();
 
         let map = self.inner.lock();
 
-        let data = map.get(path).ok_or_else(|| {
+        let entry = map.get(path);
+        args.preconditions()
+            .check("read", path, entry.map(|v| v.metadata(path)).as_ref())?;
+
+        let data = entry.ok_or_else(|| {
             Error::new(
                 ErrorKind::NotFound,
                 ObjectError::new("read", path, anyhow!("key not exists in map")),
             )
         })?;
 
-        let mut data = data.clone();
+        let mut data = data.data.clone();
         if let Some(offset) = args.offset() {
             if offset >= data.len() as u64 {
                 return Err(other(ObjectError::new(
@@ -141,23 +155,188 @@ impl Accessor for Backend {
             data = data.slice(0..size as usize);
         };
 
+        Ok(data)
+    }
+
+    fn map_writer(&self, args: &OpWrite) -> MapWriter {
+        MapWriter {
+            path: args.path().to_string(),
+            size: if args.is_unsized() {
+                None
+            } else {
+                Some(args.size())
+            },
+            map: self.inner.clone(),
+            preconditions: args.preconditions().clone(),
+            buf: Default::default(),
+        }
+    }
+
+    fn entry_stream(&self, args: &OpList) -> EntryStream {
+        let mut path = args.path().to_string();
+        if path == "/" {
+            path.clear();
+        }
+
+        let map = self.inner.lock();
+
+        let paths = map
+            .iter()
+            .map(|(k, _)| k.clone())
+            .filter(|k| k.starts_with(&path))
+            .collect::<Vec<String>>();
+
+        EntryStream {
+            backend: self.clone(),
+            paths,
+            idx: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl Accessor for Backend {
+    fn metadata(&self) -> AccessorMetadata {
+        let mut am = AccessorMetadata::default();
+        am.set_scheme(Scheme::Memory)
+            .set_root("/")
+            .set_name("memory")
+            .set_capabilities(
+                AccessorCapability::READ
+                    | AccessorCapability::WRITE
+                    | AccessorCapability::LIST
+                    | AccessorCapability::RANGE_READ
+                    | AccessorCapability::COPY
+                    | AccessorCapability::RENAME
+                    | AccessorCapability::APPEND
+                    | AccessorCapability::BLOCKING,
+            );
+
+        am
+    }
+
+    #[trace("create")]
+    async fn create(&self, args: &OpCreate) -> Result<()> {
+        self.blocking_create(args)
+    }
+
+    #[trace("read")]
+    async fn read(&self, args: &OpRead) -> Result<BytesReader> {
+        let data = self.read_data(args)?;
+
         Ok(Box::new(Cursor::new(data)))
     }
 
     #[trace("write")]
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
+        Ok(Box::new(self.map_writer(args)))
+    }
+
+    #[trace("append")]
+    async fn append(&self, args: &OpAppend, mut r: BytesReader) -> Result<()> {
         let path = args.path();
 
-        Ok(Box::new(MapWriter {
-            path: path.to_string(),
-            size: args.size(),
-            map: self.inner.clone(),
-            buf: Default::default(),
-        }))
+        let mut buf = Vec::with_capacity(args.size() as usize);
+        r.read_to_end(&mut buf).await?;
+        if buf.len() as u64 != args.size() {
+            return Err(other(ObjectError::new(
+                "append",
+                path,
+                anyhow!("append short, expect {} actual {}", args.size(), buf.len()),
+            )));
+        }
+
+        let mut map = self.inner.lock();
+        let data = match map.get(path) {
+            Some(entry) => {
+                let mut data = bytes::BytesMut::from(entry.data.as_ref());
+                data.put_slice(&buf);
+                data.freeze()
+            }
+            None => Bytes::from(buf),
+        };
+        map.insert(path.to_string(), Entry::new(data));
+
+        Ok(())
     }
 
     #[trace("stat")]
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
+        self.blocking_stat(args)
+    }
+
+    #[trace("delete")]
+    async fn delete(&self, args: &OpDelete) -> Result<()> {
+        self.blocking_delete(args)
+    }
+
+    #[trace("list")]
+    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
+        Ok(Box::new(self.entry_stream(args)))
+    }
+
+    #[trace("copy")]
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let mut map = self.inner.lock();
+
+        let entry = map.get(args.from()).ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                ObjectError::new("copy", args.from(), anyhow!("key not exists in map")),
+            )
+        })?;
+        let entry = Entry::new(entry.data.clone());
+        map.insert(args.to().to_string(), entry);
+
+        Ok(())
+    }
+
+    #[trace("rename")]
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let mut map = self.inner.lock();
+
+        let data = map.remove(args.from()).ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                ObjectError::new("rename", args.from(), anyhow!("key not exists in map")),
+            )
+        })?;
+        map.insert(args.to().to_string(), data);
+
+        Ok(())
+    }
+
+    fn blocking_create(&self, args: &OpCreate) -> Result<()> {
+        let path = args.path();
+
+        match args.mode() {
+            ObjectMode::FILE => {
+                let mut map = self.inner.lock();
+                map.insert(path.to_string(), Entry::new(Bytes::new()));
+
+                Ok(())
+            }
+            ObjectMode::DIR => {
+                let mut map = self.inner.lock();
+                map.insert(path.to_string(), Entry::new(Bytes::new()));
+
+                Ok(())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn blocking_read(&self, args: &OpRead) -> Result<BlockingBytesReader> {
+        let data = self.read_data(args)?;
+
+        Ok(Box::new(std::io::Cursor::new(data)))
+    }
+
+    fn blocking_write(&self, args: &OpWrite) -> Result<BlockingBytesWriter> {
+        Ok(Box::new(self.map_writer(args)))
+    }
+
+    fn blocking_stat(&self, args: &OpStat) -> Result<Metadata> {
         let path = args.path();
 
         if path.ends_with('/') {
@@ -166,30 +345,28 @@ impl Accessor for Backend {
                 .set_mode(ObjectMode::DIR)
                 .set_content_length(0)
                 .set_complete();
+            args.preconditions().check("stat", path, Some(&meta))?;
 
             return Ok(meta);
         }
 
         let map = self.inner.lock();
 
-        let data = map.get(path).ok_or_else(|| {
+        let entry = map.get(path);
+        args.preconditions()
+            .check("stat", path, entry.map(|v| v.metadata(path)).as_ref())?;
+
+        let entry = entry.ok_or_else(|| {
             Error::new(
                 ErrorKind::NotFound,
                 ObjectError::new("read", path, anyhow!("key not exists in map")),
             )
         })?;
 
-        let mut meta = Metadata::default();
-        meta.set_path(path)
-            .set_mode(ObjectMode::FILE)
-            .set_content_length(data.len() as u64)
-            .set_complete();
-
-        Ok(meta)
+        Ok(entry.metadata(path))
     }
 
-    #[trace("delete")]
-    async fn delete(&self, args: &OpDelete) -> Result<()> {
+    fn blocking_delete(&self, args: &OpDelete) -> Result<()> {
         let path = args.path();
 
         let mut map = self.inner.lock();
@@ -198,37 +375,81 @@ impl Accessor for Backend {
         Ok(())
     }
 
-    #[trace("list")]
-    async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
-        let mut path = args.path().to_string();
-        if path == "/" {
-            path.clear();
-        }
+    fn blocking_list(&self, args: &OpList) -> Result<ObjectIterator> {
+        Ok(Box::new(self.entry_stream(args)))
+    }
+}
 
-        let map = self.inner.lock();
+/// Entry stored in the map, we keep last modified time to support
+/// conditional operations.
+#[derive(Debug, Clone)]
+struct Entry {
+    data: Bytes,
+    last_modified: OffsetDateTime,
+    /// Etag is computed once while creating to avoid hashing the whole
+    /// data on every stat.
+    etag: String,
+}
 
-        let paths = map
-            .iter()
-            .map(|(k, _)| k.clone())
-            .filter(|k| k.starts_with(&path))
-            .collect::<Vec<String>>();
+impl Entry {
+    fn new(data: Bytes) -> Self {
+        let etag = format!("\"{:x}\"", md5::compute(&data));
+        Self {
+            data,
+            last_modified: OffsetDateTime::now_utc(),
+            etag,
+        }
+    }
 
-        Ok(Box::new(EntryStream {
-            backend: self.clone(),
-            paths,
-            idx: 0,
-        }))
+    fn metadata(&self, path: &str) -> Metadata {
+        let mut meta = Metadata::default();
+        meta.set_path(path)
+            .set_mode(ObjectMode::FILE)
+            .set_content_length(self.data.len() as u64)
+            .set_last_modified(self.last_modified)
+            .set_etag(&self.etag)
+            .set_complete();
+        meta
     }
 }
 
 struct MapWriter {
     path: String,
-    size: u64,
-    map: Arc<Mutex<HashMap<String, bytes::Bytes>>>,
+    size: Option<u64>,
+    map: Arc<Mutex<HashMap<String, Entry>>>,
+    preconditions: Preconditions,
 
     buf: bytes::BytesMut,
 }
 
+impl MapWriter {
+    /// Commit the buffered data into map.
+    fn commit(&mut self) -> Result<()> {
+        if let Some(size) = self.size {
+            if self.buf.len() != size as usize {
+                return Err(other(ObjectError::new(
+                    "write",
+                    &self.path,
+                    anyhow!("write short, expect {} actual {}", size, self.buf.len()),
+                )));
+            }
+        }
+
+        let buf = mem::take(&mut self.buf);
+        let mut map = self.map.lock();
+        // Check preconditions while holding the lock so that concurrent
+        // writers can't overwrite each other.
+        self.preconditions.check(
+            "write",
+            &self.path,
+            map.get(&self.path).map(|v| v.metadata(&self.path)).as_ref(),
+        )?;
+        map.insert(self.path.clone(), Entry::new(buf.freeze()));
+
+        Ok(())
+    }
+}
+
 impl AsyncWrite for MapWriter {
     fn poll_write(
         mut self: Pin<&mut Self>,
@@ -245,23 +466,24 @@ impl AsyncWrite for MapWriter {
     }
 
     fn poll_close(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
-        if self.buf.len() != self.size as usize {
-            return Poll::Ready(Err(other(ObjectError::new(
-                "write",
-                &self.path,
-                anyhow!(
-                    "write short, expect {} actual {}",
-                    self.size,
-                    self.buf.len()
-                ),
-            ))));
-        }
+        Poll::Ready(self.commit())
+    }
+}
 
-        let buf = mem::take(&mut self.buf);
-        let mut map = self.map.lock();
-        map.insert(self.path.clone(), buf.freeze());
+impl std::io::Write for MapWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.buf.put_slice(buf);
+        Ok(buf.len())
+    }
 
-        Poll::Ready(Ok(()))
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl BlockingBytesWrite for MapWriter {
+    fn close(&mut self) -> Result<()> {
+        self.commit()
     }
 }
 
@@ -271,12 +493,12 @@ struct EntryStream {
     idx: usize,
 }
 
-impl futures::Stream for EntryStream {
+impl Iterator for EntryStream {
     type Item = Result<Object>;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    fn next(&mut self) -> Option<Self::Item> {
         if self.idx >= self.paths.len() {
-            return Poll::Ready(None);
+            return None;
         }
 
         let idx = self.idx;
@@ -287,18 +509,22 @@ impl futures::Stream for EntryStream {
         let backend = self.backend.clone();
         let map = backend.inner.lock();
 
-        let bs = match map.get(path) {
-            Some(bs) => bs,
-            None => return self.poll_next(cx),
+        let entry = match map.get(path) {
+            Some(entry) => entry,
+            None => return self.next(),
         };
 
         let mut o = Object::new(Arc::new(self.backend.clone()), path);
-        let meta = o.metadata_mut();
-        meta.set_path(path)
-            .set_mode(ObjectMode::FILE)
-            .set_content_length(bs.len() as u64)
-            .set_complete();
+        *o.metadata_mut() = entry.metadata(path);
+
+        Some(Ok(o))
+    }
+}
+
+impl futures::Stream for EntryStream {
+    type Item = Result<Object>;
 
-        Poll::Ready(Some(Ok(o)))
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.next())
     }
 }
@@ -17,6 +17,7 @@
 mod backend;
 pub use backend::Backend;
 pub use backend::Builder;
+pub use backend::Config;
 
 #[doc(hidden)]
 #[cfg(feature = "testing")]
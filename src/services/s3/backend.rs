@@ -14,6 +14,7 @@
 
 use std::cmp::min;
 use std::collections::HashMap;
+use std::env;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::io::Error;
@@ -24,44 +25,74 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use bytes::Buf;
 use bytes::BufMut;
 use futures::TryStreamExt;
 use http::header::HeaderName;
+use http::header::CACHE_CONTROL;
+use http::header::CONTENT_DISPOSITION;
+use http::header::CONTENT_TYPE;
 use http::HeaderValue;
 use http::Response;
 use http::StatusCode;
 use hyper::body::HttpBody;
 use hyper::Body;
 use log::debug;
-use log::error;
 use log::info;
-use metrics::increment_counter;
+use log::trace;
 use minitrace::trace;
 use once_cell::sync::Lazy;
+use percent_encoding::utf8_percent_encode;
+use percent_encoding::NON_ALPHANUMERIC;
+use quick_xml::de;
+use quick_xml::escape::escape;
 use reqsign::services::aws::v4::Signer;
+use serde::Deserialize;
 use time::format_description::well_known::Rfc2822;
 use time::OffsetDateTime;
 
 use super::object_stream::S3ObjectStream;
 use crate::error::other;
+use crate::error::parse_http_status;
+use crate::error::unknown_config_key;
+use crate::error::unsupported;
 use crate::error::BackendError;
 use crate::error::ObjectError;
+use crate::io_util::into_stream;
 use crate::io_util::new_http_channel;
+use crate::io_util::parse_header_to_str;
+use crate::io_util::parse_user_metadata;
+use crate::io_util::percent_encode_path;
 use crate::io_util::HttpBodyWriter;
+use crate::io_util::MultipartWriter;
 use crate::object::Metadata;
 use crate::object::ObjectStreamer;
 use crate::ops::BytesRange;
+use crate::ops::OpAbortMultipart;
+use crate::ops::OpBatchDelete;
+use crate::ops::OpCompleteMultipart;
+use crate::ops::OpCopy;
 use crate::ops::OpCreate;
+use crate::ops::OpCreateMultipart;
 use crate::ops::OpDelete;
 use crate::ops::OpList;
+use crate::ops::OpPresign;
 use crate::ops::OpRead;
+use crate::ops::OpRename;
 use crate::ops::OpStat;
 use crate::ops::OpWrite;
+use crate::ops::OpWriteMultipart;
+use crate::ops::Preconditions;
+use crate::ops::PresignOperation;
+use crate::ops::PresignedRequest;
 use crate::Accessor;
+use crate::AccessorCapability;
 use crate::AccessorMetadata;
 use crate::BytesReader;
 use crate::BytesWriter;
 use crate::ObjectMode;
+use crate::ObjectMultipart;
+use crate::ObjectPart;
 use crate::Scheme;
 
 /// Allow constructing correct region endpoint if user gives a global endpoint.
@@ -85,6 +116,90 @@ mod constants {
         "x-amz-server-side-encryption-customer-key-md5";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &str =
         "x-amz-server-side-encryption-aws-kms-key-id";
+
+    pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
+        "x-amz-copy-source-server-side-encryption-customer-algorithm";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY: &str =
+        "x-amz-copy-source-server-side-encryption-customer-key";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5: &str =
+        "x-amz-copy-source-server-side-encryption-customer-key-md5";
+
+    pub const X_AMZ_STORAGE_CLASS: &str = "x-amz-storage-class";
+    pub const X_AMZ_META_PREFIX: &str = "x-amz-meta-";
+
+    pub const CONTENT_MD5: &str = "content-md5";
+}
+
+/// Max keys that can be deleted in one DeleteObjects request.
+const DELETE_OBJECTS_MAX_KEYS: usize = 1000;
+
+/// Part size of multipart upload used by unsized write.
+const UNSIZED_WRITE_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Config for s3 services, which can be deserialized via serde.
+///
+/// Use [`Builder::from_config`] to convert it into a builder.
+#[derive(Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// See [`Builder::root`].
+    pub root: Option<String>,
+    /// See [`Builder::bucket`].
+    pub bucket: Option<String>,
+    /// See [`Builder::endpoint`].
+    pub endpoint: Option<String>,
+    /// See [`Builder::region`].
+    pub region: Option<String>,
+    /// See [`Builder::access_key_id`].
+    pub access_key_id: Option<String>,
+    /// See [`Builder::secret_access_key`].
+    pub secret_access_key: Option<String>,
+    /// See [`Builder::server_side_encryption`].
+    pub server_side_encryption: Option<String>,
+    /// See [`Builder::server_side_encryption_aws_kms_key_id`].
+    pub server_side_encryption_aws_kms_key_id: Option<String>,
+    /// See [`Builder::server_side_encryption_customer_algorithm`].
+    pub server_side_encryption_customer_algorithm: Option<String>,
+    /// See [`Builder::server_side_encryption_customer_key`].
+    pub server_side_encryption_customer_key: Option<String>,
+    /// See [`Builder::server_side_encryption_customer_key_md5`].
+    pub server_side_encryption_customer_key_md5: Option<String>,
+}
+
+impl Debug for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut d = f.debug_struct("Config");
+
+        d.field("root", &self.root)
+            .field("bucket", &self.bucket)
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.region);
+
+        if self.access_key_id.is_some() {
+            d.field("access_key_id", &"<redacted>");
+        }
+        if self.secret_access_key.is_some() {
+            d.field("secret_access_key", &"<redacted>");
+        }
+        if self.server_side_encryption.is_some() {
+            d.field("server_side_encryption", &"<redacted>");
+        }
+        if self.server_side_encryption_aws_kms_key_id.is_some() {
+            d.field("server_side_encryption_aws_kms_key_id", &"<redacted>");
+        }
+        if self.server_side_encryption_customer_algorithm.is_some() {
+            d.field("server_side_encryption_customer_algorithm", &"<redacted>");
+        }
+        if self.server_side_encryption_customer_key.is_some() {
+            d.field("server_side_encryption_customer_key", &"<redacted>");
+        }
+        if self.server_side_encryption_customer_key_md5.is_some() {
+            d.field("server_side_encryption_customer_key_md5", &"<redacted>");
+        }
+
+        d.finish()
+    }
 }
 
 /// Builder for s3 services
@@ -169,6 +284,135 @@ impl Debug for Builder {
 }
 
 impl Builder {
+    /// Create a builder from [`Config`].
+    pub fn from_config(config: Config) -> Self {
+        let mut builder = Builder::default();
+        if let Some(v) = &config.root {
+            builder.root(v);
+        }
+        if let Some(v) = &config.bucket {
+            builder.bucket(v);
+        }
+        if let Some(v) = &config.endpoint {
+            builder.endpoint(v);
+        }
+        if let Some(v) = &config.region {
+            builder.region(v);
+        }
+        if let Some(v) = &config.access_key_id {
+            builder.access_key_id(v);
+        }
+        if let Some(v) = &config.secret_access_key {
+            builder.secret_access_key(v);
+        }
+        if let Some(v) = &config.server_side_encryption {
+            builder.server_side_encryption(v);
+        }
+        if let Some(v) = &config.server_side_encryption_aws_kms_key_id {
+            builder.server_side_encryption_aws_kms_key_id(v);
+        }
+        if let Some(v) = &config.server_side_encryption_customer_algorithm {
+            builder.server_side_encryption_customer_algorithm(v);
+        }
+        if let Some(v) = &config.server_side_encryption_customer_key {
+            builder.server_side_encryption_customer_key(v);
+        }
+        if let Some(v) = &config.server_side_encryption_customer_key_md5 {
+            builder.server_side_encryption_customer_key_md5(v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from environment variables.
+    ///
+    /// Following variables will be loaded if set:
+    ///
+    /// - `OPENDAL_S3_ROOT`: [`Builder::root`]
+    /// - `OPENDAL_S3_BUCKET`: [`Builder::bucket`]
+    /// - `OPENDAL_S3_ENDPOINT`: [`Builder::endpoint`]
+    /// - `OPENDAL_S3_REGION`: [`Builder::region`]
+    /// - `OPENDAL_S3_ACCESS_KEY_ID`: [`Builder::access_key_id`]
+    /// - `OPENDAL_S3_SECRET_ACCESS_KEY`: [`Builder::secret_access_key`]
+    /// - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION`: [`Builder::server_side_encryption`]
+    /// - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID`: [`Builder::server_side_encryption_aws_kms_key_id`]
+    /// - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM`: [`Builder::server_side_encryption_customer_algorithm`]
+    /// - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY`: [`Builder::server_side_encryption_customer_key`]
+    /// - `OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5`: [`Builder::server_side_encryption_customer_key_md5`]
+    ///
+    /// Other settings can still be changed via setters after loading.
+    pub fn from_env() -> Self {
+        let mut builder = Builder::default();
+        if let Ok(v) = env::var("OPENDAL_S3_ROOT") {
+            builder.root(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_BUCKET") {
+            builder.bucket(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_ENDPOINT") {
+            builder.endpoint(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_REGION") {
+            builder.region(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_ACCESS_KEY_ID") {
+            builder.access_key_id(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_SECRET_ACCESS_KEY") {
+            builder.secret_access_key(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_SERVER_SIDE_ENCRYPTION") {
+            builder.server_side_encryption(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID") {
+            builder.server_side_encryption_aws_kms_key_id(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM") {
+            builder.server_side_encryption_customer_algorithm(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY") {
+            builder.server_side_encryption_customer_key(&v);
+        }
+        if let Ok(v) = env::var("OPENDAL_S3_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5") {
+            builder.server_side_encryption_customer_key_md5(&v);
+        }
+
+        builder
+    }
+
+    /// Create a builder from a key/value config map.
+    ///
+    /// Keys are the same as the setter names, like `root`.
+    pub(crate) fn from_map(map: &HashMap<String, String>) -> Result<Self> {
+        let mut builder = Builder::default();
+        for (k, v) in map {
+            match k.as_str() {
+                "root" => builder.root(v),
+                "bucket" => builder.bucket(v),
+                "endpoint" => builder.endpoint(v),
+                "region" => builder.region(v),
+                "access_key_id" => builder.access_key_id(v),
+                "secret_access_key" => builder.secret_access_key(v),
+                "server_side_encryption" => builder.server_side_encryption(v),
+                "server_side_encryption_aws_kms_key_id" => {
+                    builder.server_side_encryption_aws_kms_key_id(v)
+                }
+                "server_side_encryption_customer_algorithm" => {
+                    builder.server_side_encryption_customer_algorithm(v)
+                }
+                "server_side_encryption_customer_key" => {
+                    builder.server_side_encryption_customer_key(v)
+                }
+                "server_side_encryption_customer_key_md5" => {
+                    builder.server_side_encryption_customer_key_md5(v)
+                }
+                _ => return Err(unknown_config_key(k)),
+            };
+        }
+
+        Ok(builder)
+    }
+
     /// Set root of this backend.
     ///
     /// All operations will happen under this root.
@@ -436,7 +680,7 @@ impl Builder {
         let req = hyper::Request::head(&url)
             .body(Body::empty())
             .map_err(|e| {
-                error!("backend detect_region {}: {:?}", url, e);
+                debug!("backend detect_region {}: {:?}", url, e);
                 other(BackendError::new(
                     context.clone(),
                     anyhow!("build request {}: {:?}", url, e),
@@ -444,7 +688,7 @@ impl Builder {
             })?;
 
         let res = client.request(req).await.map_err(|e| {
-            error!("backend detect_region: {}: {:?}", url, e);
+            debug!("backend detect_region: {}: {:?}", url, e);
             other(BackendError::new(
                 context.clone(),
                 anyhow!("sending request: {}: {:?}", url, e),
@@ -703,6 +947,27 @@ impl Backend {
         }
     }
 
+    /// Insert headers like content type and user metadata for write.
+    pub(crate) fn insert_write_headers(
+        &self,
+        mut req: http::request::Builder,
+        args: &OpWrite,
+    ) -> http::request::Builder {
+        if let Some(v) = args.content_type() {
+            req = req.header(CONTENT_TYPE, v);
+        }
+        if let Some(v) = args.content_disposition() {
+            req = req.header(CONTENT_DISPOSITION, v);
+        }
+        if let Some(v) = args.cache_control() {
+            req = req.header(CACHE_CONTROL, v);
+        }
+        for (k, v) in args.user_metadata() {
+            req = req.header(format!("{}{k}", constants::X_AMZ_META_PREFIX), v);
+        }
+        req
+    }
+
     /// # Note
     ///
     /// header like X_AMZ_SERVER_SIDE_ENCRYPTION doesn't need to set while
@@ -713,24 +978,7 @@ impl Backend {
         is_write: bool,
     ) -> http::request::Builder {
         if is_write {
-            if let Some(v) = &self.server_side_encryption {
-                let mut v = v.clone();
-                v.set_sensitive(true);
-
-                req = req.header(
-                    HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION),
-                    v,
-                )
-            }
-            if let Some(v) = &self.server_side_encryption_aws_kms_key_id {
-                let mut v = v.clone();
-                v.set_sensitive(true);
-
-                req = req.header(
-                    HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID),
-                    v,
-                )
-            }
+            req = self.insert_sse_kms_headers(req);
         }
 
         if let Some(v) = &self.server_side_encryption_customer_algorithm {
@@ -763,6 +1011,78 @@ impl Backend {
 
         req
     }
+
+    /// Insert `x-amz-server-side-encryption` and aws kms key id headers,
+    /// which are only needed while writing.
+    fn insert_sse_kms_headers(&self, mut req: http::request::Builder) -> http::request::Builder {
+        if let Some(v) = &self.server_side_encryption {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION),
+                v,
+            )
+        }
+        if let Some(v) = &self.server_side_encryption_aws_kms_key_id {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(constants::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID),
+                v,
+            )
+        }
+
+        req
+    }
+
+    /// Insert SSE-C headers for the copy source object.
+    ///
+    /// # Note
+    ///
+    /// SSE-C requires the same key to decrypt the copy source, so we will
+    /// reuse the customer key of current backend.
+    pub(crate) fn insert_copy_source_sse_headers(
+        &self,
+        mut req: http::request::Builder,
+    ) -> http::request::Builder {
+        if let Some(v) = &self.server_side_encryption_customer_algorithm {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+                ),
+                v,
+            )
+        }
+        if let Some(v) = &self.server_side_encryption_customer_key {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
+                ),
+                v,
+            )
+        }
+        if let Some(v) = &self.server_side_encryption_customer_key_md5 {
+            let mut v = v.clone();
+            v.set_sensitive(true);
+
+            req = req.header(
+                HeaderName::from_static(
+                    constants::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+                ),
+                v,
+            )
+        }
+
+        req
+    }
 }
 
 #[async_trait]
@@ -771,25 +1091,35 @@ impl Accessor for Backend {
         let mut am = AccessorMetadata::default();
         am.set_scheme(Scheme::S3)
             .set_root(&self.root)
-            .set_name(&self.bucket);
+            .set_name(&self.bucket)
+            .set_capabilities(
+                AccessorCapability::READ
+                    | AccessorCapability::WRITE
+                    | AccessorCapability::LIST
+                    | AccessorCapability::RANGE_READ
+                    | AccessorCapability::COPY
+                    | AccessorCapability::RENAME
+                    | AccessorCapability::PRESIGN
+                    | AccessorCapability::MULTIPART
+                    | AccessorCapability::BATCH_DELETE,
+            );
 
         am
     }
 
     #[trace("create")]
     async fn create(&self, args: &OpCreate) -> Result<()> {
-        increment_counter!("opendal_s3_create_requests");
         let p = self.get_abs_path(args.path());
 
-        let req = self.put_object(&p, 0, Body::empty()).await?;
+        let req = self.put_object(&p, 0, None, Body::empty()).await?;
         let resp = self.client.request(req).await.map_err(|e| {
-            error!("object {} put_object: {:?}", args.path(), e);
+            debug!("object {} put_object: {:?}", args.path(), e);
             other(ObjectError::new("read", args.path(), e))
         })?;
 
         match resp.status() {
             StatusCode::CREATED | StatusCode::OK => {
-                debug!("object {} create finished", args.path());
+                trace!("object {} create finished", args.path());
                 Ok(())
             }
             _ => Err(parse_error_response_without_body(
@@ -802,10 +1132,8 @@ impl Accessor for Backend {
 
     #[trace("read")]
     async fn read(&self, args: &OpRead) -> Result<BytesReader> {
-        increment_counter!("opendal_s3_read_requests");
-
         let p = self.get_abs_path(args.path());
-        debug!(
+        trace!(
             "object {} read start: offset {:?}, size {:?}",
             &p,
             args.offset(),
@@ -813,16 +1141,16 @@ impl Accessor for Backend {
         );
 
         let resp = self
-            .get_object(&p, args.offset(), args.size())
+            .get_object(&p, args.offset(), args.size(), args.preconditions())
             .await
             .map_err(|e| {
-                error!("object {} get_object: {:?}", p, e);
+                debug!("object {} get_object: {:?}", p, e);
                 e
             })?;
 
         match resp.status() {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                debug!(
+                trace!(
                     "object {} reader created: offset {:?}, size {:?}",
                     &p,
                     args.offset(),
@@ -843,16 +1171,35 @@ impl Accessor for Backend {
     #[trace("write")]
     async fn write(&self, args: &OpWrite) -> Result<BytesWriter> {
         let p = self.get_abs_path(args.path());
-        debug!("object {} write start: size {}", &p, args.size());
+
+        if args.is_unsized() {
+            // Use multipart upload if size is unknown.
+            if !args.preconditions().is_empty() {
+                return Err(unsupported("write", args.path()));
+            }
+
+            let upload_id = self.start_multipart_upload(&p, Some(args)).await?;
+            trace!(
+                "object {} write started as multipart upload: upload_id {}",
+                &p,
+                &upload_id
+            );
+
+            let mp = ObjectMultipart::new(Arc::new(self.clone()), args.path(), &upload_id);
+            return Ok(Box::new(MultipartWriter::new(mp, UNSIZED_WRITE_PART_SIZE)));
+        }
+
+        let size = args.size();
+        trace!("object {} write start: size {}", &p, size);
 
         let (tx, body) = new_http_channel();
 
-        let req = self.put_object(&p, args.size(), body).await?;
+        let req = self.put_object(&p, size, Some(args), body).await?;
 
         let bs = HttpBodyWriter::new(args, tx, self.client.request(req), |op, resp| {
             match resp.status() {
                 StatusCode::CREATED | StatusCode::OK => {
-                    debug!("object {} write finished: size {:?}", op.path(), op.size());
+                    trace!("object {} write finished: size {}", op.path(), op.size());
                     Ok(())
                 }
                 _ => Err(parse_error_response_without_body(resp, "write", op.path())),
@@ -864,10 +1211,8 @@ impl Accessor for Backend {
 
     #[trace("stat")]
     async fn stat(&self, args: &OpStat) -> Result<Metadata> {
-        increment_counter!("opendal_s3_stat_requests");
-
         let p = self.get_abs_path(args.path());
-        debug!("object {} stat start", &p);
+        trace!("object {} stat start", &p);
 
         // Stat root always returns a DIR.
         if self.get_rel_path(&p).is_empty() {
@@ -877,11 +1222,11 @@ impl Accessor for Backend {
             m.set_mode(ObjectMode::DIR);
             m.set_complete();
 
-            debug!("backed root object stat finished");
+            trace!("backed root object stat finished");
             return Ok(m);
         }
 
-        let resp = self.head_object(&p).await?;
+        let resp = self.head_object(&p, args.preconditions()).await?;
 
         match resp.status() {
             StatusCode::OK => {
@@ -921,6 +1266,18 @@ impl Accessor for Backend {
                     m.set_content_md5(v);
                 }
 
+                // Parse etag
+                if let Some(v) = resp.headers().get(http::header::ETAG) {
+                    let v = v.to_str().map_err(|e| {
+                        other(ObjectError::new(
+                            "stat",
+                            &p,
+                            anyhow!("parse {} header: {:?}", http::header::ETAG, e),
+                        ))
+                    })?;
+                    m.set_etag(v);
+                }
+
                 // Parse last_modified
                 if let Some(v) = resp.headers().get(http::header::LAST_MODIFIED) {
                     let v = v.to_str().map_err(|e| {
@@ -940,6 +1297,42 @@ impl Accessor for Backend {
                     m.set_last_modified(t);
                 }
 
+                // Parse content_type
+                if let Some(v) =
+                    parse_header_to_str(resp.headers(), CONTENT_TYPE.as_str(), "stat", &p)?
+                {
+                    m.set_content_type(v);
+                }
+
+                // Parse content_disposition
+                if let Some(v) =
+                    parse_header_to_str(resp.headers(), CONTENT_DISPOSITION.as_str(), "stat", &p)?
+                {
+                    m.set_content_disposition(v);
+                }
+
+                // Parse cache_control
+                if let Some(v) =
+                    parse_header_to_str(resp.headers(), CACHE_CONTROL.as_str(), "stat", &p)?
+                {
+                    m.set_cache_control(v);
+                }
+
+                // Parse storage_class
+                if let Some(v) =
+                    parse_header_to_str(resp.headers(), constants::X_AMZ_STORAGE_CLASS, "stat", &p)?
+                {
+                    m.set_storage_class(v);
+                }
+
+                // Parse user_metadata
+                m.set_user_metadata(parse_user_metadata(
+                    resp.headers(),
+                    constants::X_AMZ_META_PREFIX,
+                    "stat",
+                    &p,
+                )?);
+
                 if p.ends_with('/') {
                     m.set_mode(ObjectMode::DIR);
                 } else {
@@ -948,7 +1341,7 @@ impl Accessor for Backend {
 
                 m.set_complete();
 
-                debug!("object {} stat finished: {:?}", &p, m);
+                trace!("object {} stat finished: {:?}", &p, m);
                 Ok(m)
             }
             StatusCode::NOT_FOUND if p.ends_with('/') => {
@@ -958,7 +1351,7 @@ impl Accessor for Backend {
                 m.set_mode(ObjectMode::DIR);
                 m.set_complete();
 
-                debug!("object {} stat finished", &p);
+                trace!("object {} stat finished", &p);
                 Ok(m)
             }
             _ => Err(parse_error_response_with_body(resp, "stat", &p).await),
@@ -967,107 +1360,381 @@ impl Accessor for Backend {
 
     #[trace("delete")]
     async fn delete(&self, args: &OpDelete) -> Result<()> {
-        increment_counter!("opendal_s3_delete_requests");
-
         let p = self.get_abs_path(args.path());
-        debug!("object {} delete start", &p);
+        trace!("object {} delete start", &p);
 
         let resp = self.delete_object(&p).await?;
 
         match resp.status() {
             StatusCode::NO_CONTENT => {
-                debug!("object {} delete finished", &p);
+                trace!("object {} delete finished", &p);
                 Ok(())
             }
             _ => Err(parse_error_response_with_body(resp, "delete", &p).await),
         }
     }
 
+    #[trace("batch_delete")]
+    async fn batch_delete(&self, args: &OpBatchDelete) -> Result<Vec<(String, Result<()>)>> {
+        trace!("object batch_delete start: {} paths", args.paths().len());
+
+        let mut results = Vec::with_capacity(args.paths().len());
+        for paths in args.paths().chunks(DELETE_OBJECTS_MAX_KEYS) {
+            let abs_paths: Vec<String> = paths.iter().map(|p| self.get_abs_path(p)).collect();
+
+            let errors = match self.delete_objects_chunk(&abs_paths).await {
+                Ok(errors) => errors,
+                Err(e) => {
+                    // The whole request failed, all paths in this chunk
+                    // share the same error.
+                    for (path, abs_path) in paths.iter().zip(abs_paths.iter()) {
+                        results.push((path.to_string(), Err(batch_delete_error(&e, abs_path))));
+                    }
+                    continue;
+                }
+            };
+
+            // The same path could appear more than once, so don't take the
+            // error out of map.
+            for (path, abs_path) in paths.iter().zip(abs_paths.iter()) {
+                let res = match errors.get(abs_path) {
+                    Some(e) => Err(batch_delete_error(e, abs_path)),
+                    None => Ok(()),
+                };
+                results.push((path.to_string(), res));
+            }
+        }
+
+        trace!("object batch_delete finished: {} paths", args.paths().len());
+        Ok(results)
+    }
+
     #[trace("list")]
     async fn list(&self, args: &OpList) -> Result<ObjectStreamer> {
-        increment_counter!("opendal_s3_list_requests");
-
         let mut path = self.get_abs_path(args.path());
         // Make sure list path is endswith '/'
         if !path.ends_with('/') && !path.is_empty() {
             path.push('/')
         }
-        debug!("object {} list start", &path);
+        trace!(
+            "object {} list start: recursive {}",
+            &path,
+            args.recursive()
+        );
 
-        Ok(Box::new(S3ObjectStream::new(self.clone(), path)))
-    }
-}
+        // S3 will list all objects under the prefix if delimiter is not set,
+        // which is much faster than walking every level.
+        let delimiter = if args.recursive() { "" } else { "/" };
 
-impl Backend {
-    #[trace("get_object")]
-    pub(crate) async fn get_object(
-        &self,
-        path: &str,
-        offset: Option<u64>,
-        size: Option<u64>,
-    ) -> Result<hyper::Response<hyper::Body>> {
-        let url = format!("{}/{}/{}", self.endpoint, self.bucket, path);
+        Ok(Box::new(S3ObjectStream::new(
+            self.clone(),
+            path,
+            delimiter.to_string(),
+        )))
+    }
 
-        let mut req = hyper::Request::get(&url);
+    #[trace("copy")]
+    async fn copy(&self, args: &OpCopy) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} copy start: to {}", &from, &to);
 
-        if offset.is_some() || size.is_some() {
-            req = req.header(
-                http::header::RANGE,
-                BytesRange::new(offset, size).to_string(),
-            );
-        }
+        self.copy_and_check("copy", &from, &to).await?;
 
-        // Set SSE headers.
-        req = self.insert_sse_headers(req, false);
+        trace!("object {} copy finished: to {}", &from, &to);
+        Ok(())
+    }
 
-        let mut req = req.body(hyper::Body::empty()).map_err(|e| {
-            error!("object {path} get_object: {url} {e:?}");
-            other(ObjectError::new(
-                "read",
-                path,
-                anyhow!("build request {url}: {e:?}"),
-            ))
-        })?;
+    #[trace("rename")]
+    async fn rename(&self, args: &OpRename) -> Result<()> {
+        let from = self.get_abs_path(args.from());
+        let to = self.get_abs_path(args.to());
+        trace!("object {} rename start: to {}", &from, &to);
 
-        self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} get_blob: {url} {e:?}");
-            other(ObjectError::new(
-                "read",
-                path,
-                anyhow!("sign request: {url}: {e:?}"),
-            ))
-        })?;
+        // S3 doesn't support rename natively, we need to copy and then
+        // delete the source object.
+        self.copy_and_check("rename", &from, &to).await?;
+        trace!("object {} rename copied: to {}", &from, &to);
 
-        self.client.request(req).await.map_err(|e| {
-            error!("object {path} get_blob: {url} {e:?}");
-            other(ObjectError::new(
-                "read",
-                path,
-                anyhow!("send request: {url}: {e:?}"),
-            ))
-        })
+        let resp = self.delete_object(&from).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT => {
+                trace!("object {} rename finished: to {}", &from, &to);
+                Ok(())
+            }
+            _ => Err(parse_error_response_with_body(resp, "rename", &from).await),
+        }
     }
 
-    #[trace("put_object")]
-    pub(crate) async fn put_object(
-        &self,
-        path: &str,
-        size: u64,
-        body: hyper::Body,
-    ) -> Result<hyper::Request<hyper::Body>> {
-        let url = format!("{}/{}/{}", self.endpoint, self.bucket, path);
-
-        let mut req = hyper::Request::put(&url);
+    #[trace("create_multipart")]
+    async fn create_multipart(&self, args: &OpCreateMultipart) -> Result<String> {
+        let p = self.get_abs_path(args.path());
+        trace!("object {} create_multipart start", &p);
 
-        // Set content length.
-        req = req.header(http::header::CONTENT_LENGTH, size.to_string());
+        let upload_id = self.start_multipart_upload(&p, None).await?;
 
-        // Set SSE headers.
-        req = self.insert_sse_headers(req, true);
+        trace!(
+            "object {} create_multipart finished: upload_id {}",
+            &p,
+            &upload_id
+        );
+        Ok(upload_id)
+    }
 
-        // Set body
-        let mut req = req.body(body).map_err(|e| {
-            error!("object {path} put_object: {url} {e:?}");
+    #[trace("write_multipart")]
+    async fn write_multipart(&self, args: &OpWriteMultipart, r: BytesReader) -> Result<ObjectPart> {
+        let p = self.get_abs_path(args.path());
+        trace!(
+            "object {} write_multipart start: upload_id {}, part_number {}, size {}",
+            &p,
+            args.upload_id(),
+            args.part_number(),
+            args.size()
+        );
+
+        let resp = self
+            .upload_part(
+                &p,
+                args.upload_id(),
+                args.part_number(),
+                args.size(),
+                Body::wrap_stream(into_stream(r, 64 * 1024)),
+            )
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let etag = resp
+                    .headers()
+                    .get(http::header::ETAG)
+                    .ok_or_else(|| {
+                        other(ObjectError::new(
+                            "write_multipart",
+                            &p,
+                            anyhow!("{} header is missing", http::header::ETAG),
+                        ))
+                    })?
+                    .to_str()
+                    .map_err(|e| {
+                        other(ObjectError::new(
+                            "write_multipart",
+                            &p,
+                            anyhow!("parse {} header: {:?}", http::header::ETAG, e),
+                        ))
+                    })?;
+
+                trace!(
+                    "object {} write_multipart finished: part_number {}, etag {}",
+                    &p,
+                    args.part_number(),
+                    etag
+                );
+                Ok(ObjectPart::new(args.part_number(), etag))
+            }
+            _ => Err(parse_error_response_with_body(resp, "write_multipart", &p).await),
+        }
+    }
+
+    #[trace("complete_multipart")]
+    async fn complete_multipart(&self, args: &OpCompleteMultipart) -> Result<()> {
+        let p = self.get_abs_path(args.path());
+        trace!(
+            "object {} complete_multipart start: upload_id {}, parts {}",
+            &p,
+            args.upload_id(),
+            args.parts().len()
+        );
+
+        let resp = self
+            .complete_multipart_upload(&p, args.upload_id(), args.parts())
+            .await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                // CompleteMultipartUpload could return an error with `200 OK`
+                // after the request has been accepted, we need to check the
+                // body to make sure it's succeeded.
+                let bs = hyper::body::to_bytes(resp.into_body()).await.map_err(|e| {
+                    other(ObjectError::new(
+                        "complete_multipart",
+                        &p,
+                        anyhow!("read body: {:?}", e),
+                    ))
+                })?;
+                let body = String::from_utf8_lossy(&bs);
+                if body.contains("<Error>") {
+                    return Err(Error::new(
+                        ErrorKind::Interrupted,
+                        ObjectError::new(
+                            "complete_multipart",
+                            &p,
+                            anyhow!("complete multipart upload failed: {:?}", body),
+                        ),
+                    ));
+                }
+
+                trace!("object {} complete_multipart finished", &p);
+                Ok(())
+            }
+            _ => Err(parse_error_response_with_body(resp, "complete_multipart", &p).await),
+        }
+    }
+
+    #[trace("abort_multipart")]
+    async fn abort_multipart(&self, args: &OpAbortMultipart) -> Result<()> {
+        let p = self.get_abs_path(args.path());
+        trace!(
+            "object {} abort_multipart start: upload_id {}",
+            &p,
+            args.upload_id()
+        );
+
+        let resp = self.abort_multipart_upload(&p, args.upload_id()).await?;
+
+        match resp.status() {
+            // Abort a not existing upload is treated as succeeded so that
+            // abort can be retried safely.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {
+                trace!("object {} abort_multipart finished", &p);
+                Ok(())
+            }
+            _ => Err(parse_error_response_with_body(resp, "abort_multipart", &p).await),
+        }
+    }
+
+    #[trace("presign")]
+    fn presign(&self, args: &OpPresign) -> Result<PresignedRequest> {
+        let p = self.get_abs_path(args.path());
+        trace!(
+            "object {} presign start: operation {:?}, expire {}",
+            &p,
+            args.operation(),
+            args.expire()
+        );
+
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&p)
+        );
+        let req = match args.operation() {
+            PresignOperation::Read => http::Request::get(&url),
+            // SSE-C headers carry the customer key, so they are not included
+            // in presigned request, clients need to send them by themselves.
+            PresignOperation::Write => self.insert_sse_kms_headers(http::Request::put(&url)),
+        };
+        let mut req = req.body(()).map_err(|e| {
+            debug!("object {p} presign: {url} {e:?}");
+            other(ObjectError::new(
+                "presign",
+                &p,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer
+            .sign_query(&mut req, args.expire())
+            .map_err(|e| {
+                debug!("object {p} presign: {url} {e:?}");
+                other(ObjectError::new(
+                    "presign",
+                    &p,
+                    anyhow!("sign request {url}: {e:?}"),
+                ))
+            })?;
+
+        // We don't need this request anymore, consume it directly.
+        let (parts, _) = req.into_parts();
+
+        trace!("object {} presign finished", &p);
+        Ok(PresignedRequest::new(
+            parts.method,
+            parts.uri,
+            parts.headers,
+        ))
+    }
+}
+
+impl Backend {
+    #[trace("get_object")]
+    pub(crate) async fn get_object(
+        &self,
+        path: &str,
+        offset: Option<u64>,
+        size: Option<u64>,
+        preconditions: &Preconditions,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!("{}/{}/{}", self.endpoint, self.bucket, path);
+
+        let mut req = hyper::Request::get(&url);
+
+        req = preconditions.insert_headers(req);
+
+        if offset.is_some() || size.is_some() {
+            req = req.header(
+                http::header::RANGE,
+                BytesRange::new(offset, size).to_string(),
+            );
+        }
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, false);
+
+        let mut req = req.body(hyper::Body::empty()).map_err(|e| {
+            debug!("object {path} get_object: {url} {e:?}");
+            other(ObjectError::new(
+                "read",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} get_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "read",
+                path,
+                anyhow!("sign request: {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} get_blob: {url} {e:?}");
+            other(ObjectError::new(
+                "read",
+                path,
+                anyhow!("send request: {url}: {e:?}"),
+            ))
+        })
+    }
+
+    #[trace("put_object")]
+    pub(crate) async fn put_object(
+        &self,
+        path: &str,
+        size: u64,
+        args: Option<&OpWrite>,
+        body: hyper::Body,
+    ) -> Result<hyper::Request<hyper::Body>> {
+        let url = format!("{}/{}/{}", self.endpoint, self.bucket, path);
+
+        let mut req = hyper::Request::put(&url);
+
+        // Set content length.
+        req = req.header(http::header::CONTENT_LENGTH, size.to_string());
+
+        if let Some(args) = args {
+            req = args.preconditions().insert_headers(req);
+            req = self.insert_write_headers(req, args);
+        }
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, true);
+
+        // Set body
+        let mut req = req.body(body).map_err(|e| {
+            debug!("object {path} put_object: {url} {e:?}");
             other(ObjectError::new(
                 "write",
                 path,
@@ -1076,7 +1743,7 @@ impl Backend {
         })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} put_object: {url} {e:?}");
+            debug!("object {path} put_object: {url} {e:?}");
             other(ObjectError::new(
                 "write",
                 path,
@@ -1088,16 +1755,22 @@ impl Backend {
     }
 
     #[trace("head_object")]
-    pub(crate) async fn head_object(&self, path: &str) -> Result<hyper::Response<hyper::Body>> {
+    pub(crate) async fn head_object(
+        &self,
+        path: &str,
+        preconditions: &Preconditions,
+    ) -> Result<hyper::Response<hyper::Body>> {
         let url = format!("{}/{}/{}", self.endpoint, self.bucket, path);
 
         let mut req = hyper::Request::head(&url);
 
+        req = preconditions.insert_headers(req);
+
         // Set SSE headers.
         req = self.insert_sse_headers(req, false);
 
         let mut req = req.body(hyper::Body::empty()).map_err(|e| {
-            error!("object {path} head_object: {url} {e:?}");
+            debug!("object {path} head_object: {url} {e:?}");
             other(ObjectError::new(
                 "stat",
                 path,
@@ -1106,7 +1779,7 @@ impl Backend {
         })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} head_object: {url} {e:?}");
+            debug!("object {path} head_object: {url} {e:?}");
             other(ObjectError::new(
                 "stat",
                 path,
@@ -1115,7 +1788,7 @@ impl Backend {
         })?;
 
         self.client.request(req).await.map_err(|e| {
-            error!("object {path} head_object: {url} {e:?}");
+            debug!("object {path} head_object: {url} {e:?}");
             other(ObjectError::new(
                 "stat",
                 path,
@@ -1131,7 +1804,7 @@ impl Backend {
         let mut req = hyper::Request::delete(&url)
             .body(hyper::Body::empty())
             .map_err(|e| {
-                error!("object {path} delete_object: {url} {e:?}");
+                debug!("object {path} delete_object: {url} {e:?}");
                 other(ObjectError::new(
                     "delete",
                     path,
@@ -1140,7 +1813,7 @@ impl Backend {
             })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} delete_object: {url} {e:?}");
+            debug!("object {path} delete_object: {url} {e:?}");
             other(ObjectError::new(
                 "delete",
                 path,
@@ -1149,7 +1822,7 @@ impl Backend {
         })?;
 
         self.client.request(req).await.map_err(|e| {
-            error!("object {path} delete_object: {url} {e:?}");
+            debug!("object {path} delete_object: {url} {e:?}");
             other(ObjectError::new(
                 "delete",
                 path,
@@ -1158,16 +1831,437 @@ impl Backend {
         })
     }
 
+    #[trace("delete_objects")]
+    pub(crate) async fn delete_objects(
+        &self,
+        paths: &[String],
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!("{}/{}?delete", self.endpoint, self.bucket);
+
+        let content = delete_objects_request(paths);
+
+        let mut req = hyper::Request::post(&url);
+
+        // Set content length.
+        req = req.header(http::header::CONTENT_LENGTH, content.len());
+        // DeleteObjects requires Content-MD5.
+        req = req.header(
+            HeaderName::from_static(constants::CONTENT_MD5),
+            base64::encode(md5::compute(content.as_bytes()).as_ref()),
+        );
+
+        let mut req = req.body(hyper::Body::from(content)).map_err(|e| {
+            debug!("object batch_delete delete_objects: {url} {e:?}");
+            other(ObjectError::new(
+                "batch_delete",
+                "/",
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object batch_delete delete_objects: {url} {e:?}");
+            other(ObjectError::new(
+                "batch_delete",
+                "/",
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object batch_delete delete_objects: {url} {e:?}");
+            other(ObjectError::new(
+                "batch_delete",
+                "/",
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    /// Delete a chunk of objects via DeleteObjects, returns errors of
+    /// objects that failed to delete.
+    async fn delete_objects_chunk(&self, paths: &[String]) -> Result<HashMap<String, Error>> {
+        let resp = self.delete_objects(paths).await?;
+
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error_response_with_body(resp, "batch_delete", "/").await);
+        }
+
+        let bs = hyper::body::to_bytes(resp.into_body()).await.map_err(|e| {
+            other(ObjectError::new(
+                "batch_delete",
+                "/",
+                anyhow!("read body: {:?}", e),
+            ))
+        })?;
+        let out: DeleteObjectsResult = de::from_reader(bs.reader()).map_err(|e| {
+            other(ObjectError::new(
+                "batch_delete",
+                "/",
+                anyhow!("parse xml: {:?}", e),
+            ))
+        })?;
+
+        // DeleteObjects could return an error with `200 OK`, which will have
+        // `Code` in the root element.
+        if !out.code.is_empty() {
+            return Err(Error::new(
+                ErrorKind::Interrupted,
+                ObjectError::new(
+                    "batch_delete",
+                    "/",
+                    anyhow!("delete objects failed: {} {}", out.code, out.message),
+                ),
+            ));
+        }
+
+        Ok(out
+            .error
+            .into_iter()
+            .map(|e| {
+                let (io_kind, kind) = match e.code.as_str() {
+                    "AccessDenied" => (
+                        ErrorKind::PermissionDenied,
+                        crate::ErrorKind::PermissionDenied,
+                    ),
+                    "SlowDown" => (ErrorKind::Interrupted, crate::ErrorKind::RateLimited),
+                    "InternalError" | "ServiceUnavailable" => {
+                        (ErrorKind::Interrupted, crate::ErrorKind::Unexpected)
+                    }
+                    _ => (ErrorKind::Other, crate::ErrorKind::Unexpected),
+                };
+                let err = Error::new(
+                    io_kind,
+                    ObjectError::new(
+                        "batch_delete",
+                        &e.key,
+                        anyhow!("delete object failed: {} {}", e.code, e.message),
+                    )
+                    .with_kind(kind)
+                    .with_scheme(Scheme::S3),
+                );
+                (e.key, err)
+            })
+            .collect())
+    }
+
+    /// Copy object and make sure it succeeded.
+    ///
+    /// CopyObject could return an error with `200 OK` after the request has
+    /// been accepted, we need to check the body to make sure it's succeeded.
+    async fn copy_and_check(&self, op: &'static str, from: &str, to: &str) -> Result<()> {
+        let resp = self.copy_object(from, to).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error_response_with_body(resp, op, from).await);
+        }
+
+        let bs = hyper::body::to_bytes(resp.into_body())
+            .await
+            .map_err(|e| other(ObjectError::new(op, from, anyhow!("read body: {:?}", e))))?;
+        let body = String::from_utf8_lossy(&bs);
+        if body.contains("<Error>") {
+            return Err(Error::new(
+                ErrorKind::Interrupted,
+                ObjectError::new(op, from, anyhow!("copy object to {to} failed: {:?}", body)),
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[trace("copy_object")]
+    pub(crate) async fn copy_object(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!("{}/{}/{}", self.endpoint, self.bucket, to);
+
+        let mut req = hyper::Request::put(&url);
+
+        req = req.header(
+            HeaderName::from_static(constants::X_AMZ_COPY_SOURCE),
+            format!("/{}/{}", self.bucket, percent_encode_path(from)),
+        );
+
+        // Set SSE headers for the destination object.
+        req = self.insert_sse_headers(req, true);
+        // Set SSE-C headers for the source object.
+        req = self.insert_copy_source_sse_headers(req);
+
+        let mut req = req.body(hyper::Body::empty()).map_err(|e| {
+            debug!("object {from} copy_object: {url} {e:?}");
+            other(ObjectError::new(
+                "copy",
+                from,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {from} copy_object: {url} {e:?}");
+            other(ObjectError::new(
+                "copy",
+                from,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {from} copy_object: {url} {e:?}");
+            other(ObjectError::new(
+                "copy",
+                from,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    /// Start a multipart upload and returns the upload id.
+    ///
+    /// `args` is used to set headers like content type for the object
+    /// which will be created after the upload completed.
+    pub(crate) async fn start_multipart_upload(
+        &self,
+        path: &str,
+        args: Option<&OpWrite>,
+    ) -> Result<String> {
+        let resp = self.initiate_multipart_upload(path, args).await?;
+
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = hyper::body::to_bytes(resp.into_body()).await.map_err(|e| {
+                    other(ObjectError::new(
+                        "create_multipart",
+                        path,
+                        anyhow!("read body: {:?}", e),
+                    ))
+                })?;
+
+                let output: InitiateMultipartUploadResult =
+                    de::from_reader(bs.reader()).map_err(|e| {
+                        other(ObjectError::new(
+                            "create_multipart",
+                            path,
+                            anyhow!("deserialize initiate_multipart_upload output: {:?}", e),
+                        ))
+                    })?;
+
+                Ok(output.upload_id)
+            }
+            _ => Err(parse_error_response_with_body(resp, "create_multipart", path).await),
+        }
+    }
+
+    #[trace("initiate_multipart_upload")]
+    pub(crate) async fn initiate_multipart_upload(
+        &self,
+        path: &str,
+        args: Option<&OpWrite>,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!("{}/{}/{}?uploads", self.endpoint, self.bucket, path);
+
+        let mut req = hyper::Request::post(&url);
+
+        if let Some(args) = args {
+            req = self.insert_write_headers(req, args);
+        }
+
+        // Set SSE headers.
+        req = self.insert_sse_headers(req, true);
+
+        let mut req = req.body(hyper::Body::empty()).map_err(|e| {
+            debug!("object {path} initiate_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "create_multipart",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} initiate_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "create_multipart",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} initiate_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "create_multipart",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    #[trace("upload_part")]
+    pub(crate) async fn upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: hyper::Body,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!(
+            "{}/{}/{}?partNumber={}&uploadId={}",
+            self.endpoint,
+            self.bucket,
+            path,
+            part_number,
+            utf8_percent_encode(upload_id, NON_ALPHANUMERIC)
+        );
+
+        let mut req = hyper::Request::put(&url);
+
+        // Set content length.
+        req = req.header(http::header::CONTENT_LENGTH, size.to_string());
+
+        // Set SSE headers.
+        //
+        // UploadPart only requires SSE-C headers, so we don't treat it as write.
+        req = self.insert_sse_headers(req, false);
+
+        let mut req = req.body(body).map_err(|e| {
+            debug!("object {path} upload_part: {url} {e:?}");
+            other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} upload_part: {url} {e:?}");
+            other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} upload_part: {url} {e:?}");
+            other(ObjectError::new(
+                "write_multipart",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    #[trace("complete_multipart_upload")]
+    pub(crate) async fn complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[ObjectPart],
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!(
+            "{}/{}/{}?uploadId={}",
+            self.endpoint,
+            self.bucket,
+            path,
+            utf8_percent_encode(upload_id, NON_ALPHANUMERIC)
+        );
+
+        let content = complete_multipart_upload_request(parts);
+
+        let mut req = hyper::Request::post(&url);
+
+        // Set content length.
+        req = req.header(http::header::CONTENT_LENGTH, content.len());
+
+        let mut req = req.body(hyper::Body::from(content)).map_err(|e| {
+            debug!("object {path} complete_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "complete_multipart",
+                path,
+                anyhow!("build request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} complete_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "complete_multipart",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} complete_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "complete_multipart",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
+    #[trace("abort_multipart_upload")]
+    pub(crate) async fn abort_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+    ) -> Result<hyper::Response<hyper::Body>> {
+        let url = format!(
+            "{}/{}/{}?uploadId={}",
+            self.endpoint,
+            self.bucket,
+            path,
+            utf8_percent_encode(upload_id, NON_ALPHANUMERIC)
+        );
+
+        let mut req = hyper::Request::delete(&url)
+            .body(hyper::Body::empty())
+            .map_err(|e| {
+                debug!("object {path} abort_multipart_upload: {url} {e:?}");
+                other(ObjectError::new(
+                    "abort_multipart",
+                    path,
+                    anyhow!("build request {url}: {e:?}"),
+                ))
+            })?;
+
+        self.signer.sign(&mut req).map_err(|e| {
+            debug!("object {path} abort_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "abort_multipart",
+                path,
+                anyhow!("sign request {url}: {e:?}"),
+            ))
+        })?;
+
+        self.client.request(req).await.map_err(|e| {
+            debug!("object {path} abort_multipart_upload: {url} {e:?}");
+            other(ObjectError::new(
+                "abort_multipart",
+                path,
+                anyhow!("send request {url}: {e:?}"),
+            ))
+        })
+    }
+
     #[trace("list_objects")]
     pub(crate) async fn list_objects(
         &self,
         path: &str,
+        delimiter: &str,
         continuation_token: &str,
     ) -> Result<hyper::Response<hyper::Body>> {
         let mut url = format!(
-            "{}/{}?list-type=2&delimiter=/&prefix={}",
+            "{}/{}?list-type=2&prefix={}",
             self.endpoint, self.bucket, path
         );
+        if !delimiter.is_empty() {
+            url.push_str(&format!("&delimiter={delimiter}"))
+        }
         if !continuation_token.is_empty() {
             url.push_str(&format!("&continuation-token={continuation_token}"))
         }
@@ -1175,7 +2269,7 @@ impl Backend {
         let mut req = hyper::Request::get(&url)
             .body(hyper::Body::empty())
             .map_err(|e| {
-                error!("object {path} list_objects: {url} {e:?}");
+                debug!("object {path} list_objects: {url} {e:?}");
                 other(ObjectError::new(
                     "list",
                     path,
@@ -1184,7 +2278,7 @@ impl Backend {
             })?;
 
         self.signer.sign(&mut req).map_err(|e| {
-            error!("object {path} list_objects: {url} {e:?}");
+            debug!("object {path} list_objects: {url} {e:?}");
             other(ObjectError::new(
                 "list",
                 path,
@@ -1193,7 +2287,7 @@ impl Backend {
         })?;
 
         self.client.request(req).await.map_err(|e| {
-            error!("object {path} list_object: {url} {e:?}");
+            debug!("object {path} list_object: {url} {e:?}");
             other(ObjectError::new(
                 "list",
                 path,
@@ -1203,22 +2297,85 @@ impl Backend {
     }
 }
 
+/// Output of InitiateMultipartUpload.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+/// Output of DeleteObjects.
+///
+/// We always send DeleteObjects in quiet mode, so only failed objects will
+/// be returned.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct DeleteObjectsResult {
+    error: Vec<DeleteObjectsResultError>,
+    /// Set only if the whole request failed.
+    code: String,
+    /// Set only if the whole request failed.
+    message: String,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct DeleteObjectsResultError {
+    key: String,
+    code: String,
+    message: String,
+}
+
+/// Build the request body of DeleteObjects in quiet mode.
+fn delete_objects_request(paths: &[String]) -> String {
+    let mut s = String::from("<Delete><Quiet>true</Quiet>");
+    for path in paths {
+        s.push_str(&format!(
+            "<Object><Key>{}</Key></Object>",
+            String::from_utf8_lossy(&escape(path.as_bytes()))
+        ));
+    }
+    s.push_str("</Delete>");
+    s
+}
+
+/// Build the request body of CompleteMultipartUpload.
+///
+/// # Note
+///
+/// quick-xml's serializer will write primitive fields as attributes, so we
+/// build the xml by hand instead.
+fn complete_multipart_upload_request(parts: &[ObjectPart]) -> String {
+    let mut s = String::from("<CompleteMultipartUpload>");
+    for part in parts {
+        s.push_str(&format!(
+            "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+            part.part_number(),
+            String::from_utf8_lossy(&escape(part.etag().as_bytes()))
+        ));
+    }
+    s.push_str("</CompleteMultipartUpload>");
+    s
+}
+
 // Read and decode whole error response.
 fn parse_error_response_without_body(resp: Response<Body>, op: &'static str, path: &str) -> Error {
     let (part, _) = resp.into_parts();
-    let kind = match part.status {
-        StatusCode::NOT_FOUND => ErrorKind::NotFound,
-        StatusCode::FORBIDDEN => ErrorKind::PermissionDenied,
-        StatusCode::INTERNAL_SERVER_ERROR
-        | StatusCode::BAD_GATEWAY
-        | StatusCode::SERVICE_UNAVAILABLE
-        | StatusCode::GATEWAY_TIMEOUT => ErrorKind::Interrupted,
-        _ => ErrorKind::Other,
+    let (io_kind, kind) = parse_http_status(part.status);
+    // S3 returns `503 Slow Down` while throttling, there is no body to check
+    // the code for requests like `HEAD`.
+    let (io_kind, kind) = if part.status == StatusCode::SERVICE_UNAVAILABLE {
+        (ErrorKind::Interrupted, crate::ErrorKind::RateLimited)
+    } else {
+        (io_kind, kind)
     };
 
     Error::new(
-        kind,
-        ObjectError::new(op, path, anyhow!("response part: {:?}", part)),
+        io_kind,
+        ObjectError::new(op, path, anyhow!("response part: {:?}", part))
+            .with_kind(kind)
+            .with_scheme(Scheme::S3)
+            .with_response(format!("{:?}", part)),
     )
 }
 
@@ -1229,15 +2386,7 @@ async fn parse_error_response_with_body(
     path: &str,
 ) -> Error {
     let (part, mut body) = resp.into_parts();
-    let kind = match part.status {
-        StatusCode::NOT_FOUND => ErrorKind::NotFound,
-        StatusCode::FORBIDDEN => ErrorKind::PermissionDenied,
-        StatusCode::INTERNAL_SERVER_ERROR
-        | StatusCode::BAD_GATEWAY
-        | StatusCode::SERVICE_UNAVAILABLE
-        | StatusCode::GATEWAY_TIMEOUT => ErrorKind::Interrupted,
-        _ => ErrorKind::Other,
-    };
+    let (io_kind, kind) = parse_http_status(part.status);
 
     // Only read 4KiB from the response to avoid broken services.
     let mut bs = Vec::new();
@@ -1262,26 +2411,241 @@ async fn parse_error_response_with_body(
         }
     }
 
+    let body = String::from_utf8_lossy(&bs);
+    let (io_kind, kind) = match parse_error_code(&body) {
+        // Services will return `503` with special code while throttling.
+        Some("SlowDown") => (io_kind, crate::ErrorKind::RateLimited),
+        // `409` is also returned for conflicts like `OperationAborted`, so
+        // only these codes mean the target already exists.
+        Some("BucketAlreadyExists" | "BucketAlreadyOwnedByYou") => {
+            (ErrorKind::AlreadyExists, crate::ErrorKind::AlreadyExists)
+        }
+        _ => (io_kind, kind),
+    };
+
     Error::new(
-        kind,
+        io_kind,
         ObjectError::new(
             op,
             path,
-            anyhow!(
-                "response part: {:?}, body: {:?}",
-                part,
-                String::from_utf8_lossy(&bs)
-            ),
-        ),
+            anyhow!("response part: {:?}, body: {:?}", part, body),
+        )
+        .with_kind(kind)
+        .with_scheme(Scheme::S3)
+        .with_response(format!("{:?}, body: {}", part, body)),
+    )
+}
+
+/// Build the error of a path in `batch_delete` from a shared error, which
+/// keeps the kind of the shared error.
+fn batch_delete_error(e: &Error, path: &str) -> Error {
+    Error::new(
+        e.kind(),
+        ObjectError::new("batch_delete", path, anyhow!("{e}"))
+            .with_kind(crate::ErrorKind::of(e))
+            .with_scheme(Scheme::S3),
     )
 }
 
+/// Extract the error code from the xml error response like
+/// `<Error><Code>NoSuchKey</Code></Error>`.
+fn parse_error_code(body: &str) -> Option<&str> {
+    let (_, s) = body.split_once("<Code>")?;
+    let (code, _) = s.split_once("</Code>")?;
+    Some(code)
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::iproduct;
 
     use super::*;
 
+    #[test]
+    fn test_parse_initiate_multipart_upload_result() {
+        let bs = bytes::Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<InitiateMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Bucket>example-bucket</Bucket>
+  <Key>example-object</Key>
+  <UploadId>VXBsb2FkIElEIGZvciA2aWWpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA</UploadId>
+</InitiateMultipartUploadResult>"#,
+        );
+
+        let out: InitiateMultipartUploadResult =
+            de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(
+            out.upload_id,
+            "VXBsb2FkIElEIGZvciA2aWWpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA"
+        );
+    }
+
+    #[test]
+    fn test_parse_delete_objects_result() {
+        let bs = bytes::Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<DeleteResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Error>
+    <Key>sample1.txt</Key>
+    <Code>AccessDenied</Code>
+    <Message>Access Denied</Message>
+  </Error>
+  <Error>
+    <Key>sample2.txt</Key>
+    <Code>InternalError</Code>
+    <Message>We encountered an internal error. Please try again.</Message>
+  </Error>
+</DeleteResult>"#,
+        );
+
+        let out: DeleteObjectsResult = de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.code.is_empty());
+        assert_eq!(out.error.len(), 2);
+        assert_eq!(out.error[0].key, "sample1.txt");
+        assert_eq!(out.error[0].code, "AccessDenied");
+        assert_eq!(out.error[1].key, "sample2.txt");
+        assert_eq!(out.error[1].code, "InternalError");
+    }
+
+    #[test]
+    fn test_parse_delete_objects_result_error() {
+        let bs = bytes::Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>InternalError</Code>
+  <Message>We encountered an internal error. Please try again.</Message>
+</Error>"#,
+        );
+
+        let out: DeleteObjectsResult = de::from_reader(bs.reader()).expect("must success");
+
+        assert!(out.error.is_empty());
+        assert_eq!(out.code, "InternalError");
+    }
+
+    #[test]
+    fn test_delete_objects_request() {
+        let paths = vec!["a.txt".to_string(), "dir/b&c.txt".to_string()];
+
+        assert_eq!(
+            delete_objects_request(&paths),
+            "<Delete><Quiet>true</Quiet>\
+            <Object><Key>a.txt</Key></Object>\
+            <Object><Key>dir/b&amp;c.txt</Key></Object>\
+            </Delete>"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_response_without_body() {
+        let resp = Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .body(Body::empty())
+            .expect("must be valid response");
+        let err = parse_error_response_without_body(resp, "stat", "x");
+        assert_eq!(err.kind(), ErrorKind::Interrupted);
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::RateLimited);
+
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .expect("must be valid response");
+        let err = parse_error_response_without_body(resp, "stat", "x");
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_batch_delete_error() {
+        let e: Error = ObjectError::new("batch_delete", "/", anyhow!("slow down"))
+            .with_kind(crate::ErrorKind::RateLimited)
+            .into();
+
+        let err = batch_delete_error(&e, "x");
+        assert_eq!(err.kind(), ErrorKind::Interrupted);
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::RateLimited);
+        match crate::Error::from_io(err).expect("must be structured error") {
+            crate::Error::Object(e) => assert_eq!(e.scheme(), Some(Scheme::S3)),
+            e => panic!("unexpected error: {e:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_response_with_body() {
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from(
+                "<Error><Code>BucketAlreadyOwnedByYou</Code></Error>",
+            ))
+            .expect("must be valid response");
+        let err = parse_error_response_with_body(resp, "create", "x").await;
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::AlreadyExists);
+
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from("<Error><Code>OperationAborted</Code></Error>"))
+            .expect("must be valid response");
+        let err = parse_error_response_with_body(resp, "write", "x").await;
+        assert_eq!(crate::ErrorKind::of(&err), crate::ErrorKind::Unexpected);
+    }
+
+    #[test]
+    fn test_complete_multipart_upload_request() {
+        let parts = vec![
+            ObjectPart::new(1, "\"a54357aff0632cce46d942af68356b38\""),
+            ObjectPart::new(2, "\"0c78aef83f66abc1fa1e8477f296d394\""),
+        ];
+
+        assert_eq!(
+            complete_multipart_upload_request(&parts),
+            "<CompleteMultipartUpload>\
+             <Part><PartNumber>1</PartNumber><ETag>&quot;a54357aff0632cce46d942af68356b38&quot;</ETag></Part>\
+             <Part><PartNumber>2</PartNumber><ETag>&quot;0c78aef83f66abc1fa1e8477f296d394&quot;</ETag></Part>\
+             </CompleteMultipartUpload>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_presign() -> anyhow::Result<()> {
+        let acc = Builder::default()
+            .bucket("test")
+            .endpoint("http://127.0.0.1:9000")
+            .region("us-east-1")
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .finish()
+            .await?;
+
+        let cases = vec![
+            ("read", PresignOperation::Read, http::Method::GET),
+            ("write", PresignOperation::Write, http::Method::PUT),
+        ];
+
+        for (name, op, method) in cases {
+            let req = acc.presign(&OpPresign::new(
+                "dir/a b?c#d.txt",
+                op,
+                time::Duration::hours(1),
+            )?)?;
+
+            assert_eq!(req.method(), method, "{name}");
+            assert_eq!(req.uri().host(), Some("127.0.0.1"), "{name}");
+            assert_eq!(req.uri().path(), "/test/dir/a%20b%3Fc%23d.txt", "{name}");
+
+            let query = req.uri().query().expect("presigned uri must have query");
+            assert!(
+                query.contains("X-Amz-Algorithm=AWS4-HMAC-SHA256"),
+                "{name}: {query}"
+            );
+            assert!(query.contains("X-Amz-Expires=3600"), "{name}: {query}");
+            assert!(query.contains("X-Amz-Signature="), "{name}: {query}");
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_detect_region() {
         let client = hyper::Client::builder().build(hyper_tls::HttpsConnector::new());
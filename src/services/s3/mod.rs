@@ -69,6 +69,7 @@
 mod backend;
 pub use backend::Backend;
 pub use backend::Builder;
+pub use backend::Config;
 
 mod object_stream;
 
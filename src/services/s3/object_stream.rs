@@ -26,8 +26,11 @@ use futures::future::BoxFuture;
 use futures::ready;
 use futures::StreamExt;
 use log::debug;
+use log::trace;
 use quick_xml::de;
 use serde::Deserialize;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
 use super::Backend;
 use crate::error::other;
@@ -38,6 +41,7 @@ use crate::ObjectMode;
 pub struct S3ObjectStream {
     backend: Backend,
     path: String,
+    delimiter: String,
 
     token: String,
     done: bool,
@@ -51,10 +55,11 @@ enum State {
 }
 
 impl S3ObjectStream {
-    pub fn new(backend: Backend, path: String) -> Self {
+    pub fn new(backend: Backend, path: String, delimiter: String) -> Self {
         Self {
             backend,
             path,
+            delimiter,
 
             token: "".to_string(),
             done: false,
@@ -73,9 +78,10 @@ impl futures::Stream for S3ObjectStream {
             State::Idle => {
                 let backend = self.backend.clone();
                 let path = self.path.clone();
+                let delimiter = self.delimiter.clone();
                 let token = self.token.clone();
                 let fut = async move {
-                    let mut resp = backend.list_objects(&path, &token).await?;
+                    let mut resp = backend.list_objects(&path, &delimiter, &token).await?;
 
                     if resp.status() != http::StatusCode::OK {
                         let e = other(ObjectError::new("list", &path, anyhow!("{:?}", resp)));
@@ -143,7 +149,7 @@ impl futures::Stream for S3ObjectStream {
                         .set_content_length(0)
                         .set_complete();
 
-                    debug!(
+                    trace!(
                         "object {} got entry, path: {}, mode: {}",
                         &self.path,
                         meta.path(),
@@ -162,10 +168,35 @@ impl futures::Stream for S3ObjectStream {
                         &backend.get_rel_path(&object.key),
                     );
                     let meta = o.metadata_mut();
-                    meta.set_mode(ObjectMode::FILE)
-                        .set_content_length(object.size as u64);
+                    // Without delimiter, dirs created by users will be
+                    // returned as objects ends with `/`.
+                    let mode = if object.key.ends_with('/') {
+                        ObjectMode::DIR
+                    } else {
+                        ObjectMode::FILE
+                    };
+                    meta.set_mode(mode).set_content_length(object.size as u64);
+                    if let Some(v) = &object.etag {
+                        meta.set_etag(v);
+                    }
+                    if let Some(v) = &object.last_modified {
+                        let t = OffsetDateTime::parse(v, &Rfc3339).map_err(|e| {
+                            other(ObjectError::new(
+                                "list",
+                                &object.key,
+                                anyhow!("parse last_modified {}: {:?}", v, e),
+                            ))
+                        })?;
+                        meta.set_last_modified(t);
+                    }
+                    if let Some(v) = &object.storage_class {
+                        meta.set_storage_class(v);
+                    }
+                    // ListObjects doesn't return content type, content
+                    // disposition, content md5, cache control and user
+                    // metadata, so the metadata is not complete.
 
-                    debug!(
+                    trace!(
                         "object {} got entry, path: {}, mode: {}",
                         &self.path,
                         meta.path(),
@@ -175,7 +206,7 @@ impl futures::Stream for S3ObjectStream {
                 }
 
                 if self.done {
-                    debug!("object {} list done", &self.path);
+                    trace!("object {} list done", &self.path);
                     return Poll::Ready(None);
                 }
 
@@ -209,6 +240,12 @@ struct Output {
 struct OutputContent {
     key: String,
     size: u64,
+    #[serde(default)]
+    last_modified: Option<String>,
+    #[serde(rename = "ETag", default)]
+    etag: Option<String>,
+    #[serde(default)]
+    storage_class: Option<String>,
 }
 
 #[derive(Default, Debug, Eq, PartialEq, Deserialize)]
@@ -272,11 +309,17 @@ mod tests {
             vec![
                 OutputContent {
                     key: "photos/2006".to_string(),
-                    size: 56
+                    size: 56,
+                    last_modified: Some("2016-04-30T23:51:29.000Z".to_string()),
+                    etag: Some("\"d41d8cd98f00b204e9800998ecf8427e\"".to_string()),
+                    storage_class: Some("STANDARD".to_string()),
                 },
                 OutputContent {
                     key: "photos/2007".to_string(),
-                    size: 100
+                    size: 100,
+                    last_modified: Some("2016-04-30T23:51:29.000Z".to_string()),
+                    etag: Some("\"d41d8cd98f00b204e9800998ecf8427e\"".to_string()),
+                    storage_class: Some("STANDARD".to_string()),
                 }
             ]
         )
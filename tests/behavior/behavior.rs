@@ -23,9 +23,13 @@
 use std::io;
 
 use anyhow::Result;
+use futures::AsyncWriteExt;
 use futures::StreamExt;
 use log::debug;
+use opendal::ops::Preconditions;
 use opendal::services;
+use opendal::AccessorCapability;
+use opendal::ErrorKind;
 use opendal::ObjectMode;
 use opendal::Operator;
 use sha2::Digest;
@@ -53,11 +57,16 @@ macro_rules! behavior_tests {
 
                 test_write,
                 test_write_with_dir_path,
+                test_write_with_if_none_match,
+                test_write_with_options,
+                test_write_unsized,
+                test_append,
 
                 test_read_full,
                 test_read_range,
                 test_read_not_exist,
                 test_read_with_dir_path,
+                test_read_with_if_match,
                 #[cfg(feature = "compress")]
                 test_read_decompress_gzip,
                 #[cfg(feature = "compress")]
@@ -68,14 +77,28 @@ macro_rules! behavior_tests {
                 test_stat_not_cleaned_path,
                 test_stat_not_exist,
                 test_stat_root,
+                test_stat_with_if_match,
 
                 test_list_dir,
                 test_list_sub_dir,
                 test_list_dir_with_file_path,
+                test_walk,
 
                 test_delete,
                 test_delete_not_existing,
                 test_delete_empty_dir,
+                test_batch_delete,
+                test_batch_delete_duplicated,
+
+                test_copy,
+                test_copy_not_exist,
+                test_copy_to_self,
+                test_rename,
+                test_rename_not_exist,
+
+                test_blocking_write_read,
+                test_blocking_overwrite,
+                test_blocking_list,
             );
         )*
     };
@@ -259,6 +282,151 @@ async fn test_write_with_dir_path(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write with if-none-match `*` should only succeed while file not exists.
+async fn test_write_with_if_none_match(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let res = op
+        .object(&path)
+        .write_with(&content, |op| {
+            op.with_preconditions(Preconditions::new().with_if_none_match("*"))
+        })
+        .await;
+    if let Err(e) = &res {
+        // Skip services that don't support conditional write.
+        if ErrorKind::of(e) == ErrorKind::Unsupported {
+            return Ok(());
+        }
+    }
+    res.expect("write must succeed");
+
+    let err = op
+        .object(&path)
+        .write_with(b"hello", |op| {
+            op.with_preconditions(Preconditions::new().with_if_none_match("*"))
+        })
+        .await
+        .expect_err("write must fail");
+    assert_eq!(ErrorKind::of(&err), ErrorKind::ConditionNotMatch);
+
+    let meta = op.object(&path).metadata().await?;
+    assert_eq!(meta.content_length(), size as u64);
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Write with options should be returned by stat if service supports them.
+async fn test_write_with_options(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let _ = op
+        .object(&path)
+        .write_with(&content, |op| {
+            op.with_content_type("text/plain")
+                .with_cache_control("max-age=3600")
+                .with_user_metadata("location", "earth")
+        })
+        .await
+        .expect("write must succeed");
+
+    let meta = op.object(&path).metadata().await?;
+    assert_eq!(meta.content_length(), size as u64);
+    // Only check services that support content type.
+    if meta.content_type().is_some() {
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(meta.cache_control(), Some("max-age=3600"));
+        assert_eq!(
+            meta.user_metadata().get("location").map(|v| v.as_str()),
+            Some("earth")
+        );
+    }
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Write without size should succeed.
+async fn test_write_unsized(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let mut w = op
+        .object(&path)
+        .unsized_writer()
+        .await
+        .expect("create unsized writer must succeed");
+    for chunk in content.chunks(1024) {
+        w.write_all(chunk).await?;
+    }
+    w.close().await?;
+
+    let meta = op.object(&path).metadata().await?;
+    assert_eq!(meta.content_length(), size as u64);
+
+    let bs = op.object(&path).read().await?;
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Append should create the file if not exist and append to the end.
+async fn test_append(op: Operator) -> Result<()> {
+    if !op
+        .metadata()
+        .capabilities()
+        .contains(AccessorCapability::APPEND)
+    {
+        return Ok(());
+    }
+
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content_one, size_one) = gen_bytes();
+    let (content_two, size_two) = gen_bytes();
+
+    op.object(&path)
+        .append(content_one.clone())
+        .await
+        .expect("append to not exist file must succeed");
+    op.object(&path)
+        .append(content_two.clone())
+        .await
+        .expect("append to existing file must succeed");
+
+    let meta = op.object(&path).metadata().await?;
+    assert_eq!(meta.content_length(), (size_one + size_two) as u64);
+
+    let bs = op.object(&path).read().await?;
+    assert_eq!(&bs[..size_one], &content_one[..], "read content one");
+    assert_eq!(&bs[size_one..], &content_two[..], "read content two");
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Stat existing file should return metadata
 async fn test_stat(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -343,6 +511,54 @@ async fn test_stat_root(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat with if-match and if-none-match should follow the etag.
+async fn test_stat_with_if_match(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let _ = op
+        .object(&path)
+        .write(&content)
+        .await
+        .expect("write must succeed");
+
+    let meta = op.object(&path).metadata().await?;
+    let etag = match meta.etag() {
+        Some(etag) => etag.to_string(),
+        // Skip services that don't support etag.
+        None => return Ok(()),
+    };
+
+    let meta = op
+        .object(&path)
+        .metadata_with(|op| op.with_preconditions(Preconditions::new().with_if_match(&etag)))
+        .await?;
+    assert_eq!(meta.content_length(), size as u64);
+
+    let err = op
+        .object(&path)
+        .metadata_with(|op| {
+            op.with_preconditions(Preconditions::new().with_if_match("\"invalid_etag\""))
+        })
+        .await
+        .expect_err("stat must fail");
+    assert_eq!(ErrorKind::of(&err), ErrorKind::ConditionNotMatch);
+
+    let err = op
+        .object(&path)
+        .metadata_with(|op| op.with_preconditions(Preconditions::new().with_if_none_match(&etag)))
+        .await
+        .expect_err("stat must fail");
+    assert_eq!(ErrorKind::of(&err), ErrorKind::ConditionNotMatch);
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 /// Read full content should match.
 async fn test_read_full(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -429,6 +645,47 @@ async fn test_read_with_dir_path(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Read with if-match should only succeed while etag matches.
+async fn test_read_with_if_match(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let _ = op
+        .object(&path)
+        .write(&content)
+        .await
+        .expect("write must succeed");
+
+    let meta = op.object(&path).metadata().await?;
+    let etag = match meta.etag() {
+        Some(etag) => etag.to_string(),
+        // Skip services that don't support etag.
+        None => return Ok(()),
+    };
+
+    let bs = op
+        .object(&path)
+        .read_with(|op| op.with_preconditions(Preconditions::new().with_if_match(&etag)))
+        .await?;
+    assert_eq!(size, bs.len(), "read size");
+
+    let err = op
+        .object(&path)
+        .read_with(|op| {
+            op.with_preconditions(Preconditions::new().with_if_match("\"invalid_etag\""))
+        })
+        .await
+        .expect_err("read must fail");
+    assert_eq!(ErrorKind::of(&err), ErrorKind::ConditionNotMatch);
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
 // Read a compressed file.
 #[cfg(feature = "compress")]
 async fn test_read_decompress_gzip(op: Operator) -> Result<()> {
@@ -569,6 +826,48 @@ async fn test_list_dir_with_file_path(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Walk should return all descendants.
+async fn test_walk(op: Operator) -> Result<()> {
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let paths = [
+        format!("{parent}{}", uuid::Uuid::new_v4()),
+        format!("{parent}x/{}", uuid::Uuid::new_v4()),
+        format!("{parent}x/y/{}", uuid::Uuid::new_v4()),
+    ];
+    for path in &paths {
+        debug!("Generate a random file: {}", path);
+        let (content, _) = gen_bytes();
+
+        let _ = op
+            .object(path)
+            .write(&content)
+            .await
+            .expect("write must succeed");
+    }
+
+    let mut obs = op.object(&parent).walk().await?;
+    let mut found = Vec::new();
+    while let Some(o) = obs.next().await {
+        let meta = o?.metadata().await?;
+        if meta.mode() == ObjectMode::FILE {
+            found.push(meta.path().to_string());
+        }
+    }
+    for path in &paths {
+        assert!(found.contains(path), "file {path} should be found in walk");
+    }
+
+    for path in &paths {
+        op.object(path).delete().await.expect("delete must succeed");
+    }
+    // Remove dirs from the deepest one so that they are empty while deleting.
+    for dir in [format!("{parent}x/y/"), format!("{parent}x/"), parent] {
+        op.object(&dir).delete().await.expect("delete must succeed");
+    }
+
+    Ok(())
+}
+
 // Delete existing file should succeed.
 async fn test_delete(op: Operator) -> Result<()> {
     let path = uuid::Uuid::new_v4().to_string();
@@ -612,3 +911,299 @@ async fn test_delete_not_existing(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+// Batch delete should delete all files and succeed for not existing ones.
+async fn test_batch_delete(op: Operator) -> Result<()> {
+    let paths: Vec<String> = (0..3).map(|_| uuid::Uuid::new_v4().to_string()).collect();
+    for path in &paths {
+        debug!("Generate a random file: {}", path);
+        let (content, _) = gen_bytes();
+
+        let _ = op
+            .object(path)
+            .write(&content)
+            .await
+            .expect("write must succeed");
+    }
+    let not_exist = uuid::Uuid::new_v4().to_string();
+
+    let inputs: Vec<&str> = paths
+        .iter()
+        .map(|v| v.as_str())
+        .chain([not_exist.as_str()])
+        .collect();
+    let results = op.batch().delete(&inputs).await?;
+
+    assert_eq!(results.len(), inputs.len());
+    for ((path, res), input) in results.into_iter().zip(inputs) {
+        assert_eq!(path, input);
+        assert!(res.is_ok(), "delete {path} must succeed");
+        assert!(!op.object(&path).is_exist().await?);
+    }
+
+    Ok(())
+}
+
+/// Batch delete should report every input even if paths are duplicated.
+async fn test_batch_delete_duplicated(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, _) = gen_bytes();
+
+    let _ = op
+        .object(&path)
+        .write(&content)
+        .await
+        .expect("write must succeed");
+
+    let inputs = [path.as_str(), path.as_str()];
+    let results = op.batch().delete(&inputs).await?;
+
+    assert_eq!(results.len(), inputs.len());
+    for (p, res) in results {
+        assert_eq!(p, path);
+        assert!(res.is_ok(), "delete {p} must succeed");
+    }
+    assert!(!op.object(&path).is_exist().await?);
+
+    Ok(())
+}
+
+/// Copy file should keep the source and create the target.
+async fn test_copy(op: Operator) -> Result<()> {
+    let from = uuid::Uuid::new_v4().to_string();
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let to = format!("{parent}{}", uuid::Uuid::new_v4());
+    debug!("Generate a random file: {}", &from);
+    let (content, size) = gen_bytes();
+
+    let _ = op
+        .object(&from)
+        .write(&content)
+        .await
+        .expect("write must succeed");
+
+    op.object(&from).copy_to(&to).await?;
+
+    assert!(op.object(&from).is_exist().await?);
+    let bs = op.object(&to).read().await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&from)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    op.object(&to).delete().await.expect("delete must succeed");
+    op.object(&parent)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy not exist file should return NotFound
+async fn test_copy_not_exist(op: Operator) -> Result<()> {
+    let from = uuid::Uuid::new_v4().to_string();
+    let to = uuid::Uuid::new_v4().to_string();
+
+    let result = op.object(&from).copy_to(&to).await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+
+    Ok(())
+}
+
+/// Copy file to itself should fail and keep the content.
+async fn test_copy_to_self(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes();
+
+    let _ = op
+        .object(&path)
+        .write(&content)
+        .await
+        .expect("write must succeed");
+
+    let result = op.object(&path).copy_to(&path).await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    let bs = op.object(&path).read().await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&path)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Rename file should remove the source and create the target.
+async fn test_rename(op: Operator) -> Result<()> {
+    let from = uuid::Uuid::new_v4().to_string();
+    let parent = format!("{}/", uuid::Uuid::new_v4());
+    let to = format!("{parent}{}", uuid::Uuid::new_v4());
+    debug!("Generate a random file: {}", &from);
+    let (content, size) = gen_bytes();
+
+    let _ = op
+        .object(&from)
+        .write(&content)
+        .await
+        .expect("write must succeed");
+
+    op.object(&from).rename_to(&to).await?;
+
+    assert!(!op.object(&from).is_exist().await?);
+    let bs = op.object(&to).read().await?;
+    assert_eq!(size, bs.len(), "read size");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(&bs)),
+        format!("{:x}", Sha256::digest(&content)),
+        "read content"
+    );
+
+    op.object(&to).delete().await.expect("delete must succeed");
+    op.object(&parent)
+        .delete()
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Rename not exist file should return NotFound
+async fn test_rename_not_exist(op: Operator) -> Result<()> {
+    let from = uuid::Uuid::new_v4().to_string();
+    let to = uuid::Uuid::new_v4().to_string();
+
+    let result = op.object(&from).rename_to(&to).await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+
+    Ok(())
+}
+
+/// Blocking write, read, stat and delete should work without an async context.
+async fn test_blocking_write_read(op: Operator) -> Result<()> {
+    // Blocking operations can't be called inside async context directly.
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let path = uuid::Uuid::new_v4().to_string();
+        debug!("Generate a random file: {}", &path);
+        let (content, size) = gen_bytes();
+
+        op.object(&path)
+            .blocking_write(&content)
+            .expect("blocking write must succeed");
+
+        let meta = op.object(&path).blocking_metadata()?;
+        assert_eq!(meta.mode(), ObjectMode::FILE);
+        assert_eq!(meta.content_length(), size as u64);
+
+        let bs = op.object(&path).blocking_read()?;
+        assert_eq!(size, bs.len(), "read size");
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs)),
+            format!("{:x}", Sha256::digest(&content)),
+            "read content"
+        );
+
+        let (offset, length) = gen_offset_length(size);
+        let bs = op
+            .object(&path)
+            .blocking_range_read(offset..offset + length)?;
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs)),
+            format!(
+                "{:x}",
+                Sha256::digest(&content[offset as usize..(offset + length) as usize])
+            ),
+            "read range content"
+        );
+
+        op.object(&path)
+            .blocking_delete()
+            .expect("blocking delete must succeed");
+        assert!(!op.object(&path).blocking_is_exist()?);
+        Ok(())
+    })
+    .await?
+}
+
+/// Blocking overwrite a longer object should not leave its trailing bytes.
+async fn test_blocking_overwrite(op: Operator) -> Result<()> {
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let path = uuid::Uuid::new_v4().to_string();
+        debug!("Generate a random file: {}", &path);
+        let (content, size) = gen_bytes();
+
+        op.object(&path)
+            .blocking_write(&content)
+            .expect("blocking write must succeed");
+
+        let shorter = &content[..size / 2];
+        op.object(&path)
+            .blocking_write(shorter)
+            .expect("blocking overwrite must succeed");
+
+        let meta = op.object(&path).blocking_metadata()?;
+        assert_eq!(meta.content_length(), shorter.len() as u64);
+
+        let bs = op.object(&path).blocking_read()?;
+        assert_eq!(shorter.len(), bs.len(), "read size");
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&bs)),
+            format!("{:x}", Sha256::digest(shorter)),
+            "read content"
+        );
+
+        op.object(&path)
+            .blocking_delete()
+            .expect("blocking delete must succeed");
+        Ok(())
+    })
+    .await?
+}
+
+/// Blocking list should return the created file.
+async fn test_blocking_list(op: Operator) -> Result<()> {
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let dir = format!("{}/", uuid::Uuid::new_v4());
+        let path = format!("{dir}{}", uuid::Uuid::new_v4());
+        debug!("Generate a random file: {}", &path);
+        let (content, _) = gen_bytes();
+
+        op.object(&path)
+            .blocking_write(&content)
+            .expect("blocking write must succeed");
+
+        let mut found = false;
+        for o in op.object(&dir).blocking_list()? {
+            let o = o?;
+            if o.path() == path {
+                found = true;
+                break;
+            }
+        }
+        assert!(found, "file should be found in list");
+
+        op.object(&path)
+            .blocking_delete()
+            .expect("blocking delete must succeed");
+        op.object(&dir)
+            .blocking_delete()
+            .expect("blocking delete must succeed");
+        Ok(())
+    })
+    .await?
+}
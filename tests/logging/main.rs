@@ -0,0 +1,137 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for `LoggingLayer`.
+//!
+//! Logs are captured by a global logger, so the tests live in their own
+//! binary to avoid conflicting with loggers installed by other tests.
+
+use std::io;
+
+use log::Level;
+use log::LevelFilter;
+use log::Log;
+use log::Metadata;
+use log::Record;
+use once_cell::sync::Lazy;
+use opendal::services::memory;
+use opendal::LoggingLayer;
+use opendal::Operator;
+use parking_lot::Mutex;
+
+/// Target of all logs emitted by `LoggingLayer`.
+const LOGGING_TARGET: &str = "opendal::layers::logging";
+
+/// Logger that captures all logs emitted by `LoggingLayer`.
+#[derive(Default)]
+struct CaptureLogger {
+    logs: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == LOGGING_TARGET
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.logs
+                .lock()
+                .push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Lazy<&'static CaptureLogger> = Lazy::new(|| {
+    let logger = Box::leak(Box::new(CaptureLogger::default()));
+    log::set_logger(logger).expect("set logger must succeed");
+    log::set_max_level(LevelFilter::Trace);
+    logger
+});
+
+/// Returns true if there is a log at `level` that contains all `parts`.
+fn has_log(level: Level, parts: &[&str]) -> bool {
+    LOGGER
+        .logs
+        .lock()
+        .iter()
+        .any(|(l, msg)| *l == level && parts.iter().all(|p| msg.contains(p)))
+}
+
+#[tokio::test]
+async fn test_logging_layer() -> anyhow::Result<()> {
+    Lazy::force(&LOGGER);
+    let op = Operator::new(memory::Backend::build().finish().await?).layer(LoggingLayer);
+
+    op.object("test").write("Hello, World!").await?;
+    assert_eq!(op.object("test").read().await?, b"Hello, World!");
+    assert_eq!(
+        op.object("not_exist").metadata().await.unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+
+    let blocking_op = op.clone();
+    tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        blocking_op
+            .object("blocking")
+            .blocking_write("Hello, World!")?;
+        assert_eq!(
+            blocking_op.object("blocking").blocking_read()?,
+            b"Hello, World!"
+        );
+        Ok(())
+    })
+    .await??;
+
+    for (op, path) in [
+        ("write", "test"),
+        ("read", "test"),
+        ("stat", "not_exist"),
+        ("blocking_write", "blocking"),
+        ("blocking_read", "blocking"),
+    ] {
+        let operation = format!("service=memory operation={op} path={path}");
+        assert!(has_log(Level::Debug, &[&operation, "started"]), "{op}");
+    }
+    for (op, path) in [("write", "test"), ("blocking_write", "blocking")] {
+        let operation = format!("service=memory operation={op} path={path}");
+        assert!(
+            has_log(
+                Level::Debug,
+                &[&operation, "writer closed after written 13 bytes"]
+            ),
+            "{op}"
+        );
+    }
+    for (op, path) in [("read", "test"), ("blocking_read", "blocking")] {
+        let operation = format!("service=memory operation={op} path={path}");
+        assert!(
+            has_log(
+                Level::Debug,
+                &[&operation, "reader terminated after read 13 bytes"]
+            ),
+            "{op}"
+        );
+    }
+    // NotFound is expected, so it's logged at debug level.
+    assert!(has_log(
+        Level::Debug,
+        &["operation=stat path=not_exist", "failed", "kind=NotFound"]
+    ));
+    assert!(!has_log(Level::Error, &["path=not_exist"]));
+
+    Ok(())
+}